@@ -0,0 +1,232 @@
+use std::{
+    cmp::{max, min},
+    io::{stdin, stdout, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    args::Args,
+    board::Board,
+    cell::Cell,
+    config::{Config, ConfigError},
+    engine::{self, Engine, Options},
+    error::Error,
+    game::Game,
+    locale::{self, tr, trf},
+    mode::Mode,
+    notation::{board_text, Numbering},
+    pos::Pos,
+    stats::Stats,
+};
+
+/// Default delay between the demo moves
+const DEMO_DELAY: Duration = Duration::from_millis(500);
+
+/// Plain text game for screen readers. Board is printed as rows of
+/// characters and every move and state change is announced on its own line.
+#[derive(Debug)]
+pub struct Accessible {
+    game: Game,
+    /// AI playing both sides in the demo mode
+    demo: Option<Box<dyn Engine>>,
+    delay: Duration,
+    stats: Option<Stats>,
+}
+
+impl Accessible {
+    /// Creates new [`Accessible`] game based on the arguments and config
+    pub fn new(args: &Args, config: &Config) -> Self {
+        let (w, h) = match args.size {
+            Some(size) => (size.x, size.y),
+            None => config.size.unwrap_or((3, 3)),
+        };
+        let win = args.win_len.unwrap_or(min(max(w, h), 5));
+        let temperature = match args.deterministic {
+            true => 0.,
+            false => config.ai.temperature,
+        };
+        let seed = args.get_seed();
+        let mut board = Board::new(w, h, win);
+        board.win_len =
+            board
+                .win_len
+                .with(args.win_rows, args.win_cols, args.win_diag);
+        board.points = args.points;
+        board.numbering = Numbering {
+            origin: args.origin.unwrap_or(config.origin),
+            rtl: args.rtl || config.rtl.unwrap_or_else(locale::rtl),
+        };
+        let mut game = Game::new(board, Mode::Normal).seed(seed);
+        game.blocks = args.blocks;
+        game.handicap = args.handicap;
+        Self {
+            game,
+            demo: args.demo.then(|| {
+                let options = Options {
+                    seed,
+                    temperature,
+                    time: args.ai_time,
+                    ..Options::default()
+                };
+                let name = args.ai.as_deref().unwrap_or(engine::DEFAULT);
+                let engine = args.engines.create(name, &options);
+                engine.expect("AI checked by the args")
+            }),
+            delay: args
+                .delay
+                .map(|d| Duration::from_millis(d as u64))
+                .unwrap_or(DEMO_DELAY),
+            stats: (config.stats && !args.demo).then(Stats::load),
+        }
+    }
+
+    /// Runs the game, config errors are announced first
+    pub fn run(&mut self, errors: &[ConfigError]) -> Result<(), Error> {
+        for e in errors {
+            println!("{}", trf("accessible.config-error", &[e]));
+        }
+        let size = self.game.board.size;
+        if let Err(e) = self.game.board.win_len.check(size.x, size.y) {
+            println!("{}", trf("accessible.unwinnable", &[&e]));
+        }
+        if self.game.blocks > 0 || self.game.handicap > 0 {
+            println!("{}", trf("accessible.seed", &[&self.game.seed]));
+        }
+        self.new_game();
+        match self.demo.is_some() {
+            true => self.run_demo(),
+            false => self.run_input(),
+        }?;
+        if let Some(stats) = &self.stats {
+            stats.save()?;
+        }
+        Ok(())
+    }
+
+    /// Plays games of the AI against itself until interrupted
+    fn run_demo(&mut self) -> Result<(), Error> {
+        loop {
+            thread::sleep(self.delay);
+            let Some(engine) = self.demo.as_mut() else {
+                return Ok(());
+            };
+            let player = self.game.current_player();
+            match engine.choose_move(&self.game.board, player)? {
+                Some(pos) => self.place(pos)?,
+                None => self.new_game(),
+            }
+        }
+    }
+
+    /// Reads commands from the standard input until quit or end of input
+    fn run_input(&mut self) -> Result<(), Error> {
+        let mut line = String::new();
+        loop {
+            match self.game.result() {
+                Some(_) => print!("{} ", tr("accessible.game-over")),
+                None => {
+                    let player = self.game.current_player().symbol();
+                    print!("{} ", trf("accessible.turn", &[&player]))
+                }
+            }
+            _ = stdout().flush();
+
+            line.clear();
+            if stdin().read_line(&mut line)? == 0 {
+                println!();
+                return Ok(());
+            }
+            match line.trim().to_lowercase().as_str() {
+                "" | "board" => self.print_board(),
+                "help" | "?" => Self::print_help(),
+                "restart" => self.new_game(),
+                "quit" | "q" => return Ok(()),
+                cmd => match self.game.board.parse_cell(cmd) {
+                    Some(pos) => self.place(pos)?,
+                    None => {
+                        println!("{}", trf("accessible.unknown", &[&cmd]))
+                    }
+                },
+            }
+        }
+    }
+
+    /// Starts new game and announces it
+    fn new_game(&mut self) {
+        self.game.restart(Cell::Cross);
+        let goal = match self.game.board.points {
+            true => tr("accessible.points-goal"),
+            false => tr("accessible.goal"),
+        };
+        println!(
+            "{}",
+            trf(
+                "accessible.new-game",
+                &[
+                    &self.game.board.size.x,
+                    &self.game.board.size.y,
+                    &self.game.board.win_len.describe(),
+                    &goal,
+                    &self.game.current_player().symbol(),
+                ],
+            )
+        );
+        if self.game.blocks > 0 {
+            println!("{}", tr("accessible.blocks"));
+        }
+        if !self.game.board.handicap.is_empty() {
+            let board = &self.game.board;
+            let cells: Vec<_> =
+                board.handicap.iter().map(|p| board.cell_name(p)).collect();
+            let cells = cells.join(", ");
+            let weaker = Cell::Cross.next().symbol();
+            println!("{}", trf("accessible.handicap", &[&weaker, &cells]));
+        }
+        if self.demo.is_none() {
+            println!("{}", tr("accessible.intro"));
+        }
+        self.print_board();
+    }
+
+    /// Places mark of the current player and announces the move and the
+    /// state of the game
+    fn place(&mut self, pos: Pos) -> Result<(), Error> {
+        let name = self.game.board.cell_name(&pos);
+        let player = self.game.current_player();
+        if let Err(e) = self.game.play(pos) {
+            println!("{}", trf("accessible.invalid", &[&name, &e]));
+            return Ok(());
+        }
+
+        println!("{}", trf("accessible.move", &[&player.symbol(), &name]));
+        self.print_board();
+        if self.game.board.points {
+            let lines = [Cell::Cross, Cell::Circle]
+                .map(|c| self.game.board.completed(c));
+            println!("{}", trf("accessible.lines", &[&lines[0], &lines[1]]));
+        }
+        let result = self.game.result();
+        match result {
+            Some(Cell::Empty) => println!("{}", tr("accessible.draw")),
+            Some(winner) => {
+                println!("{}", trf("accessible.wins", &[&winner.symbol()]))
+            }
+            None => {}
+        }
+        if let (Some(result), Some(stats)) = (result, self.stats.as_mut()) {
+            stats.add(result);
+        }
+        Ok(())
+    }
+
+    /// Prints the board as rows of characters with row and column names
+    fn print_board(&self) {
+        print!("{}", board_text(&self.game.board));
+    }
+
+    /// Prints all the commands
+    fn print_help() {
+        println!("{}", tr("accessible.help"));
+    }
+}
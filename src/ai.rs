@@ -0,0 +1,971 @@
+use std::{
+    cmp::Reverse,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bitboard::Bitboard,
+    board::Board,
+    book,
+    cell::Cell,
+    mcts::{self, Mcts},
+    pos::Pos,
+    rng::Rng,
+    symmetry::Symmetry,
+    table::{Bound, Entry, Table},
+    weights::{self, Weights},
+    win_len::WinLen,
+};
+
+/// Score of the winning position
+const WIN: i64 = 1_000_000_000;
+/// Score bound higher than any position score
+const INF: i64 = WIN * 2;
+/// Directions in which lines are checked
+const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+/// Number of the opening moves, which are chosen with some randomness
+const OPENING: usize = 4;
+/// Number of the best moves the random opening move is chosen from
+const OPENING_WIDTH: usize = 4;
+/// Number of cells from which the root moves are searched in parallel
+#[cfg(feature = "parallel")]
+const PARALLEL_LEN: usize = 49;
+/// Seed of the Zobrist keys, fixed so the hashes are the same every search
+const ZOBRIST_SEED: u64 = 0x5EED;
+
+/// Difficulty of the [`Ai`] player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+    /// Monte Carlo tree search, which plays out random games instead of
+    /// searching all the moves
+    Mcts,
+}
+
+/// Evaluation of a position or a move from the player's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eval {
+    /// Player can force a win
+    Win,
+    /// Opponent can force a win
+    Loss,
+    /// Heuristic score, positive when the player is better
+    Score(i64),
+}
+
+impl Eval {
+    /// Gets the evaluation from the opponent's point of view
+    pub fn flip(self) -> Self {
+        match self {
+            Eval::Win => Eval::Loss,
+            Eval::Loss => Eval::Win,
+            Eval::Score(score) => Eval::Score(-score),
+        }
+    }
+}
+
+impl From<i64> for Eval {
+    fn from(value: i64) -> Self {
+        match value {
+            s if s >= WIN => Eval::Win,
+            s if s <= -WIN => Eval::Loss,
+            s => Eval::Score(s),
+        }
+    }
+}
+
+/// Computer player choosing moves on the [`Board`]
+#[derive(Debug, Clone)]
+pub struct Ai {
+    pub difficulty: Difficulty,
+    /// Randomness of the opening moves, 0 means always the best move
+    pub temperature: f64,
+    /// Time per move of the hard AI, searches to fixed depth when not set
+    pub time: Option<Duration>,
+    rng: Rng,
+    /// Positions searched so far, reused by the following searches
+    table: Table,
+    weights: Weights,
+}
+
+impl Ai {
+    /// Creates new [`Ai`] with given difficulty, its random choices are
+    /// seeded by the current time until [`Ai::seed`] is set
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            temperature: 0.,
+            time: None,
+            rng: Rng::from_time(),
+            table: Table::default(),
+            weights: weights::current().clone(),
+        }
+    }
+
+    /// Sets randomness of the opening moves
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Sets time per move, the hard AI searches deeper until the time runs
+    /// out and plays the best move found so far
+    pub fn time(mut self, time: Option<Duration>) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Sets seed of the random choice of the opening moves
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Sets weights of the heuristic scoring the moves
+    pub fn weights(mut self, weights: Weights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets size of the transposition table in MB, 0 disables it
+    pub fn table_size(mut self, size: usize) -> Self {
+        self.table = Table::new(size);
+        self
+    }
+
+    /// Gets move the AI would play on the given board as the given player.
+    /// Opening moves are randomly chosen from the best moves, when
+    /// temperature is set. Hard AI uses opening book on the standard board.
+    pub fn best_move(&mut self, board: &Board, player: Cell) -> Option<Pos> {
+        if self.difficulty == Difficulty::Hard && board.state().is_none() {
+            if let Some(moves) = book::moves(board, player) {
+                let id = match self.temperature > 0. {
+                    true => self.rng.range(moves.len()),
+                    false => 0,
+                };
+                return moves.get(id).copied();
+            }
+        }
+
+        if self.temperature <= 0.
+            || self.difficulty == Difficulty::Mcts
+            || board.history.len() >= OPENING
+            || board.state().is_some()
+        {
+            return self.search(board, player).0;
+        }
+
+        let mut pos = self.position(board);
+        let moves = match self.difficulty {
+            Difficulty::Easy => pos.candidates(player, false),
+            Difficulty::Medium => pos.candidates(player, true),
+            Difficulty::Hard | Difficulty::Mcts => pos.root_moves(player),
+        };
+        self.table = mem::take(&mut pos.table);
+        self.pick(&moves).map(|id| Pos::from_index(id, pos.width))
+    }
+
+    /// Searches for the best move on the given board as the given player.
+    /// Returns the move and the number of searched positions.
+    pub fn search(
+        &mut self,
+        board: &Board,
+        player: Cell,
+    ) -> (Option<Pos>, usize) {
+        if board.state().is_some() {
+            return (None, 0);
+        }
+
+        let mut pos = self.position(board);
+        let id = match (self.difficulty, self.time) {
+            (Difficulty::Easy, _) => {
+                pos.candidates(player, false).first().copied()
+            }
+            (Difficulty::Medium, _) => {
+                pos.candidates(player, true).first().copied()
+            }
+            (Difficulty::Hard, Some(time)) => {
+                let (id, searched) = Ai::search_timed(pos, player, time);
+                pos = searched;
+                id
+            }
+            (Difficulty::Hard, None) => {
+                let (depth, width) = pos.limits();
+                pos.search(player, depth, width, None).map(|(id, _)| id)
+            }
+            (Difficulty::Mcts, time) => {
+                let time = time.unwrap_or(mcts::TIME);
+                let mut mcts = Mcts::new(board, &mut self.rng);
+                let (id, count) = mcts.search(player, time, usize::MAX);
+                pos.nodes = count;
+                id
+            }
+        };
+        self.table = pos.table;
+        let best = id.map(|id| Pos::from_index(id, pos.width));
+        (best, pos.nodes.max(1))
+    }
+
+    /// Evaluates the position on the given board from the point of view of
+    /// the given player on turn
+    pub fn evaluate(&mut self, board: &Board, player: Cell) -> Eval {
+        match board.state() {
+            Some(Cell::Empty) => return Eval::Score(0),
+            Some(cell) if cell == player => return Eval::Win,
+            Some(_) => return Eval::Loss,
+            None => {}
+        }
+        let mut pos = self.position(board);
+        let (depth, width) = pos.limits();
+        let score = pos
+            .search(player, depth, width, None)
+            .map_or(0, |(_, score)| score);
+        self.table = pos.table;
+        score.into()
+    }
+
+    /// Evaluates the best move and the played move of the given player on
+    /// the board, both are searched to the same depth so they can be
+    /// compared
+    pub fn rate(
+        &mut self,
+        board: &Board,
+        player: Cell,
+        played: Pos,
+    ) -> (Eval, Eval) {
+        let mut pos = self.position(board);
+        let (depth, width) = pos.limits();
+        let id = played.index(pos.width);
+        let played = -pos.negamax(id, player, depth, width, -INF, INF);
+        // Played move may be outside of the searched candidates
+        let best = pos
+            .search(player, depth, width, Some(id))
+            .map_or(played, |(_, score)| score.max(played));
+        self.table = pos.table;
+        (best.into(), played.into())
+    }
+
+    /// Creates position of the board using the AI's table and weights
+    fn position(&mut self, board: &Board) -> Position {
+        let table = mem::take(&mut self.table);
+        Position::new(board, table, self.weights.clone())
+    }
+
+    /// Searches the position on a background thread with increasing depth
+    /// until the time runs out. Returns the best move of the deepest
+    /// finished search and the searched position.
+    fn search_timed(
+        pos: Position,
+        player: Cell,
+        time: Duration,
+    ) -> (Option<usize>, Position) {
+        let deadline = Instant::now() + time;
+        let stop = pos.stop.clone();
+        let (tx, rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let mut pos = pos;
+            pos.deepen(player, tx);
+            pos
+        });
+
+        let mut best = None;
+        // Disconnects when the search finishes before the deadline
+        while let Ok(id) =
+            rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        {
+            best = Some(id);
+        }
+        stop.store(true, Ordering::Relaxed);
+        let pos = handle.join().expect("AI search thread panicked");
+        pos.stop.store(false, Ordering::Relaxed);
+
+        // Search didn't finish even the first depth
+        let best =
+            best.or_else(|| pos.candidates(player, true).first().copied());
+        (best, pos)
+    }
+
+    /// Randomly picks one of the first moves, move on index `i` has weight
+    /// `e^(-i / temperature)`
+    fn pick(&mut self, moves: &[usize]) -> Option<usize> {
+        let weights: Vec<_> = (0..moves.len().min(OPENING_WIDTH))
+            .map(|i| (-(i as f64) / self.temperature).exp())
+            .collect();
+        let mut val = self.rng.unit() * weights.iter().sum::<f64>();
+        for (id, weight) in moves.iter().zip(weights) {
+            if val < weight {
+                return Some(*id);
+            }
+            val -= weight;
+        }
+        moves.first().copied()
+    }
+}
+
+/// Lightweight board representation used for the search
+#[derive(Debug, Clone)]
+struct Position {
+    cells: Vec<Cell>,
+    /// Same cells as bits, when the board fits in them, so the lines are
+    /// checked faster. Tiny boards are searched to the end without the
+    /// evaluation, where the bits don't pay off.
+    bits: Option<Bitboard>,
+    width: usize,
+    height: usize,
+    win_len: WinLen,
+    nodes: usize,
+    /// When set, the search is stopped and its results are discarded
+    stop: Arc<AtomicBool>,
+    table: Table,
+    weights: Weights,
+    /// Zobrist keys of the cross, circle and blocked cell on each index
+    keys: Vec<[u64; 3]>,
+    /// Key of the circle being on move
+    turn: u64,
+    /// Zobrist hash of the cells mapped by each of the symmetries, the
+    /// smallest one is the hash of the canonical position
+    hashes: Vec<u64>,
+    /// Symmetries, under which the position scores the same, with the
+    /// index each cell is mapped to
+    syms: Vec<(Symmetry, Vec<u32>)>,
+}
+
+impl Position {
+    /// Creates new [`Position`] from the given board, which uses the given
+    /// transposition table and heuristic weights
+    fn new(board: &Board, mut table: Table, weights: Weights) -> Self {
+        table.prepare((board.size.x, board.size.y, board.win_len));
+        let mut rng = Rng::new(ZOBRIST_SEED);
+        let keys: Vec<_> = (0..board.cells.len())
+            .map(|_| [rng.next_u64(), rng.next_u64(), rng.next_u64()])
+            .collect();
+        // Transposed board swaps lengths of the rows and the columns
+        let syms: Vec<_> = Symmetry::all(board.size)
+            .into_iter()
+            .filter(|s| {
+                !s.transposes() || board.win_len.rows == board.win_len.cols
+            })
+            .map(|s| {
+                let map = (0..board.cells.len())
+                    .map(|id| {
+                        let pos = Pos::from_index(id, board.size.x);
+                        s.apply(pos, board.size).index(board.size.x) as u32
+                    })
+                    .collect();
+                (s, map)
+            })
+            .collect();
+        let mut pos = Self {
+            cells: board.cells.to_vec(),
+            bits: Bitboard::new(board).filter(|_| board.cells.len() > 9),
+            width: board.size.x,
+            height: board.size.y,
+            win_len: board.win_len,
+            nodes: 0,
+            stop: Arc::default(),
+            table,
+            weights,
+            keys,
+            turn: rng.next_u64(),
+            hashes: vec![0; syms.len()],
+            syms,
+        };
+        for id in 0..pos.cells.len() {
+            pos.toggle(id, pos.cells[id]);
+        }
+        pos
+    }
+
+    /// Searches with increasing depth and sends the best move after each
+    /// finished depth, until stopped or the game is searched to the end
+    fn deepen(&mut self, player: Cell, tx: Sender<usize>) {
+        let (_, width) = self.limits();
+        let mut best = None;
+        for depth in 1..=self.empty() {
+            let Some((id, score)) = self.search(player, depth, width, best)
+            else {
+                return;
+            };
+            if tx.send(id).is_err() || score >= WIN {
+                return;
+            }
+            best = Some(id);
+        }
+    }
+
+    /// Searches for the best move using negamax with alpha-beta pruning,
+    /// `first` move is searched first. Returns the move with its score,
+    /// [`None`] when the search was stopped.
+    fn search(
+        &mut self,
+        player: Cell,
+        depth: usize,
+        width: usize,
+        first: Option<usize>,
+    ) -> Option<(usize, i64)> {
+        let mut moves: Vec<_> = self
+            .candidates(player, true)
+            .into_iter()
+            .take(width)
+            .collect();
+        if let Some(i) = moves.iter().position(|m| Some(*m) == first) {
+            moves[..=i].rotate_right(1);
+        }
+
+        #[cfg(feature = "parallel")]
+        if self.cells.len() >= PARALLEL_LEN {
+            let threads =
+                thread::available_parallelism().map_or(1, |n| n.get());
+            return self
+                .search_parallel(player, depth, width, &moves, threads);
+        }
+
+        let mut best = None;
+        let mut alpha = -INF;
+        for id in moves {
+            let score = -self.negamax(id, player, depth, width, -INF, -alpha);
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(id);
+            }
+        }
+        match self.stopped() {
+            true => None,
+            false => best.map(|id| (id, alpha)),
+        }
+    }
+
+    /// Searches the moves split between the given number of threads. Each
+    /// move is searched with full window and without the table, so the
+    /// result doesn't depend on the order the threads finish in, ties go
+    /// to the earlier move.
+    #[cfg(feature = "parallel")]
+    fn search_parallel(
+        &mut self,
+        player: Cell,
+        depth: usize,
+        width: usize,
+        moves: &[usize],
+        threads: usize,
+    ) -> Option<(usize, i64)> {
+        let threads = threads.clamp(1, moves.len().max(1));
+        let table = mem::take(&mut self.table);
+        let workers: Vec<_> = (0..threads).map(|_| self.clone()).collect();
+        self.table = table;
+
+        let mut scores = vec![-INF; moves.len()];
+        thread::scope(|s| {
+            let handles: Vec<_> = workers
+                .into_iter()
+                .enumerate()
+                .map(|(i, mut pos)| {
+                    s.spawn(move || {
+                        pos.nodes = 0;
+                        let scores: Vec<_> = (i..moves.len())
+                            .step_by(threads)
+                            .map(|j| {
+                                let score = pos.negamax(
+                                    moves[j], player, depth, width, -INF, INF,
+                                );
+                                (j, -score)
+                            })
+                            .collect();
+                        (scores, pos.nodes)
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let (res, nodes) =
+                    handle.join().expect("AI search thread panicked");
+                self.nodes += nodes;
+                for (j, score) in res {
+                    scores[j] = score;
+                }
+            }
+        });
+
+        if self.stopped() {
+            return None;
+        }
+        let (mut best, mut alpha) = (None, -INF);
+        for (id, score) in moves.iter().zip(scores) {
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(*id);
+            }
+        }
+        best.map(|id| (id, alpha))
+    }
+
+    /// Gets the most promising moves sorted by their search score. Losing
+    /// moves are left out unless all the moves lose, winning move is
+    /// returned alone.
+    fn root_moves(&mut self, player: Cell) -> Vec<usize> {
+        let (depth, width) = self.limits();
+        let mut moves: Vec<_> = self
+            .candidates(player, true)
+            .into_iter()
+            .take(width)
+            .map(|id| (-self.negamax(id, player, depth, width, -INF, INF), id))
+            .collect();
+        moves.sort_by_key(|(score, _)| Reverse(*score));
+
+        let Some(best) = moves.first().map(|(s, _)| *s) else {
+            return vec![];
+        };
+        if best >= WIN || best <= -WIN {
+            moves.truncate(1);
+        }
+        moves.retain(|(s, _)| *s > -WIN || *s == best);
+        moves.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Gets search depth and number of moves searched in each position
+    fn limits(&self) -> (usize, usize) {
+        match self.cells.len() {
+            0..=9 => (self.empty(), usize::MAX),
+            10..=25 => (4, 10),
+            _ => (3, 8),
+        }
+    }
+
+    /// Plays the move on given index and evaluates resulting position from
+    /// the opponent's point of view
+    fn negamax(
+        &mut self,
+        id: usize,
+        player: Cell,
+        depth: usize,
+        width: usize,
+        alpha: i64,
+        beta: i64,
+    ) -> i64 {
+        self.nodes += 1;
+        self.place(id, player);
+        let score = if self.wins_at(id) {
+            -WIN - depth as i64
+        } else if self.is_full() {
+            0
+        } else if depth <= 1 {
+            -self.evaluate(player)
+        } else {
+            self.node(player.next(), depth - 1, width, alpha, beta)
+        };
+        self.place(id, Cell::Empty);
+        score
+    }
+
+    /// Searches moves of the given player in the current position. Result
+    /// is stored in the transposition table, so the position doesn't have
+    /// to be searched again.
+    fn node(
+        &mut self,
+        player: Cell,
+        depth: usize,
+        width: usize,
+        mut alpha: i64,
+        mut beta: i64,
+    ) -> i64 {
+        if self.stopped() {
+            return 0;
+        }
+
+        let turn = match player {
+            Cell::Circle => self.turn,
+            _ => 0,
+        };
+        let (hash, sym) = self.canonical();
+        let key = hash ^ turn;
+        let orig_alpha = alpha;
+        let entry = self.table.get(key);
+        if let Some(entry) = entry.filter(|e| e.depth as usize >= depth) {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower => alpha = alpha.max(entry.score),
+                Bound::Upper => beta = beta.min(entry.score),
+            }
+            if alpha >= beta {
+                return entry.score;
+            }
+        }
+
+        let mut moves: Vec<_> = self
+            .candidates(player, true)
+            .into_iter()
+            .take(width)
+            .collect();
+        // Tries the best move of the previous search first
+        let prev = entry.and_then(|e| e.best).map(|b| self.revert(sym, b));
+        if let Some(i) = moves.iter().position(|m| Some(*m) == prev) {
+            moves[..=i].rotate_right(1);
+        }
+
+        let (mut best, mut best_id) = (-INF, None);
+        for next in moves {
+            let score =
+                -self.negamax(next, player, depth, width, -beta, -alpha);
+            if score > best {
+                best = score;
+                best_id = Some(next as u32);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if self.stopped() {
+            return 0;
+        }
+        let bound = if best <= orig_alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.store(Entry {
+            key,
+            score: best,
+            best: best_id.map(|id| self.syms[sym].1[id as usize]),
+            depth: depth.min(u8::MAX as usize) as u8,
+            bound,
+        });
+        best
+    }
+
+    /// Checks whether the search was stopped
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Places the cell on given index, empty cell removes the current one
+    fn place(&mut self, id: usize, cell: Cell) {
+        self.toggle(id, self.cells[id]);
+        self.toggle(id, cell);
+        self.cells[id] = cell;
+        if let Some(bits) = &mut self.bits {
+            bits.set(id, cell);
+        }
+    }
+
+    /// Gets number of the empty cells
+    fn empty(&self) -> usize {
+        match &self.bits {
+            Some(bits) => bits.empty().count(),
+            None => self.cells.iter().filter(|c| **c == Cell::Empty).count(),
+        }
+    }
+
+    /// Checks whether there's no empty cell
+    fn is_full(&self) -> bool {
+        match &self.bits {
+            Some(bits) => bits.is_full(),
+            None => !self.cells.contains(&Cell::Empty),
+        }
+    }
+
+    /// Adds or removes the cell on given index from the hash
+    fn toggle(&mut self, id: usize, cell: Cell) {
+        let key = match cell {
+            Cell::Cross => 0,
+            Cell::Circle => 1,
+            Cell::Blocked => 2,
+            Cell::Empty => return,
+        };
+        for (hash, (_, map)) in self.hashes.iter_mut().zip(&self.syms) {
+            *hash ^= self.keys[map[id] as usize][key];
+        }
+    }
+
+    /// Gets hash of the canonical position and index of the symmetry
+    /// mapping the position to it
+    fn canonical(&self) -> (u64, usize) {
+        self.hashes
+            .iter()
+            .enumerate()
+            .map(|(id, hash)| (*hash, id))
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Maps index of the cell in the canonical position back to the
+    /// position, the position is mapped to it by the given symmetry
+    fn revert(&self, sym: usize, id: u32) -> usize {
+        let size = Pos::new(self.width, self.height);
+        let pos = Pos::from_index(id as usize, self.width);
+        self.syms[sym].0.revert(pos, size).index(self.width)
+    }
+
+    /// Gets empty cells worth playing, sorted from the most promising one.
+    /// When `defend` is set, opponent's threats are considered as well.
+    fn candidates(&self, player: Cell, defend: bool) -> Vec<usize> {
+        let ids: Vec<_> = match &self.bits {
+            Some(bits) if self.cells.len() > 9 => bits.near(),
+            _ => (0..self.cells.len())
+                .filter(|id| self.cells[*id] == Cell::Empty)
+                .filter(|id| self.cells.len() <= 9 || self.near(*id))
+                .collect(),
+        };
+        let mut moves: Vec<_> = ids
+            .into_iter()
+            .map(|id| {
+                let mut score = self.cell_score(id, player);
+                if defend {
+                    let defence = self.cell_score(id, player.next());
+                    score += defence * self.weights.defend / 100;
+                }
+                (score, id)
+            })
+            .collect();
+
+        if moves.is_empty() {
+            let center = self.width / 2 + self.height / 2 * self.width;
+            return match self.cells.get(center) {
+                Some(Cell::Empty) => vec![center],
+                _ => self
+                    .cells
+                    .iter()
+                    .position(|c| *c == Cell::Empty)
+                    .into_iter()
+                    .collect(),
+            };
+        }
+
+        moves.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        moves.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Checks whether there is any mark next to the cell on given index
+    fn near(&self, id: usize) -> bool {
+        let (x, y) = ((id % self.width) as isize, (id / self.width) as isize);
+        (-1..=1).any(|dy| {
+            (-1..=1).any(|dx| {
+                self.get(x + dx, y + dy)
+                    .is_some_and(|c| c == Cell::Cross || c == Cell::Circle)
+            })
+        })
+    }
+
+    /// Gets score of placing player on the given cell based on the lines
+    /// going through it
+    fn cell_score(&self, id: usize, player: Cell) -> i64 {
+        let (x, y) = ((id % self.width) as isize, (id / self.width) as isize);
+        let mut score = 0;
+        for (dx, dy) in DIRS {
+            let fwd = self.run(x, y, dx, dy, player);
+            let bwd = self.run(x, y, -dx, -dy, player);
+            let win_len = self.win_len.get((dx, dy));
+            let len = fwd.0 + bwd.0 + 1;
+            if len >= win_len {
+                return WIN;
+            }
+
+            let space = fwd.1 + bwd.1 + 1;
+            if space < win_len {
+                continue;
+            }
+            let open = (fwd.1 > fwd.0) as usize + (bwd.1 > bwd.0) as usize;
+            score += self.weights.line(len) * self.weights.open[open];
+        }
+        score
+    }
+
+    /// Gets number of consecutive player marks and number of cells not
+    /// blocked by the opponent in the given direction from the given cell
+    fn run(
+        &self,
+        x: isize,
+        y: isize,
+        dx: isize,
+        dy: isize,
+        player: Cell,
+    ) -> (usize, usize) {
+        let (mut marks, mut space) = (0, 0);
+        let mut consecutive = true;
+        for i in 1..self.win_len.get((dx, dy)) as isize {
+            match self.get(x + dx * i, y + dy * i) {
+                Some(c) if c == player => {
+                    marks += consecutive as usize;
+                    space += 1;
+                }
+                Some(Cell::Empty) => {
+                    consecutive = false;
+                    space += 1;
+                }
+                _ => break,
+            }
+        }
+        (marks, space)
+    }
+
+    /// Evaluates position from the given player's point of view
+    fn evaluate(&self, player: Cell) -> i64 {
+        if let Some(bits) = &self.bits {
+            return bits.evaluate(player);
+        }
+        let mut score = 0;
+        for y in 0..self.height as isize {
+            for x in 0..self.width as isize {
+                for (dx, dy) in DIRS {
+                    score += self.window_score(x, y, dx, dy, player);
+                }
+            }
+        }
+        score
+    }
+
+    /// Gets score of the window of win length starting at given position
+    fn window_score(
+        &self,
+        x: isize,
+        y: isize,
+        dx: isize,
+        dy: isize,
+        player: Cell,
+    ) -> i64 {
+        let (mut mine, mut theirs) = (0, 0);
+        for i in 0..self.win_len.get((dx, dy)) as isize {
+            match self.get(x + dx * i, y + dy * i) {
+                Some(Cell::Empty) => {}
+                Some(c) if c == player => mine += 1,
+                Some(Cell::Blocked) | None => return 0,
+                Some(_) => theirs += 1,
+            }
+        }
+        match (mine, theirs) {
+            (0, 0) => 0,
+            (m, 0) => 4_i64.pow(m),
+            (0, t) => -(4_i64.pow(t)),
+            _ => 0,
+        }
+    }
+
+    /// Checks whether the mark on the given index completes a line
+    fn wins_at(&self, id: usize) -> bool {
+        if let Some(bits) = &self.bits {
+            return bits.wins_at(id);
+        }
+        let player = self.cells[id];
+        let (x, y) = ((id % self.width) as isize, (id / self.width) as isize);
+        DIRS.iter().any(|(dx, dy)| {
+            self.run(x, y, *dx, *dy, player).0
+                + self.run(x, y, -dx, -dy, player).0
+                + 1
+                >= self.win_len.get((*dx, *dy))
+        })
+    }
+
+    /// Gets cell on given position, [`None`] when out of the board
+    fn get(&self, x: isize, y: isize) -> Option<Cell> {
+        if x < 0 || y < 0 || x as usize >= self.width {
+            return None;
+        }
+        self.cells
+            .get(x as usize + y as usize * self.width)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symmetric_positions_share_entry() {
+        let position = |moves: &[(usize, usize)]| {
+            let mut board = Board::new(4, 4, 3);
+            board.win_len = board.win_len.with(Some(4), None, None);
+            for (i, (x, y)) in moves.iter().enumerate() {
+                let cell = [Cell::Cross, Cell::Circle][i % 2];
+                board.set(cell, *x, *y).unwrap();
+            }
+            Position::new(&board, Table::new(0), Weights::default())
+        };
+        let pos = position(&[(0, 0), (1, 2)]);
+        let (hash, sym) = pos.canonical();
+        // Flipped horizontally and vertically
+        let flipped = position(&[(3, 3), (2, 1)]);
+        assert_eq!(flipped.canonical().0, hash);
+        // Transposed, but rows and columns need different lengths
+        let transposed = position(&[(0, 0), (2, 1)]);
+        assert_ne!(transposed.canonical().0, hash);
+
+        let id = Pos::new(3, 1).index(4);
+        let canonical = pos.syms[sym].1[id];
+        assert_eq!(pos.revert(sym, canonical), id);
+    }
+
+    #[test]
+    fn bitboard_matches_cells() {
+        let mut rng = Rng::new(3);
+        for (w, h, win) in [(5, 5, 4), (7, 7, 4), (15, 15, 5), (20, 5, 4)] {
+            let mut board = Board::new(w, h, win);
+            let mut player = Cell::Cross;
+            while board.history.len() < 12 && board.state().is_none() {
+                let id = rng.range(w * h);
+                if board.set(player, id % w, id / w).is_err() {
+                    continue;
+                }
+                let bits =
+                    Position::new(&board, Table::new(0), Weights::default());
+                let mut cells =
+                    Position::new(&board, Table::new(0), Weights::default());
+                cells.bits = None;
+                for p in [Cell::Cross, Cell::Circle] {
+                    assert_eq!(bits.evaluate(p), cells.evaluate(p));
+                    assert_eq!(
+                        bits.candidates(p, true),
+                        cells.candidates(p, true)
+                    );
+                }
+                assert_eq!(bits.wins_at(id), cells.wins_at(id));
+                player = player.next();
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_search_matches_sequential() {
+        let mut rng = Rng::new(5);
+        for _ in 0..4 {
+            let mut board = Board::new(5, 5, 4);
+            let mut player = Cell::Cross;
+            for _ in 0..6 {
+                let id = rng.range(25);
+                if board.set(player, id % 5, id / 5).is_ok() {
+                    player = player.next();
+                }
+            }
+            if board.state().is_some() {
+                continue;
+            }
+
+            let mut pos =
+                Position::new(&board, Table::new(0), Weights::default());
+            let (depth, width) = pos.limits();
+            let moves: Vec<_> = pos
+                .candidates(player, true)
+                .into_iter()
+                .take(width)
+                .collect();
+            let best = pos.search(player, depth, width, None);
+            for threads in [1, 3] {
+                let res =
+                    pos.search_parallel(player, depth, width, &moves, threads);
+                assert_eq!(res, best);
+            }
+        }
+    }
+}
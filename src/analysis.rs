@@ -0,0 +1,241 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use termint::{
+    buffer::Buffer,
+    geometry::{Constraint, Coords},
+    widgets::{Block, StrSpanExtension, Widget},
+};
+
+use crate::{
+    ai::{Ai, Eval},
+    board::Board,
+    cell::Cell,
+    locale::{tr, trf},
+    pos::Pos,
+    theme::Theme,
+};
+
+/// Move losing less than this percentage of the best move's score is still
+/// accurate
+const MARGIN: i64 = 10;
+/// Maximum number of the listed missed wins
+const MAX_MISSED: usize = 3;
+
+/// Review of the finished game by the engine
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Analysis {
+    /// Moves of the game
+    moves: Vec<(Cell, Pos)>,
+    /// Index of the first move of the losing player, after which the
+    /// opponent could force a win
+    pub blunder: Option<usize>,
+    /// Indexes of the moves, which gave up a forced win
+    pub missed: Vec<usize>,
+    /// Percentage of the accurate moves of cross and circle
+    pub accuracy: [Option<usize>; 2],
+}
+
+impl Analysis {
+    /// Analyses the moves of the finished game on the given board by
+    /// replaying them from its starting position
+    pub fn new(board: &Board, ai: &mut Ai) -> Self {
+        let mut replay = Board::new(board.size.x, board.size.y, 0);
+        replay.win_len = board.win_len;
+        let played: Vec<_> = board.history.iter().map(|(_, p)| *p).collect();
+        for (id, cell) in board.cells.occupied() {
+            let pos = Pos::from_index(id, board.size.x);
+            // Blocks and handicap marks stay, only the moves are replayed
+            if !played.contains(&pos) {
+                replay.cells.set(id, cell);
+            }
+        }
+
+        let mut res = Self {
+            moves: board.history.clone(),
+            ..Self::default()
+        };
+        let loser = board.state().map(|s| s.next());
+        let mut accurate = [(0, 0); 2];
+        for (i, (cell, pos)) in board.history.iter().enumerate() {
+            let (best, eval) = ai.rate(&replay, *cell, *pos);
+            if best == Eval::Win && eval != Eval::Win {
+                res.missed.push(i);
+            }
+            let lost = best != Eval::Loss && eval == Eval::Loss;
+            if lost && res.blunder.is_none() && loser == Some(*cell) {
+                res.blunder = Some(i);
+            }
+
+            let id = (*cell == Cell::Circle) as usize;
+            accurate[id].0 += Analysis::accurate(best, eval) as usize;
+            accurate[id].1 += 1;
+            if replay.set(*cell, pos.x, pos.y).is_err() {
+                break;
+            }
+        }
+        res.accuracy = accurate
+            .map(|(acc, all)| (all > 0).then(|| (acc * 100 + all / 2) / all));
+        res
+    }
+
+    /// Checks whether the game on the board can be analysed, moves of the
+    /// variants can't be replayed by the engine
+    pub fn supported(board: &Board) -> bool {
+        board.standard() && !board.history.is_empty()
+    }
+
+    /// Gets the panel showing the analysis
+    pub fn panel(&self, board: &Board) -> AnalysisPanel {
+        let name = |i: usize| {
+            let (cell, pos) = self.moves[i];
+            format!("{}. {} {}", i + 1, cell.symbol(), board.cell_name(&pos))
+        };
+        let accuracy = |id: usize| match self.accuracy[id] {
+            Some(acc) => format!("{acc}%"),
+            None => "-".into(),
+        };
+        let mut missed: Vec<_> = self
+            .missed
+            .iter()
+            .take(MAX_MISSED)
+            .map(|i| name(*i))
+            .collect();
+        if self.missed.len() > MAX_MISSED {
+            missed.push(format!("+{}", self.missed.len() - MAX_MISSED));
+        }
+        if missed.is_empty() {
+            missed.push(tr("analysis.none").into());
+        }
+
+        let lines = vec![
+            trf("analysis.accuracy", &[&accuracy(0), &accuracy(1)]),
+            trf(
+                "analysis.blunder",
+                &[&self
+                    .blunder
+                    .map(name)
+                    .unwrap_or(tr("analysis.none").into())],
+            ),
+            trf("analysis.missed", &[&missed.join(", ")]),
+        ];
+        AnalysisPanel {
+            lines,
+            theme: board.theme,
+        }
+    }
+
+    /// Checks whether the played move was as good as the best one or
+    /// worse only by a small margin
+    fn accurate(best: Eval, played: Eval) -> bool {
+        match (best, played) {
+            (_, Eval::Win) | (Eval::Loss, _) => true,
+            (Eval::Score(best), Eval::Score(played)) => {
+                played >= best - best.abs() * MARGIN / 100
+            }
+            _ => false,
+        }
+    }
+}
+
+/// [`Analysis`] computed on a background thread, so the game can be
+/// continued meanwhile
+#[derive(Debug)]
+pub struct Analyzing {
+    rx: Receiver<(Ai, Analysis)>,
+}
+
+impl Analyzing {
+    /// Starts analysing the finished game on the given board with the
+    /// given engine
+    pub fn start(mut ai: Ai, board: Board) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let analysis = Analysis::new(&board, &mut ai);
+            _ = tx.send((ai, analysis));
+        });
+        Self { rx }
+    }
+
+    /// Gets the engine back with the analysis, when it's finished
+    pub fn result(&self) -> Option<(Ai, Analysis)> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Panel with the [`Analysis`] rendered over the game
+#[derive(Debug, Clone)]
+pub struct AnalysisPanel {
+    lines: Vec<String>,
+    theme: Theme,
+}
+
+impl Widget for AnalysisPanel {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut block = Block::vertical()
+            .title(tr("analysis.title").fg(self.theme.accent))
+            .border_color(self.theme.dim);
+        for line in self.lines.iter() {
+            block.add_child(line.as_str(), Constraint::Length(1));
+        }
+        block.add_child(
+            tr("analysis.keys").fg(self.theme.dim),
+            Constraint::Length(1),
+        );
+        block.render(buffer);
+    }
+
+    fn height(&self, _size: &Coords) -> usize {
+        self.lines.len() + 3
+    }
+
+    fn width(&self, _size: &Coords) -> usize {
+        self.lines.iter().map(|l| l.len()).max().unwrap_or_default() + 2
+    }
+}
+
+impl From<AnalysisPanel> for Box<dyn Widget> {
+    fn from(value: AnalysisPanel) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ai::Difficulty;
+
+    use super::*;
+
+    #[test]
+    fn finds_blunder_and_missed_win() {
+        let mut board = Board::new(3, 3, 3);
+        // Circle's edge reply loses, cross then misses the immediate win
+        // and wins only after circle doesn't block
+        let moves = [
+            (1, 1),
+            (1, 0),
+            (0, 0),
+            (2, 2),
+            (0, 2),
+            (0, 1),
+            (1, 2),
+            (2, 1),
+            (2, 0),
+        ];
+        let mut player = Cell::Cross;
+        for (x, y) in moves {
+            board.set(player, x, y).unwrap();
+            player = player.next();
+        }
+        assert_eq!(board.state(), Some(Cell::Cross));
+
+        let analysis = Analysis::new(&board, &mut Ai::new(Difficulty::Hard));
+        assert_eq!(analysis.blunder, Some(1));
+        assert_eq!(analysis.missed, [6]);
+        assert_eq!(analysis.accuracy, [Some(80), Some(50)]);
+        let panel = analysis.panel(&board);
+        assert_eq!(panel.lines[1], "First blunder: 2. O b1");
+    }
+}
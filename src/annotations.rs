@@ -0,0 +1,60 @@
+use crate::pos::Pos;
+
+/// Temporary annotations drawn on the board by spectators
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    /// Whether selected cell is controlled by the spectator
+    pub active: bool,
+    /// Start of the line being drawn
+    pub anchor: Option<Pos>,
+    pub cells: Vec<Pos>,
+    pub lines: Vec<(Pos, Pos)>,
+}
+
+impl Annotations {
+    /// Highlights given cell or removes its highlight
+    pub fn toggle_cell(&mut self, pos: Pos) {
+        match self.cells.iter().position(|c| *c == pos) {
+            Some(id) => _ = self.cells.remove(id),
+            None => self.cells.push(pos),
+        }
+    }
+
+    /// Starts the line on given cell or finishes the started line
+    pub fn line_point(&mut self, pos: Pos) {
+        match self.anchor.take() {
+            Some(start) if start != pos => self.lines.push((start, pos)),
+            Some(_) => {}
+            None => self.anchor = Some(pos),
+        }
+    }
+
+    /// Removes all the annotations
+    pub fn clear(&mut self) {
+        self.anchor = None;
+        self.cells.clear();
+        self.lines.clear();
+    }
+
+    /// Gets cells the line between given cells goes through
+    pub fn line_cells(start: Pos, end: Pos) -> Vec<Pos> {
+        let (x0, y0) = (start.x as isize, start.y as isize);
+        let (x1, y1) = (end.x as isize, end.y as isize);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs());
+        (0..=steps)
+            .map(|i| {
+                let x = x0 + div_round((x1 - x0) * i, steps);
+                let y = y0 + div_round((y1 - y0) * i, steps);
+                Pos::new(x as usize, y as usize)
+            })
+            .collect()
+    }
+}
+
+/// Divides and rounds to the nearest integer
+fn div_round(num: isize, den: isize) -> isize {
+    if den == 0 {
+        return 0;
+    }
+    (2 * num + den * num.signum()) / (2 * den)
+}
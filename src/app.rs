@@ -1,62 +1,350 @@
 use std::{
     cmp::{max, min},
-    io::{stdout, Write},
-    time::Duration,
+    fs,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use crossterm::{
-    event::{poll, read, Event, KeyCode, KeyEvent, KeyModifiers},
-    terminal::{disable_raw_mode, enable_raw_mode},
-};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use termint::{
     enums::{Color, Modifier},
     geometry::{Constraint, Coords, TextAlign},
     term::Term,
-    widgets::{Layout, Paragraph, Spacer, StrSpanExtension, Text, Widget},
+    widgets::{
+        Block, Layout, Paragraph, Spacer, StrSpanExtension, Text, Widget,
+    },
+};
+
+use crate::{
+    ai::{Ai, Difficulty},
+    analysis::{Analysis, Analyzing},
+    args::Args,
+    assist::{Assist, Hinting},
+    board::{Board, BoardEvent, Illegal},
+    board_row::BoardRow,
+    bot::Bot,
+    cell::Cell,
+    clipboard,
+    color_mode::ColorMode,
+    command_line::{CommandLine, LineCommand},
+    config::{Config, ConfigError},
+    confirm::Confirm,
+    controls::{Controls, PlayerKeys},
+    daily::Daily,
+    demo::Demo,
+    engine::{self, Options, Registry},
+    error::Error,
+    eval_bar::{self, EvalBar},
+    export::export,
+    game::{self, Game},
+    guard::TermGuard,
+    input::{Action, Input},
+    locale::{self, tr, trf},
+    marks::Marks,
+    mode::Mode,
+    move_log::{MoveLog, LOG_WIDTH},
+    notation::{board_text, Numbering, Origin},
+    overlay::Overlay,
+    palette::{Command, Palette},
+    pos::Pos,
+    record::{self, Record},
+    recording::{Playback, Recording},
+    replay::Replay,
+    screen::Screen,
+    series::Series,
+    signals,
+    slots::{self, LoadScreen},
+    sound::{Sound, SoundEvent, SoundMode},
+    start_rule::StartRule,
+    stats::Stats,
+    status::Status,
+    table,
+    tournament::Tournament,
+    wizard::Wizard,
 };
 
-use crate::{board::Board, cell::Cell, error::Error};
+/// Maximum count prefix of the movement
+const MAX_COUNT: usize = 999;
+/// Minimum number of cells in each direction shown by the board view
+const MIN_VIEW: usize = 3;
+/// Minimum time between two rendered frames
+const FRAME: Duration = Duration::from_millis(16);
+/// Default longest wait for events while something animates
+const TICK: Duration = Duration::from_millis(100);
+/// Longest wait for events when nothing animates
+const IDLE: Duration = Duration::from_millis(250);
 
 /// App struct containing the main loop, key listeners and rendering
 #[derive(Debug)]
 pub struct App {
-    pub term: Term,
-    pub board: Board,
-    pub player: Cell,
-    pub score: (usize, usize),
+    pub term: Screen,
+    pub game: Game,
+    pub palette: Option<Palette>,
+    /// List of the save slots to load the game from, when it's opened
+    pub load_screen: Option<LoadScreen>,
+    /// Name of the slot the game in progress is saved to on quit
+    pub save_as: Option<String>,
+    pub sound: Sound,
+    pub demo: Option<Demo>,
+    pub config_errors: Vec<ConfigError>,
+    pub log: Option<MoveLog>,
+    pub controls: Controls,
+    /// Own keys of the first and the second player at one keyboard
+    pub players: Option<PlayerKeys>,
+    pub stats: Option<Stats>,
+    pub wizard: Option<Wizard>,
+    /// Whether the second player is asked to swap sides
+    pub swap_prompt: bool,
+    /// Hints and risky move confirmation of the assisted mode
+    pub assist: Option<Assist>,
+    /// Hint of the assisted mode searched in the background
+    pub hinting: Option<Hinting>,
+    /// Move letting the opponent win, which has to be confirmed
+    pub confirm_move: Option<Pos>,
+    pub relative: bool,
+    /// Color mode given by the arguments
+    pub color_mode: Option<ColorMode>,
+    /// Mark style given by the arguments
+    pub marks: Option<Marks>,
+    /// Numbering origin given by the arguments
+    pub origin: Option<Origin>,
+    /// Whether the right-to-left labels are forced by the arguments
+    pub rtl: bool,
+    /// Sound mode given by the arguments
+    pub sound_mode: Option<SoundMode>,
+    /// Count prefix of the next movement
+    pub count: usize,
+    /// Path finished games are exported to
+    pub export: Option<String>,
+    /// Messages shown in the status bar instead of the help
+    pub status: Status,
+    /// Whether the rules screen is shown
+    pub show_rules: bool,
+    /// Name of the cell typed in the placement prompt, when it's opened
+    pub prompt: Option<String>,
+    /// Command typed after `:`, when the command line is opened
+    pub command_line: Option<CommandLine>,
+    pub tournament: Option<Tournament>,
+    /// Whether the tournament bracket is shown
+    pub show_bracket: bool,
+    /// Time the game was paused at, board is hidden while paused
+    pub paused: Option<Instant>,
+    /// Whether quitting the game in progress has to be confirmed
+    pub confirm_quit: bool,
+    /// Whether playing the game, which can't be won, has to be confirmed
+    pub confirm_win: bool,
+    /// AI playing circle against the player in the daily challenge or the
+    /// external bot
+    pub opponent: Option<Demo>,
+    /// AI engines the opponent can be chosen from
+    pub engines: Registry,
+    /// Review of the finished game, when it's computed
+    pub analysis: Option<Analysis>,
+    /// Analysis of the finished game running in the background
+    pub analyzing: Option<Analyzing>,
+    /// Hard AI giving the hints and analysing the finished games, so its
+    /// table is reused. It's moved to the search thread while searching.
+    pub hard_ai: Option<Ai>,
+    /// Whether the analysis panel is shown
+    pub show_analysis: bool,
+    /// Engine's evaluation shown beside the board, when it's enabled
+    pub eval_bar: Option<EvalBar>,
+    /// Moves of the imported game, which are still to be replayed
+    pub replay: Option<Replay>,
+    /// Path of the file the actions are recorded to on quit
+    pub record_path: Option<String>,
+    /// Actions of the session recorded so far
+    pub recording: Option<Recording>,
+    /// Recorded session, which is being played back
+    pub playback: Option<Playback>,
+    /// Whether the screen changed since the last rendered frame
+    pub dirty: bool,
+    /// Time the last frame was rendered at
+    pub last_frame: Instant,
+    /// Longest wait for events while something animates
+    pub tick: Duration,
 }
 
 impl App {
-    /// Creates new [`App`] with board with given size and win length
-    pub fn new(size: Option<Coords>, win: Option<usize>) -> Self {
-        let (w, h) = match size {
-            Some(c) => (c.x, c.y),
-            _ => App::fullscreen_size(),
+    /// Creates new [`App`] based on given arguments and config. Config
+    /// errors are displayed before the game starts.
+    pub fn new(
+        args: &Args,
+        config: Config,
+        config_errors: Vec<ConfigError>,
+    ) -> Self {
+        let temperature = match args.deterministic {
+            true => 0.,
+            false => config.ai.temperature,
+        };
+        // Seed given to the daily challenge selects its day
+        let daily = args
+            .daily
+            .then(|| args.seed.map_or_else(Daily::today, Daily::new));
+        let seed = match &daily {
+            Some(daily) => daily.day,
+            None => args.get_seed(),
+        };
+        let options = Options {
+            seed,
+            temperature,
+            time: args.ai_time,
+            table_size: args.tt_size.unwrap_or(table::DEFAULT_SIZE),
+        };
+        let engine = || {
+            let name = args.ai.as_deref().unwrap_or(engine::DEFAULT);
+            let engine = args.engines.create(name, &options);
+            engine.expect("AI checked by the args")
+        };
+        let demo = args.demo.then(|| {
+            let delay = args.delay.map(|d| Duration::from_millis(d as u64));
+            Demo::new(engine(), delay)
+        });
+        let opponent = daily
+            .as_ref()
+            .map(|_| Demo::new(engine(), Some(Duration::ZERO)));
+
+        let wizard = (!args.demo && !Config::exists()).then(Wizard::new);
+
+        let mut game = Game::new(Board::new(3, 3, 3), args.mode).seed(seed);
+        game.blocks = daily.as_ref().map_or(args.blocks, |d| d.blocks);
+        game.handicap = args.handicap;
+        game.size = match args.mode {
+            Mode::Notakto => Some(Pos::new(3, 3)),
+            _ => daily.as_ref().map(|d| d.size).or(args.size),
+        };
+        game.win_len = match args.mode {
+            Mode::Notakto => Some(3),
+            _ => daily.as_ref().map(|d| d.win_len).or(args.win_len),
+        };
+        game.win_dirs = (args.win_rows, args.win_cols, args.win_diag);
+        game.swap_rule = args.swap_rule;
+        game.points = args.points;
+        game.infinite = args.infinite;
+        game.pieces = args.pieces;
+        game.decay = args.decay;
+        game.fog = args.fog;
+        game.series = args.best_of.map(Series::new);
+        game.board_count = args.boards.unwrap_or(game::BOARDS);
+        game.daily = daily;
+
+        let mut app = Self {
+            term: Screen::new().small_screen(App::small_screen()),
+            game,
+            palette: None,
+            load_screen: None,
+            // Loaded game is saved back to its slot
+            save_as: args.save_as.clone().or(args.load.clone()),
+            sound: Sound::default(),
+            demo,
+            config_errors,
+            log: None,
+            controls: Controls::default(),
+            players: None,
+            stats: None,
+            wizard,
+            swap_prompt: false,
+            assist: args.assist.map(Assist::new),
+            hinting: None,
+            confirm_move: None,
+            relative: args.relative,
+            marks: args.marks,
+            origin: args.origin,
+            rtl: args.rtl,
+            color_mode: args.color_mode,
+            sound_mode: args.sound,
+            count: 0,
+            export: args.export.clone(),
+            status: Status::default(),
+            show_rules: false,
+            prompt: None,
+            command_line: None,
+            tournament: None,
+            show_bracket: false,
+            paused: None,
+            confirm_quit: false,
+            confirm_win: false,
+            opponent,
+            engines: args.engines.clone(),
+            analysis: None,
+            analyzing: None,
+            hard_ai: None,
+            show_analysis: false,
+            eval_bar: None,
+            replay: None,
+            record_path: args.record.clone(),
+            recording: args.record.as_ref().map(|_| Recording::new(seed)),
+            playback: None,
+            dirty: true,
+            last_frame: Instant::now(),
+            tick: TICK,
         };
-        let win = win.unwrap_or(min(max(w, h), 5));
+        app.apply_config(config);
+        app
+    }
+
+    /// Plays against the given bot, which plays circle
+    pub fn play_bot(&mut self, bot: Bot) {
+        self.opponent = Some(Demo::new(Box::new(bot), Some(Duration::ZERO)));
+    }
 
-        Self {
-            term: Term::new().small_screen(App::small_screen()),
-            board: Board::new(w, h, win),
-            player: Cell::Cross,
-            score: (0, 0),
+    /// Sets up the board of the recorded game and replays its moves with
+    /// the given delay, the game can be continued after that
+    pub fn import(
+        &mut self,
+        record: &Record,
+        delay: Option<Duration>,
+    ) -> Result<(), Error> {
+        let (mut board, first) = record.board()?;
+        let rules = record.rules()?;
+        board.theme = self.game.board.theme;
+        board.relative = self.game.board.relative;
+        board.labels = self.game.board.labels;
+        board.numbering = self.game.board.numbering;
+        board.threats = self.game.board.threats;
+        board.marks = self.game.board.marks;
+        self.game.load(board, first, &rules);
+        // Records saved before the rule summary was added
+        if record.tag("Rules").is_none() {
+            self.status.push(tr("status.no-rules"));
         }
+        self.analysis = None;
+        self.analyzing = None;
+        self.replay = Some(Replay::new(record.moves.clone(), delay));
+        Ok(())
+    }
+
+    /// Plays back the recorded session with given speed
+    pub fn play_back(&mut self, recording: Recording, speed: f64) {
+        self.playback = Some(Playback::new(recording, speed));
+    }
+
+    /// Starts the tournament, its bracket is shown first
+    pub fn start_tournament(&mut self, tournament: Tournament) {
+        self.tournament = Some(tournament);
+        self.show_bracket = true;
     }
 
     /// Runs the [`App`]
     pub fn run(&mut self) -> Result<(), Error> {
-        // Saves screen, clears screen and hides cursor
-        print!("\x1b[?1049h\x1b[2J\x1b[?25l");
-        _ = stdout().flush();
-        enable_raw_mode()?;
-
-        let res = self.main_loop();
+        let signal = signals::register()?;
+        let guard = TermGuard::enter()?;
+        let res = self.main_loop(&signal);
+        drop(guard);
 
-        disable_raw_mode()?;
-        // Restores screen
-        print!("\x1b[?1049l\x1b[?25h");
-        _ = stdout().flush();
+        if signal.load(Ordering::SeqCst) || self.save_as.is_some() {
+            self.save_game()?;
+        }
 
+        if let Some(stats) = &self.stats {
+            stats.save()?;
+        }
+        if let (Some(path), Some(recording)) =
+            (&self.record_path, &self.recording)
+        {
+            recording.save(path)?;
+        }
         match res {
             Err(Error::Exit) => Ok(()),
             _ => res,
@@ -64,37 +352,232 @@ impl App {
     }
 
     /// Main loop of the [`App`]
-    fn main_loop(&mut self) -> Result<(), Error> {
-        self.render()?;
+    fn main_loop(&mut self, signal: &AtomicBool) -> Result<(), Error> {
+        let input = Input::start();
         loop {
-            if poll(Duration::from_millis(100))? {
-                self.event()?;
+            if signal.load(Ordering::SeqCst) {
+                return Err(Error::Exit);
+            }
+            if self.dirty && self.last_frame.elapsed() >= FRAME {
+                self.render()?;
+                self.dirty = false;
+                self.last_frame = Instant::now();
+            }
+
+            // Signals don't interrupt waiting for events, so even when
+            // nothing animates, the wait is limited
+            let timeout = self.timeout().unwrap_or(IDLE);
+            // All the pending events are handled before the next frame, so
+            // held keys and bursts of resizes are rendered only once
+            if let Some(event) = input.next(timeout)? {
+                self.event(event)?;
+                while let Some(event) = input.next(Duration::ZERO)? {
+                    self.event(event)?;
+                }
             }
+            self.update()?;
+        }
+    }
+
+    /// Updates the status, the background work and the timed events
+    pub fn update(&mut self) -> Result<(), Error> {
+        if self.status.update() {
+            self.redraw()?;
+        }
+        if self.paused.is_none()
+            && self.game.series.as_ref().is_some_and(|s| s.ready())
+        {
+            self.next_round();
+            self.redraw()?;
         }
+        if self.demo_update()
+            || self.opponent_update()
+            || self.replay_update()
+            || self.hint_update()
+            || self.analysis_update()
+            || self.eval_bar_update()
+            || self.playback_update()?
+        {
+            self.redraw()?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the opponent, the analysis or the eval bar is still
+    /// working in the background
+    pub fn working(&self) -> bool {
+        let turn = self.game.player == Cell::Circle
+            && self.game.board.state().is_none();
+        // Paused opponent waits until the game is resumed
+        self.opponent
+            .as_ref()
+            .filter(|_| self.paused.is_none())
+            .is_some_and(|o| turn || o.spinner().is_some())
+            || self.hinting.is_some()
+            || self.analyzing.is_some()
+            || self.eval_bar.as_ref().is_some_and(|b| b.evaluating())
+    }
+
+    /// Gets how long the main loop can wait for events before something
+    /// has to be updated, [`None`] when nothing animates
+    fn timeout(&self) -> Option<Duration> {
+        let running = self.config_errors.is_empty();
+        let demo = self.demo.as_ref().filter(|_| running);
+        // Opponent only has to be updated while it's thinking or on turn
+        let turn = self.game.player == Cell::Circle
+            && self.game.board.state().is_none();
+        let opponent = self.opponent.as_ref().filter(|o| {
+            running && self.paused.is_none() && (turn || o.spinner().is_some())
+        });
+        let animation = [
+            demo.map(|d| d.remaining()),
+            opponent.map(|o| o.remaining()),
+            self.replay
+                .as_ref()
+                .filter(|r| !r.finished())
+                .map(|r| r.remaining()),
+            self.game
+                .series
+                .as_ref()
+                .filter(|_| self.paused.is_none())
+                .and_then(|s| s.remaining()),
+            self.hinting.as_ref().map(|_| self.tick),
+            self.analyzing.as_ref().map(|_| self.tick),
+            self.playback.as_ref().map(|p| p.remaining()),
+            self.eval_bar
+                .as_ref()
+                .filter(|b| b.evaluating())
+                .map(|_| self.tick),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .map(|t| min(t, self.tick));
+
+        let frame = self
+            .dirty
+            .then(|| FRAME.saturating_sub(self.last_frame.elapsed()));
+        [animation, frame, self.status.remaining()]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// Requests rendering of the screen in the next frame
+    pub fn redraw(&mut self) -> Result<(), Error> {
+        self.dirty = true;
+        Ok(())
     }
 
     /// Renders current screen of the [`App`]
     pub fn render(&mut self) -> Result<(), Error> {
+        if !self.config_errors.is_empty() {
+            self.term.render(self.render_config_errors())?;
+            return Ok(());
+        }
+        if let Some(wizard) = &self.wizard {
+            self.term.render(wizard.render(&self.game.board.theme))?;
+            return Ok(());
+        }
+        if let Some(winner) =
+            self.game.series.as_ref().and_then(|s| s.winner())
+        {
+            self.term.render(self.render_match_over(winner))?;
+            return Ok(());
+        }
+        if let Some(tournament) =
+            self.tournament.as_ref().filter(|_| self.show_bracket)
+        {
+            self.term
+                .render(tournament.render(&self.game.board.theme))?;
+            return Ok(());
+        }
+        if self.paused.is_some() {
+            self.term.render(self.render_paused())?;
+            return Ok(());
+        }
+        if self.show_rules {
+            self.term.render(self.render_rules())?;
+            return Ok(());
+        }
+
+        // Board is shown as seen by the player on turn in the fog of war
+        self.game.board.viewer = self.game.player;
+        self.fit_view();
         let mut layout = Layout::vertical().center();
+        if let Some(daily) = self.render_daily() {
+            layout.add_child(daily, Constraint::Length(1));
+        }
         layout.add_child(self.render_state(), Constraint::Length(1));
-        layout.add_child(self.board.clone(), Constraint::Min(0));
+        layout.add_child(self.render_boards(), Constraint::Min(0));
 
         let mut center = Layout::horizontal().center();
         center.add_child(layout, Constraint::Min(0));
+        if let Some(log) = self.render_log() {
+            center.add_child(Spacer::new(), Constraint::Length(2));
+            center.add_child(log, Constraint::Length(LOG_WIDTH));
+        }
 
         let mut main = Layout::vertical();
         main.add_child(center, Constraint::Fill);
-        main.add_child(Self::render_help(), Constraint::Length(1));
+        let width = self.term.size().map(|(w, _)| w).unwrap_or_default();
+        main.add_child(self.render_status(width), Constraint::Length(1));
 
-        self.term.render(main)?;
+        if let Some(load) = &self.load_screen {
+            self.term.render(Overlay::new(main, load.clone()))?;
+            return Ok(());
+        }
+        if let Some(analysis) =
+            self.analysis.as_ref().filter(|_| self.show_analysis)
+        {
+            let panel = analysis.panel(&self.game.board);
+            self.term.render(Overlay::new(main, panel))?;
+            return Ok(());
+        }
+        match &self.palette {
+            Some(palette) => {
+                self.term.render(Overlay::new(main, palette.clone()))?
+            }
+            None if self.confirm_quit => {
+                let confirm = Confirm::new(
+                    tr("quit.title"),
+                    tr("quit.text"),
+                    tr("quit.keys"),
+                    self.game.board.theme,
+                );
+                self.term.render(Overlay::new(main, confirm))?
+            }
+            None if self.confirm_move.is_some() => {
+                let confirm = Confirm::new(
+                    tr("risky.title"),
+                    tr("risky.text"),
+                    tr("risky.keys"),
+                    self.game.board.theme,
+                );
+                self.term.render(Overlay::new(main, confirm))?
+            }
+            None if self.confirm_win => {
+                let confirm = Confirm::new(
+                    tr("unwinnable.title"),
+                    tr("unwinnable.text"),
+                    tr("unwinnable.keys"),
+                    self.game.board.theme,
+                );
+                self.term.render(Overlay::new(main, confirm))?
+            }
+            None => self.term.render(main)?,
+        }
         Ok(())
     }
 
-    /// Handles key listening
-    fn event(&mut self) -> Result<(), Error> {
-        match read()? {
+    /// Handles the input event
+    fn event(&mut self, event: Event) -> Result<(), Error> {
+        match event {
             Event::Key(e) => self.key_handler(e),
-            Event::Resize(_, _) => self.render(),
+            Event::Resize(_, _) => {
+                self.term.clear();
+                self.redraw()
+            }
             _ => Ok(()),
         }
     }
@@ -102,114 +585,1736 @@ impl App {
 
 impl App {
     /// Handles key events
-    fn key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+    pub fn key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        if self.playback.is_some() {
+            // Keys would change the played back session, only exit works
+            return match Action::from_key(event) {
+                Some(Action::Exit) => Err(Error::Exit),
+                _ => Ok(()),
+            };
+        }
+        let action = self.action(event);
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(action);
+        }
+        self.action_handler(action)
+    }
+
+    /// Gets action of the given key. Keys of the dialogs, the prompts and
+    /// the screens other than the game aren't translated.
+    fn action(&self, event: KeyEvent) -> Action {
+        if self.dialog_open() {
+            return Action::Key(event);
+        }
+        if let KeyCode::Char(c @ '0'..='9') = event.code {
+            let digit = c as usize - '0' as usize;
+            if self.game.board.size == Pos::new(3, 3) && digit > 0 {
+                return Action::Numpad(digit);
+            }
+            if digit > 0 || self.count > 0 {
+                return Action::Count(digit);
+            }
+        }
+        // Shared movement keys are replaced by the keys of each player
+        match self.players.filter(|_| self.winner().is_none()) {
+            Some(keys) => {
+                if let Some(id) = keys.owner(event.code) {
+                    // Keys of the player, who isn't on turn, are ignored
+                    let turn = self.game.player != self.game.first_player();
+                    return match id == turn as usize {
+                        true => keys.0[id].own_action(event.code),
+                        false => None,
+                    }
+                    .unwrap_or(Action::Key(event));
+                }
+            }
+            None => {
+                if let Some(action) = self.controls.action(event.code) {
+                    return action;
+                }
+            }
+        }
         match event.code {
-            KeyCode::Up | KeyCode::Char('k') => self.board.up(),
-            KeyCode::Down | KeyCode::Char('j') => self.board.down(),
-            KeyCode::Right | KeyCode::Char('l') => self.board.right(),
-            KeyCode::Left | KeyCode::Char('h') => self.board.left(),
-            KeyCode::Enter => match self.board.set_selected(self.player) {
-                Ok(Some(Cell::Cross)) => self.score.0 += 1,
-                Ok(Some(Cell::Circle)) => self.score.1 += 1,
-                Ok(Some(Cell::Empty)) => {
-                    self.score = (self.score.0 + 1, self.score.1 + 1)
-                }
-                Ok(_) => self.player = self.player.next(),
-                Err(_) => {}
-            },
-            KeyCode::Char('r') => {
-                self.board.restart();
-                self.player = Cell::Cross;
+            KeyCode::Char('y') if self.swap_prompt => Action::Swap(true),
+            KeyCode::Char('n') if self.swap_prompt => Action::Swap(false),
+            _ => Action::from_key(event).unwrap_or(Action::Key(event)),
+        }
+    }
+
+    /// Checks whether the keys are handled by a dialog, a prompt or a
+    /// screen other than the game
+    fn dialog_open(&self) -> bool {
+        !self.config_errors.is_empty()
+            || self.demo.is_some()
+            || self.wizard.is_some()
+            || self
+                .game
+                .series
+                .as_ref()
+                .is_some_and(|s| s.winner().is_some())
+            || self.show_bracket
+            || self.paused.is_some()
+            || self.confirm_quit
+            || self.confirm_win
+            || self.confirm_move.is_some()
+            || self.palette.is_some()
+            || self.load_screen.is_some()
+            || self.show_analysis
+            || self.show_rules
+            || self.prompt.is_some()
+            || self.command_line.is_some()
+    }
+
+    /// Handles keys of the dialogs, the prompts and the screens other than
+    /// the game
+    fn dialog_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        if !self.config_errors.is_empty() {
+            if event.code == KeyCode::Char('c')
+                && event.modifiers.contains(KeyModifiers::CONTROL)
+            {
+                return Err(Error::Exit);
+            }
+            self.config_errors.clear();
+            return self.redraw();
+        }
+        if self.demo.is_some() {
+            return self.spectator_key_handler(event);
+        }
+        if self.wizard.is_some() {
+            return self.wizard_key_handler(event);
+        }
+        if self
+            .game
+            .series
+            .as_ref()
+            .is_some_and(|s| s.winner().is_some())
+        {
+            return self.match_over_key_handler(event);
+        }
+        if self.show_bracket {
+            return self.bracket_key_handler(event);
+        }
+        if self.paused.is_some() {
+            return self.paused_key_handler(event);
+        }
+        if self.confirm_quit {
+            return self.quit_key_handler(event);
+        }
+        if self.confirm_win {
+            return self.win_key_handler(event);
+        }
+        if self.confirm_move.is_some() {
+            return self.move_key_handler(event);
+        }
+        if self.palette.is_some() {
+            return self.palette_key_handler(event);
+        }
+        if self.load_screen.is_some() {
+            return self.load_key_handler(event);
+        }
+        if self.show_analysis {
+            if event.code == KeyCode::Char('c')
+                && event.modifiers.contains(KeyModifiers::CONTROL)
+            {
+                return Err(Error::Exit);
+            }
+            self.show_analysis = false;
+            return self.redraw();
+        }
+        if self.show_rules {
+            if event.code == KeyCode::Char('c')
+                && event.modifiers.contains(KeyModifiers::CONTROL)
+            {
+                return Err(Error::Exit);
+            }
+            self.show_rules = false;
+            return self.redraw();
+        }
+        if self.prompt.is_some() {
+            return self.prompt_key_handler(event);
+        }
+        if self.command_line.is_some() {
+            return self.command_line_key_handler(event);
+        }
+        Ok(())
+    }
+
+    /// Handles the action of the player
+    pub fn action_handler(&mut self, action: Action) -> Result<(), Error> {
+        if let Action::Count(digit) = action {
+            self.count = (self.count * 10 + digit).min(MAX_COUNT);
+            return Ok(());
+        }
+        let count = std::mem::take(&mut self.count).max(1);
+        if let Some(f) = action.movement() {
+            self.move_sel(f, count);
+            return self.redraw();
+        }
+
+        match action {
+            Action::Key(event) => return self.dialog_key_handler(event),
+            Action::Place
+                if self.tournament.is_some() && self.winner().is_some() =>
+            {
+                self.end_tournament_game()
+            }
+            Action::Place => self.place(),
+            Action::Numpad(digit) => {
+                // Numpad layout, 7 is top left and 3 is bottom right
+                let id = digit - 1;
+                self.game.board.select(Pos::new(id % 3, 2 - id / 3));
+                self.place();
+            }
+            Action::Swap(swap) => {
+                self.game.swapped ^= swap;
+                self.swap_prompt = false;
+            }
+            Action::SwitchNext if self.game.mode.free_marks() => {
+                self.game.mark = self.game.mark.next()
+            }
+            Action::SwitchNext => self.game.switch_board(true),
+            Action::SwitchPrev => self.game.switch_board(false),
+            Action::Export => self.export(),
+            Action::Analysis => self.open_analysis(),
+            Action::Hint => self.hint(),
+            Action::PlaceAt => self.prompt = Some(String::new()),
+            Action::CommandLine => {
+                let engines = self.engines.clone();
+                self.command_line = Some(CommandLine::new(engines))
+            }
+            Action::Palette => {
+                self.palette = Some(Palette::new(self.game.board.theme));
+            }
+            Action::Log => {
+                self.log = match self.log {
+                    Some(_) => None,
+                    None => Some(MoveLog::new()),
+                }
+            }
+            Action::ScrollUp => {
+                let len = self.game.board.history.len();
+                if let Some(log) = self.log.as_mut() {
+                    log.scroll_up(len);
+                }
+            }
+            Action::ScrollDown => {
+                if let Some(log) = self.log.as_mut() {
+                    log.scroll_down();
+                }
+            }
+            Action::Run(cmd) => self.run_command(cmd)?,
+            Action::Exit => return Err(Error::Exit),
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events when quit confirmation is shown
+    fn quit_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
             }
-            KeyCode::Char('R') => self.score = (0, 0),
+            KeyCode::Char('y') => return Err(Error::Exit),
+            KeyCode::Char('n') | KeyCode::Esc => self.confirm_quit = false,
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events when unwinnable game confirmation is shown
+    fn win_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('y') => self.confirm_win = false,
+            KeyCode::Char('n') | KeyCode::Esc => return Err(Error::Exit),
             KeyCode::Char('c')
                 if event.modifiers.contains(KeyModifiers::CONTROL) =>
             {
                 return Err(Error::Exit);
             }
-            KeyCode::Esc | KeyCode::Char('q') => return Err(Error::Exit),
             _ => return Ok(()),
         }
-        self.render()
+        self.redraw()
     }
 
-    /// Gets board size based on the current screen size.
-    /// Minimum size is based on the win size.
-    fn fullscreen_size() -> (usize, usize) {
-        Term::get_size()
-            .map(|(w, h)| {
-                (
-                    max(w.saturating_sub(1) / 4, 3),
-                    max(
-                        h.saturating_sub(
-                            2 + Self::render_help().height(&Coords::new(w, h)),
-                        ) / 2,
-                        3,
-                    ),
-                )
-            })
-            .unwrap_or((3, 3))
+    /// Handles key events when risky move confirmation is shown
+    fn move_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char('y') => {
+                if let Some(pos) = self.confirm_move.take() {
+                    self.game.board.select(pos);
+                    self.play();
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => self.confirm_move = None,
+            _ => return Ok(()),
+        }
+        self.redraw()
     }
 
-    /// Small screen to be displayed, when game can't fit
-    fn small_screen() -> Layout {
-        let mut layout = Layout::vertical().center();
-        layout.add_child(
-            "Terminal too small!"
-                .modifier(Modifier::BOLD)
-                .align(TextAlign::Center),
-            Constraint::Min(0),
-        );
-        layout.add_child(
-            "You have to increase terminal size".align(TextAlign::Center),
-            Constraint::Min(0),
-        );
-        layout
+    /// Handles key events when placement prompt is opened
+    fn prompt_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return Ok(());
+        };
+
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char(c) if c.is_ascii_alphanumeric() => {
+                prompt.push(c.to_ascii_lowercase())
+            }
+            KeyCode::Backspace => _ = prompt.pop(),
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                let name = self.prompt.take().unwrap_or_default();
+                match self.game.board.parse_cell(&name) {
+                    Some(pos) => {
+                        self.game.board.select(pos);
+                        self.place();
+                    }
+                    None => self.status.push(trf("status.no-cell", &[&name])),
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.redraw()
     }
 
-    /// Renders game state text
-    fn render_state(&self) -> Layout {
-        let (player, msg) = match self.board.state() {
-            Some(Cell::Empty) => (Cell::Empty, "Draw!"),
-            None => (self.player, " turn."),
-            Some(plr) => (plr, " wins!"),
+    /// Handles key events when the command line is opened
+    fn command_line_key_handler(
+        &mut self,
+        event: KeyEvent,
+    ) -> Result<(), Error> {
+        let Some(line) = self.command_line.as_mut() else {
+            return Ok(());
         };
 
-        let player = match player {
-            Cell::Circle => "O".fg(Color::Red),
-            Cell::Cross => "X".fg(Color::Green),
-            _ => "".to_span(),
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char(c) => line.push(c),
+            KeyCode::Tab => line.complete(),
+            // Deleting past the start closes the line like in vim
+            KeyCode::Backspace if !line.pop() => self.command_line = None,
+            KeyCode::Esc => self.command_line = None,
+            KeyCode::Enter => {
+                let Some(line) = self.command_line.take() else {
+                    return Ok(());
+                };
+                if line.input().trim().is_empty() {
+                    return self.redraw();
+                }
+                let res =
+                    line.command().and_then(|cmd| self.run_line_command(cmd));
+                match res {
+                    Err(Error::Exit) => return Err(Error::Exit),
+                    Err(e) => self.status.push(e.to_string()),
+                    Ok(_) => {}
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events when command palette is opened
+    fn palette_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        let Some(palette) = self.palette.as_mut() else {
+            return Ok(());
         };
-        let stat_len = player.get_text().len() + msg.len();
 
-        let mut layout = Layout::horizontal();
-        let p = Paragraph::new(vec![player.into(), msg.into()]).separator(" ");
-        layout.add_child(p, Constraint::Min(0));
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        match event.code {
+            KeyCode::Char('c') if ctrl => return Err(Error::Exit),
+            KeyCode::Char('p') if ctrl => palette.up(),
+            KeyCode::Char('n') if ctrl => palette.down(),
+            KeyCode::Up => palette.up(),
+            KeyCode::Down => palette.down(),
+            KeyCode::Char(c) => palette.push(c),
+            KeyCode::Backspace => palette.pop(),
+            KeyCode::Enter => {
+                let cmd = palette.selected();
+                self.palette = None;
+                if let Some(cmd) = cmd {
+                    self.run_command(cmd)?;
+                }
+            }
+            KeyCode::Esc => self.palette = None,
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
 
-        let score = format!("{}:{}", self.score.0, self.score.1);
-        if score.len() + stat_len <= self.board.width(&Coords::new(0, 0)) {
-            layout.add_child(Spacer::new(), Constraint::Fill);
-            layout.add_child(
-                Paragraph::new(vec![
-                    self.score.0.to_string().fg(Color::Green).into(),
-                    self.score.1.to_string().fg(Color::Red).into(),
-                ])
-                .separator(":"),
-                Constraint::Min(0),
-            );
+    /// Handles key events when the load screen is shown
+    fn load_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        let Some(load) = self.load_screen.as_mut() else {
+            return Ok(());
+        };
+
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Up | KeyCode::Char('k') => load.up(),
+            KeyCode::Down | KeyCode::Char('j') => load.down(),
+            KeyCode::Enter => {
+                let name = load.selected().map(|s| s.name.clone());
+                self.load_screen = None;
+                if let Some(name) = name {
+                    self.load_slot(&name);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.load_screen = None,
+            _ => return Ok(()),
         }
-        layout
+        self.redraw()
     }
 
-    /// Renders help with all the keybinds
-    fn render_help() -> Paragraph {
-        Paragraph::new(vec![
-            "[Arrows/hjkl]Move".fg(Color::Gray).into(),
-            "[Enter]Place".fg(Color::Gray).into(),
-            "[r]Restart".fg(Color::Gray).into(),
-            "[R]Resets score".fg(Color::Gray).into(),
-            "[Esc|q]Quit".fg(Color::Gray).into(),
-        ])
+    /// Handles key events when first-run wizard is shown
+    fn wizard_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        let Some(wizard) = self.wizard.as_mut() else {
+            return Ok(());
+        };
+
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Up | KeyCode::Char('k') => wizard.up(),
+            KeyCode::Down | KeyCode::Char('j') => wizard.down(),
+            KeyCode::Left | KeyCode::Char('h') => wizard.change(-1),
+            KeyCode::Right | KeyCode::Char('l') => wizard.change(1),
+            KeyCode::Enter => {
+                let res = wizard.save();
+                self.wizard = None;
+                let (config, mut errors) = Config::load();
+                if let Err(e) = res {
+                    let msg = trf("status.config-not-saved", &[&e]);
+                    errors.push(ConfigError { line: 0, msg });
+                }
+                self.config_errors = errors;
+                self.apply_config(config);
+            }
+            KeyCode::Esc => self.wizard = None,
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events of the demo spectator, who can annotate the board
+    fn spectator_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        if let Some(f) = self.controls.movement(event.code) {
+            self.game.board.annotations.active = true;
+            f(&mut self.game.board);
+            return self.redraw();
+        }
+
+        let pos = self.game.board.selected;
+        let anno = &mut self.game.board.annotations;
+        match event.code {
+            KeyCode::Char('m') => anno.toggle_cell(pos),
+            KeyCode::Char('v') => anno.line_point(pos),
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char('c') => anno.clear(),
+            KeyCode::Esc | KeyCode::Char('q') => return Err(Error::Exit),
+            _ => return Ok(()),
+        }
+        anno.active = true;
+        self.redraw()
+    }
+
+    /// Handles key events when the game is paused
+    fn paused_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char('p') => {
+                let paused = self.paused.take().map(|p| p.elapsed());
+                if let (Some(paused), Some(series)) =
+                    (paused, self.game.series.as_mut())
+                {
+                    series.resume(paused);
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events when tournament bracket is shown
+    fn bracket_key_handler(&mut self, event: KeyEvent) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Enter
+                if self
+                    .tournament
+                    .as_ref()
+                    .is_some_and(|t| t.champion().is_none()) =>
+            {
+                self.show_bracket = false;
+                self.new_game();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Err(Error::Exit),
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Handles key events when match-over screen is shown
+    fn match_over_key_handler(
+        &mut self,
+        event: KeyEvent,
+    ) -> Result<(), Error> {
+        match event.code {
+            KeyCode::Char('c')
+                if event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Err(Error::Exit);
+            }
+            KeyCode::Char('r') => {
+                if let Some(series) = self.game.series.as_mut() {
+                    *series = Series::new(series.best_of);
+                }
+                self.game.score = (0, 0);
+                self.new_game();
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Err(Error::Exit),
+            _ => return Ok(()),
+        }
+        self.redraw()
+    }
+
+    /// Applies given config, board is recreated when its size isn't given
+    /// by the arguments
+    fn apply_config(&mut self, mut config: Config) {
+        let (w, h) = match self.game.size {
+            Some(c) => (c.x, c.y),
+            None => config
+                .size
+                .unwrap_or_else(|| App::fullscreen_size(config.controls)),
+        };
+        let mut board = self.game.new_board(w, h);
+        // Size given by arguments is checked already
+        self.confirm_win = !board.win_len.fits(w, h);
+        let mode = self.color_mode.unwrap_or(config.color_mode);
+        board.theme = mode.apply(config.theme);
+        board.relative = self.relative || config.relative;
+        board.labels = config.labels;
+        board.numbering = Numbering {
+            origin: self.origin.unwrap_or(config.origin),
+            rtl: self.rtl || config.rtl.unwrap_or_else(locale::rtl),
+        };
+        board.marks = self.marks.unwrap_or(config.marks);
+        self.set_board(board);
+        // Player always starts the daily challenge
+        self.game.start_rule = match self.game.daily {
+            Some(_) => StartRule::AlwaysX,
+            None => config.start_rule,
+        };
+
+        if let Some(mode) = self.sound_mode {
+            mode.apply(&mut config.sound);
+        }
+        self.sound = Sound::new(config.sound);
+        self.controls = config.controls;
+        self.players = config.players;
+        self.tick = config.tick.unwrap_or(TICK);
+        // Daily challenge progress is always tracked
+        self.stats =
+            (config.stats || self.game.daily.is_some()).then(Stats::load);
+    }
+
+    /// Starts new game on the given board
+    fn set_board(&mut self, board: Board) {
+        self.analysis = None;
+        self.analyzing = None;
+        self.game.set_board(board);
+    }
+
+    /// Replaces the board by the one with given size and win length, the
+    /// look of the board is kept and the game is restarted
+    fn change_board(&mut self, size: Pos, win: usize) -> Result<(), Error> {
+        if self.game.daily.is_some()
+            || self.tournament.is_some()
+            || self.game.mode == Mode::Notakto
+        {
+            Err(Error::Unavailable(tr("error.board-locked").into()))?;
+        }
+        self.game.resize(size, win)?;
+        self.analysis = None;
+        self.analyzing = None;
+        self.replay = None;
+        self.swap_prompt = false;
+        Ok(())
+    }
+
+    /// Moves selected cell given number of times using given function
+    fn move_sel(&mut self, f: fn(&mut Board), count: usize) {
+        for _ in 0..count {
+            f(&mut self.game.board);
+        }
+        self.sound.play(SoundEvent::Tick);
+        // Hidden cell would give away the mark hidden on it
+        match self.game.legality(self.game.board.selected) {
+            Err(Illegal::Hidden) => {}
+            Err(e) if e.by_rule() => self.status.push(e.reason()),
+            _ => {}
+        }
+    }
+
+    /// Places current player on the selected cell, unless the opponent AI
+    /// is on turn
+    fn place(&mut self) {
+        if self.replay.is_some() {
+            self.status.push(tr("status.wait-replay"));
+            return;
+        }
+        if self.opponent.is_some() && self.game.player == Cell::Circle {
+            self.status.push(tr("status.wait-ai"));
+            return;
+        }
+        let pos = self.game.board.selected;
+        if self.assist.is_some()
+            && Assist::risky(&self.game.board, self.game.player, pos)
+        {
+            self.confirm_move = Some(pos);
+            return;
+        }
+        self.play();
+    }
+
+    /// Selects the move suggested by the AI, when the player on turn has
+    /// hints left in the assisted mode
+    fn hint(&mut self) {
+        let player = self.game.player;
+        if self.winner().is_some() {
+            return;
+        }
+        if self.opponent.is_some() && player == Cell::Circle {
+            self.status.push(tr("status.wait-ai"));
+            return;
+        }
+        if self.hinting.is_some() {
+            return;
+        }
+        let Some(assist) = self.assist.as_mut() else {
+            self.status.push(tr("status.no-assist"));
+            return;
+        };
+        let Some(left) = assist.take(player) else {
+            self.status.push(tr("status.no-hints"));
+            return;
+        };
+
+        let ai = self.hard_ai();
+        let hinting = Hinting::start(ai, &self.game.board, player, left);
+        self.hinting = Some(hinting);
+    }
+
+    /// Selects the hint, when the AI found it. Returns whether screen
+    /// should be rendered.
+    fn hint_update(&mut self) -> bool {
+        let Some((ai, pos)) = self.hinting.as_ref().and_then(|h| h.result())
+        else {
+            return false;
+        };
+        self.hard_ai.get_or_insert(ai);
+        let Some(hinting) = self.hinting.take() else {
+            return false;
+        };
+        // Hint is outdated, when a move was played meanwhile
+        let board = &mut self.game.board;
+        if let Some(pos) =
+            pos.filter(|_| hinting.is_for(board, self.game.player))
+        {
+            board.select(pos);
+            self.status.push(trf(
+                "status.hint",
+                &[&board.cell_name(&pos), &hinting.left],
+            ));
+        }
+        true
+    }
+
+    /// Takes the hard AI giving the hints and analysing the games, new one
+    /// is created while it's searching
+    fn hard_ai(&mut self) -> Ai {
+        self.hard_ai
+            .take()
+            .unwrap_or_else(|| Ai::new(Difficulty::Hard))
+    }
+
+    /// Plays the current player on the selected cell
+    fn play(&mut self) {
+        self.swap_prompt = false;
+        let pos = self.game.board.selected;
+        // Playing hidden mark is ignored like moving onto it, the reason
+        // would give away the mark
+        match self.game.legality(pos) {
+            Err(Illegal::Hidden) => return,
+            Err(e) => {
+                self.status.push(e.reason());
+                self.sound.play(SoundEvent::Invalid);
+                return;
+            }
+            Ok(_) => {}
+        }
+        let events = match self.game.play(pos) {
+            Ok(events) => events,
+            Err(e) => {
+                self.status.push(e.to_string());
+                self.sound.play(SoundEvent::Invalid);
+                return;
+            }
+        };
+        if self.winner().is_none() && !events.is_empty() {
+            self.swap_prompt = self.game.swap_rule
+                && self.demo.is_none()
+                && self.game.history().len() == 1;
+        }
+        self.handle_events(events);
+    }
+
+    /// Reacts to the events of the played board
+    fn handle_events(&mut self, events: Vec<BoardEvent>) {
+        for event in events {
+            match (event, self.winner()) {
+                (BoardEvent::Placed(..) | BoardEvent::Moved(..), None) => {
+                    self.sound.play(SoundEvent::Place)
+                }
+                // Dead board of the multi-board game doesn't end the game
+                (BoardEvent::State(Some(_)), Some(winner)) => {
+                    self.game_ended(winner)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Records result of the ended game and announces it
+    fn game_ended(&mut self, winner: Cell) {
+        if let (Some(stats), None, None) =
+            (self.stats.as_mut(), &self.demo, &self.replay)
+        {
+            stats.add(winner);
+        }
+
+        // Only today's challenge counts to the streak
+        if let (Cell::Cross, Some(daily), Some(stats)) = (
+            winner,
+            self.game.daily.as_ref().filter(|d| d.is_today()),
+            self.stats.as_mut(),
+        ) {
+            stats.solve_daily(daily.day);
+            let streak = stats.streak(daily.day);
+            self.status.push(trf("status.daily-solved", &[&streak]));
+        }
+
+        if self.export.is_some() {
+            self.export();
+        }
+        if self.demo.is_none()
+            && self.game.boards.is_empty()
+            && Analysis::supported(&self.game.board)
+        {
+            let ai = self.hard_ai();
+            let board = self.game.board.clone();
+            self.analyzing = Some(Analyzing::start(ai, board));
+        }
+
+        // Opponent AI or bot always plays circle
+        match winner {
+            Cell::Circle if self.opponent.is_some() => {
+                self.sound.play(SoundEvent::Lose)
+            }
+            Cell::Cross | Cell::Circle => self.sound.play(SoundEvent::Win),
+            _ => self.sound.play(SoundEvent::Draw),
+        }
+    }
+
+    /// Shows the analysis of the finished game, when it's computed
+    fn open_analysis(&mut self) {
+        if self.analysis.is_some() {
+            self.show_analysis = true;
+        } else if self.analyzing.is_some() {
+            self.status.push(tr("status.analysing"));
+        } else {
+            self.status.push(tr("status.no-analysis"));
+        }
+    }
+
+    /// Stores the analysis of the finished game, when it's computed.
+    /// Returns whether screen should be rendered.
+    fn analysis_update(&mut self) -> bool {
+        let Some((ai, analysis)) =
+            self.analyzing.as_ref().and_then(|a| a.result())
+        else {
+            return false;
+        };
+        self.hard_ai.get_or_insert(ai);
+        self.analyzing = None;
+        self.analysis = Some(analysis);
+        self.status.push(tr("status.analysis-ready"));
+        true
+    }
+
+    /// Evaluates the current position for the eval bar, when it changed.
+    /// Returns whether screen should be rendered.
+    fn eval_bar_update(&mut self) -> bool {
+        let Some(bar) = self.eval_bar.as_mut() else {
+            return false;
+        };
+        bar.update(&self.game.board, self.game.player)
+    }
+
+    /// Exports the board as an image or as a game record, when the path
+    /// has the record extension, to the export path or to the file named
+    /// by the current time
+    fn export(&mut self) {
+        let path = self.export.clone().unwrap_or_else(|| {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            format!("tictactoe-{time}.svg")
+        });
+        let res = match path.ends_with(&format!(".{}", record::EXTENSION)) {
+            true => self.export_record(&path),
+            false => export(&self.game.board, &self.game.rules(), &path),
+        };
+        self.status.push(match res {
+            Ok(_) => trf("status.exported", &[&path]),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    /// Copies the board as text to the clipboard
+    fn copy(&mut self) {
+        self.status.push(
+            match clipboard::copy(&board_text(&self.game.board)) {
+                Ok(_) => tr("status.copied").to_string(),
+                Err(e) => e.to_string(),
+            },
+        );
+    }
+
+    /// Saves the game in progress to the named slot or to the autosave
+    /// file, so it can be continued by loading or importing it
+    fn save_game(&self) -> Result<(), Error> {
+        let in_progress = !self.game.board.history.is_empty()
+            && self.game.board.state().is_none()
+            && self.replay.is_none();
+        if !in_progress
+            || self.game.mode != Mode::Normal
+            || self.game.pieces.is_some()
+            || self.game.infinite
+        {
+            return Ok(());
+        }
+        let (path, flag) = match &self.save_as {
+            Some(name) => (slots::path(name)?, format!("--load {name}")),
+            None => {
+                let Some(path) = record::autosave_path() else {
+                    return Ok(());
+                };
+                let flag = format!("--import {}", path.display());
+                (path, flag)
+            }
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(&path, e))?;
+        }
+        self.export_record(&path)?;
+        println!("{}", trf("game-saved", &[&flag]));
+        Ok(())
+    }
+
+    /// Saves the game to the slot with given name, it's saved to the slot
+    /// on quit from now on
+    fn save_slot(&mut self, name: &str) -> Result<(), Error> {
+        let path = slots::path(name)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(&path, e))?;
+        }
+        self.export_record(&path)?;
+        self.save_as = Some(name.to_string());
+        self.status.push(trf("status.saved", &[&name]));
+        Ok(())
+    }
+
+    /// Checks whether a saved game can be loaded. Saved games replace the
+    /// board and rules, so they can be loaded only in the normal game
+    /// against another player.
+    fn check_load(&self) -> Result<(), Error> {
+        if self.game.mode != Mode::Normal
+            || self.game.daily.is_some()
+            || self.tournament.is_some()
+            || self.opponent.is_some()
+        {
+            Err(Error::Unavailable(tr("error.load-normal").into()))?;
+        }
+        Ok(())
+    }
+
+    /// Opens the load screen, when a saved game can be loaded
+    fn open_load_screen(&mut self) {
+        match self.check_load() {
+            Ok(_) => {
+                self.load_screen = Some(LoadScreen::new(self.game.board.theme))
+            }
+            Err(e) => self.status.push(e.to_string()),
+        }
+    }
+
+    /// Loads the game from the slot with given name, it's saved back to
+    /// the slot on quit
+    fn load_slot(&mut self, name: &str) {
+        let res = slots::path(name)
+            .and_then(Record::load)
+            .and_then(|rec| self.import(&rec, None));
+        match res {
+            Ok(_) => {
+                self.save_as = Some(name.to_string());
+                self.status.push(trf("status.loaded", &[&name]));
+            }
+            Err(e) => self.status.push(e.to_string()),
+        }
+    }
+
+    /// Saves the game record to the given path
+    fn export_record<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        // Moves of the variants can't be written as single cells and
+        // the growing board shifts them
+        if self.game.mode != Mode::Normal
+            || self.game.pieces.is_some()
+            || self.game.infinite
+        {
+            Err(Error::Unavailable(tr("error.export-normal").into()))?;
+        }
+        let opponent = self.opponent.as_ref().and_then(|o| o.name());
+        let names = match self.tournament.as_ref().and_then(|t| t.names()) {
+            Some((cross, circle)) => [cross, circle],
+            None if self.demo.is_some() => ["AI", "AI"],
+            None => ["Player", opponent.unwrap_or("Player")],
+        };
+        let rules = self.game.rules();
+        Record::new(&self.game.board, &rules, names, self.game.board.state())
+            .save(path)
+    }
+
+    /// Plays the next move of the imported game, when it's time for it.
+    /// Returns whether screen should be rendered.
+    fn replay_update(&mut self) -> bool {
+        let Some(pos) = self.replay.as_mut().and_then(|r| r.next_move())
+        else {
+            return false;
+        };
+        let sel = self.game.board.selected;
+        self.game.board.select(pos);
+        self.play();
+        self.game.board.select(sel);
+        if self.replay.as_ref().is_some_and(|r| r.finished()) {
+            self.replay = None;
+            self.status.push(tr("status.replay-finished"));
+        }
+        true
+    }
+
+    /// Handles the next recorded action, when it's time for it. Returns
+    /// whether screen should be rendered.
+    fn playback_update(&mut self) -> Result<bool, Error> {
+        // Results of the background work aren't recorded, so the playback
+        // waits for them
+        if self.working() {
+            return Ok(false);
+        }
+        let Some(action) =
+            self.playback.as_mut().and_then(|p| p.next_action())
+        else {
+            return Ok(false);
+        };
+        if self.playback.as_ref().is_some_and(|p| p.finished()) {
+            self.playback = None;
+            self.status.push(tr("status.playback-finished"));
+        }
+        self.action_handler(action)?;
+        Ok(true)
+    }
+
+    /// Plays the move when the demo AI finished thinking, otherwise starts
+    /// thinking about the next move. Returns whether screen should be
+    /// rendered.
+    fn demo_update(&mut self) -> bool {
+        let Some(demo) = self.demo.as_mut() else {
+            return false;
+        };
+        if let Some(pos) = demo.result() {
+            self.demo_move(pos.ok().flatten());
+            return true;
+        }
+        // Renders the next spinner frame
+        if demo.spinner().is_some() {
+            return true;
+        }
+        if self.config_errors.is_empty() && demo.ready() {
+            demo.think(&self.game.board, self.game.player);
+            return true;
+        }
+        false
+    }
+
+    /// Plays the move when the opponent AI finished thinking, otherwise
+    /// starts thinking when it's on turn. Returns whether screen should be
+    /// rendered.
+    fn opponent_update(&mut self) -> bool {
+        // Move found meanwhile is kept until the game is resumed
+        if self.paused.is_some() {
+            return false;
+        }
+        let Some(opponent) = self.opponent.as_mut() else {
+            return false;
+        };
+        let turn = self.game.player == Cell::Circle
+            && self.game.board.state().is_none();
+        if let Some(pos) = opponent.result() {
+            let pos = match pos {
+                Ok(pos) => pos,
+                Err(e) => {
+                    // Failed bot is removed, so the player plays both sides
+                    self.status.push(e.to_string());
+                    self.opponent = None;
+                    return true;
+                }
+            };
+            // Move is outdated, when the game was restarted meanwhile
+            if let Some(pos) = pos.filter(|_| turn) {
+                let sel = self.game.board.selected;
+                self.game.board.select(pos);
+                self.play();
+                self.game.board.select(sel);
+            }
+            return true;
+        }
+        if opponent.spinner().is_some() {
+            return true;
+        }
+        if turn && self.config_errors.is_empty() && opponent.ready() {
+            opponent.think(&self.game.board, self.game.player);
+            return true;
+        }
+        false
+    }
+
+    /// Plays move found by the demo AI, restarts the game when it ended
+    fn demo_move(&mut self, pos: Option<Pos>) {
+        match pos {
+            Some(pos) => {
+                let sel = self.game.board.selected;
+                self.game.board.select(pos);
+                self.place();
+                // Keeps the selection, when spectator annotates the board
+                if self.game.board.annotations.active {
+                    self.game.board.select(sel);
+                }
+            }
+            None if self.game.series.is_none() => self.new_game(),
+            None => {}
+        }
+    }
+
+    /// Records result of the finished tournament game and shows the bracket
+    fn end_tournament_game(&mut self) {
+        let winner = self.winner();
+        let Some(tournament) = self.tournament.as_mut() else {
+            return;
+        };
+        let Some((cross, circle)) = tournament.sides() else {
+            return;
+        };
+        tournament.record(match winner {
+            Some(Cell::Cross) => Some(cross),
+            Some(Cell::Circle) => Some(circle),
+            _ => None,
+        });
+        if let Err(e) = tournament.save() {
+            self.status.push(trf("status.tournament-not-saved", &[&e]));
+        }
+        self.show_bracket = true;
+    }
+
+    /// Starts new game
+    fn new_game(&mut self) {
+        // Players take turns with the marks in the tournament instead
+        match self.tournament {
+            Some(_) => self.game.restart(Cell::Cross),
+            None => self.game.new_game(),
+        }
+        self.replay = None;
+        self.analysis = None;
+        self.analyzing = None;
+        self.swap_prompt = false;
+        if let Some(assist) = self.assist.as_mut() {
+            assist.restart();
+        }
+    }
+
+    /// Starts next round of the match
+    fn next_round(&mut self) {
+        if let Some(series) = self.game.series.as_mut() {
+            series.next_round();
+        }
+        self.new_game();
+    }
+
+    /// Gets player who won the finished game, [`Cell::Empty`] on a draw
+    fn winner(&self) -> Option<Cell> {
+        self.game.result()
+    }
+
+    /// Executes given command
+    fn run_command(&mut self, cmd: Command) -> Result<(), Error> {
+        match cmd {
+            Command::NewGame => self.new_game(),
+            Command::ResetScore => self.game.score = (0, 0),
+            Command::Rules => self.show_rules = true,
+            Command::CopyBoard => self.copy(),
+            Command::Zoom => {
+                self.game.board.zoom = self.game.board.zoom.next();
+                let zoom = self.game.board.zoom.name();
+                self.status.push(trf("status.zoom", &[&zoom]));
+            }
+            Command::Labels => {
+                let labels = !self.game.board.labels;
+                self.game.board.labels = labels;
+                for board in self.game.boards.iter_mut() {
+                    board.labels = labels;
+                }
+            }
+            Command::Threats => {
+                let threats = !self.game.board.threats;
+                self.game.board.threats = threats;
+                for board in self.game.boards.iter_mut() {
+                    board.threats = threats;
+                }
+            }
+            Command::EvalBar => {
+                self.eval_bar = match self.eval_bar {
+                    Some(_) => None,
+                    None => Some(EvalBar::default()),
+                };
+            }
+            Command::Pause => self.paused = Some(Instant::now()),
+            Command::Load => self.open_load_screen(),
+            Command::Quit => self.quit()?,
+        }
+        Ok(())
+    }
+
+    /// Executes given command typed in the command line
+    fn run_line_command(&mut self, cmd: LineCommand) -> Result<(), Error> {
+        match cmd {
+            LineCommand::Resize(size) => {
+                let win =
+                    self.game.win_len.unwrap_or(min(max(size.x, size.y), 5));
+                self.change_board(size, win)?;
+                self.status.push(trf("status.resized", &[&size.x, &size.y]));
+            }
+            LineCommand::Win(len) => {
+                self.change_board(self.game.board.size, len)?;
+                self.status.push(trf("status.win-len", &[&len]));
+            }
+            LineCommand::Theme(theme) => {
+                let theme = self.game.board.theme.mode.apply(*theme);
+                self.game.board.theme = theme;
+                for board in self.game.boards.iter_mut() {
+                    board.theme = theme;
+                }
+            }
+            LineCommand::Save(name) => self.save_slot(&name)?,
+            LineCommand::Load(None) => self.open_load_screen(),
+            LineCommand::Load(Some(name)) => {
+                self.check_load()?;
+                self.load_slot(&name);
+            }
+            LineCommand::Ai(name) => self.set_ai(name)?,
+            LineCommand::Run(cmd) => self.run_command(cmd)?,
+        }
+        Ok(())
+    }
+
+    /// Plays against the AI engine with given name, which plays circle.
+    /// [`None`] turns the AI opponent off.
+    fn set_ai(&mut self, name: Option<String>) -> Result<(), Error> {
+        if self.game.daily.is_some() || self.tournament.is_some() {
+            Err(Error::Unavailable(tr("error.ai-locked").into()))?;
+        }
+        let options = Options {
+            seed: self.game.seed,
+            ..Options::default()
+        };
+        self.opponent = name
+            .map(|name| self.engines.create(&name, &options))
+            .transpose()?
+            .map(|engine| Demo::new(engine, Some(Duration::ZERO)));
+        self.status.push(match self.opponent {
+            Some(_) => tr("status.ai-on"),
+            None => tr("status.ai-off"),
+        });
+        Ok(())
+    }
+
+    /// Quits the game, quitting the game in progress has to be confirmed
+    fn quit(&mut self) -> Result<(), Error> {
+        // Game in progress is saved to the slot on quit
+        if self.winner().is_some()
+            || self.game.board.history.is_empty()
+            || self.save_as.is_some()
+        {
+            return Err(Error::Exit);
+        }
+        self.confirm_quit = true;
+        Ok(())
+    }
+
+    /// Shows only part of the board, which fits the screen, when the board
+    /// is larger than the screen. Move log keeps its space, when the
+    /// board has enough space without it.
+    fn fit_view(&mut self) {
+        let Some((w, h)) =
+            self.term.size().filter(|_| self.game.boards.is_empty())
+        else {
+            return;
+        };
+        let log = match self.log {
+            Some(_) => LOG_WIDTH + 2,
+            None => 0,
+        };
+        let (cw, ch) = self.game.board.zoom.cell();
+        let border = self.game.board.zoom.border();
+        let gutter = self.game.board.gutter();
+        let bar = match self.eval_bar {
+            Some(_) => eval_bar::WIDTH,
+            None => 0,
+        };
+        let w = w.saturating_sub(gutter.x + bar);
+        // Layouts count the state and status lines to the width too
+        let cols = match w.saturating_sub(log + 2) / cw {
+            cols if cols >= MIN_VIEW => cols,
+            _ => w.saturating_sub(2) / cw,
+        };
+        // State, status and daily challenge lines
+        let lines = 2 + self.game.daily.is_some() as usize + gutter.y;
+        let rows = h.saturating_sub(lines + border) / ch;
+        self.game
+            .board
+            .fit_view(Pos::new(max(cols, MIN_VIEW), max(rows, MIN_VIEW)));
+    }
+
+    /// Gets board size based on the current screen size.
+    /// Minimum size is based on the win size.
+    fn fullscreen_size(controls: Controls) -> (usize, usize) {
+        Term::get_size()
+            .map(|(w, h)| {
+                (
+                    max(w.saturating_sub(1) / 4, 3),
+                    max(
+                        h.saturating_sub(
+                            2 + Self::render_help(
+                                false,
+                                Mode::Normal,
+                                controls,
+                                None,
+                                Color::Default,
+                                w,
+                            )
+                            .height(&Coords::new(w, h)),
+                        ) / 2,
+                        3,
+                    ),
+                )
+            })
+            .unwrap_or((3, 3))
+    }
+
+    /// Small screen to be displayed, when game can't fit
+    pub fn small_screen() -> Layout {
+        let mut layout = Layout::vertical().center();
+        layout.add_child(
+            tr("small.title")
+                .modifier(Modifier::BOLD)
+                .align(TextAlign::Center),
+            Constraint::Min(0),
+        );
+        layout.add_child(
+            tr("small.text").align(TextAlign::Center),
+            Constraint::Min(0),
+        );
+        layout
+    }
+
+    /// Renders game state text
+    fn render_state(&self) -> Layout {
+        let (player, msg) = match self.winner() {
+            None if self.swap_prompt => (self.game.player, "state.swap"),
+            Some(Cell::Empty) => (Cell::Empty, "state.draw"),
+            None => (self.game.player, "state.turn"),
+            Some(plr) => (plr, "state.wins"),
+        };
+        // Message follows the player's name, draw has no player
+        let mut msg = match player {
+            Cell::Empty => tr(msg).to_string(),
+            _ => format!(" {}", tr(msg)),
+        };
+
+        let player = match player {
+            Cell::Circle | Cell::Cross => self
+                .game
+                .mode
+                .player_name(player)
+                .map_or_else(|| player.symbol().to_string(), str::to_string)
+                .fg(self.game.board.theme.cell(player))
+                .modifier(self.game.board.theme.weight(player)),
+            _ => "".to_span(),
+        };
+        let mut stat_len = player.get_text().len() + msg.len();
+        // Mark placed by the player on turn, when players choose it
+        let mark = (self.game.mode.free_marks() && self.winner().is_none())
+            .then(|| {
+                format!("[{}]", self.game.mark.symbol()).fg(self
+                    .game
+                    .board
+                    .theme
+                    .cell(self.game.mark))
+            });
+        let width = self.boards_width();
+        // Turn message is hidden on small boards, so the mark fits
+        if mark.is_some() && stat_len + 5 > width {
+            stat_len -= msg.len();
+            msg.clear();
+        }
+        // Only the spinner is shown, when the text doesn't fit
+        let thinking = self
+            .demo
+            .as_ref()
+            .or(self.opponent.as_ref())
+            .and_then(|d| d.spinner())
+            .map(|s| match stat_len + 15 <= width {
+                true => trf("state.thinking", &[&s]),
+                false => s.to_string(),
+            });
+
+        let mut layout = Layout::horizontal();
+        // Empty span can't be rendered in paragraph
+        let mut items: Vec<Box<dyn Text>> = vec![];
+        if !player.get_text().is_empty() {
+            items.push(player.into());
+        }
+        if !msg.is_empty() {
+            items.push(msg.to_span().into());
+        }
+        if let Some(mark) = mark {
+            stat_len += 4;
+            items.push(mark.into());
+        }
+        if let Some(thinking) = thinking {
+            // Paragraph width counts bytes of the multibyte spinner
+            stat_len += thinking.len() + 1;
+            items.push(thinking.fg(self.game.board.theme.dim).into());
+        }
+        let p = Paragraph::new(items).separator(" ");
+        layout.add_child(p, Constraint::Min(0));
+        if self.game.points {
+            let (lines, len) = self.render_lines();
+            stat_len += len + 1;
+            layout.add_child(Spacer::new(), Constraint::Length(1));
+            layout.add_child(lines, Constraint::Min(0));
+        }
+
+        let (score, score_len) = self.render_score();
+        if score_len + stat_len <= width {
+            layout.add_child(Spacer::new(), Constraint::Fill);
+            let rule = self.game.start_rule.name();
+            if self.tournament.is_none()
+                && rule.len() + score_len + stat_len + 2 <= width
+            {
+                layout.add_child(
+                    rule.fg(self.game.board.theme.dim),
+                    Constraint::Min(0),
+                );
+                layout.add_child(Spacer::new(), Constraint::Length(1));
+            }
+            layout.add_child(score, Constraint::Min(0));
+        }
+        layout
+    }
+
+    /// Renders score of the players, or names of the players in the
+    /// tournament. Returns it together with its length.
+    fn render_score(&self) -> (Paragraph, usize) {
+        let theme = &self.game.board.theme;
+        if let Some((cross, circle)) =
+            self.tournament.as_ref().and_then(|t| t.names())
+        {
+            let len = cross.chars().count() + circle.chars().count() + 4;
+            let names = Paragraph::new(vec![
+                cross.fg(theme.cell(Cell::Cross)).into(),
+                tr("vs").into(),
+                circle.fg(theme.cell(Cell::Circle)).into(),
+            ])
+            .separator(" ");
+            return (names, len);
+        }
+
+        let score = match &self.game.series {
+            Some(series) => series.wins,
+            None => self.game.score,
+        };
+        let first = self.game.first_player();
+        let score_len = format!("{}:{}", score.0, score.1).len();
+        let score = Paragraph::new(vec![
+            score.0.to_string().fg(theme.cell(first)).into(),
+            score.1.to_string().fg(theme.cell(first.next())).into(),
+        ])
+        .separator(":");
+        (score, score_len)
+    }
+
+    /// Renders the boards with the eval bar on their left, when it's
+    /// enabled
+    fn render_boards(&self) -> Box<dyn Widget> {
+        let boards = self.render_board_row();
+        let Some(bar) = &self.eval_bar else {
+            return boards;
+        };
+        bar.gauge(self.game.board.theme, boards).into()
+    }
+
+    /// Renders the played board, or all the boards of the multi-board
+    /// variant, where dead boards are dimmed
+    fn render_board_row(&self) -> Box<dyn Widget> {
+        if self.game.boards.is_empty() {
+            return Box::new(self.game.board.clone());
+        }
+
+        let boards = self.game.boards.iter().enumerate().map(|(id, board)| {
+            let mut board = match id == self.game.active {
+                true => self.game.board.clone(),
+                false => board.clone(),
+            };
+            board.focused = id == self.game.active;
+            board.zoom = self.game.board.zoom;
+            if board.state().is_some() {
+                board.theme.cross = board.theme.dim;
+            }
+            board
+        });
+        BoardRow::new(boards.collect()).into()
+    }
+
+    /// Gets width of all the rendered boards
+    fn boards_width(&self) -> usize {
+        self.render_boards().width(&Coords::new(0, 0))
+    }
+
+    /// Renders date of the daily challenge, which is highlighted when it's
+    /// solved, and streak of the solved challenges
+    fn render_daily(&self) -> Option<Paragraph> {
+        let daily = self.game.daily.as_ref()?;
+        let stats = self.stats.as_ref();
+        let theme = &self.game.board.theme;
+        let date = match stats.is_some_and(|s| s.solved(daily.day)) {
+            true => daily.date().fg(theme.accent),
+            false => daily.date().fg(theme.dim),
+        };
+        let streak = stats.map_or(0, |s| s.streak(daily.day));
+        Some(
+            Paragraph::new(vec![
+                date.into(),
+                trf("daily.streak", &[&streak]).fg(theme.dim).into(),
+            ])
+            .separator(" "),
+        )
+    }
+
+    /// Renders number of lines completed by each player, returns it
+    /// together with its length
+    fn render_lines(&self) -> (Paragraph, usize) {
+        let [cross, circle] = [Cell::Cross, Cell::Circle].map(|c| {
+            format!("{}{}", c.symbol(), self.game.board.completed(c))
+        });
+        let len = cross.len() + circle.len() + 1;
+        let theme = &self.game.board.theme;
+        let lines = Paragraph::new(vec![
+            cross.fg(theme.cell(Cell::Cross)).into(),
+            circle.fg(theme.cell(Cell::Circle)).into(),
+        ])
+        .separator(" ");
+        (lines, len)
+    }
+
+    /// Renders move log panel next to the board, when it's opened and
+    /// terminal is wide enough
+    fn render_log(&self) -> Option<Layout> {
+        let log = self.log.as_ref()?;
+        let size = Coords::new(0, 0);
+        let (width, _) = self.term.size()?;
+        if width < self.boards_width() + LOG_WIDTH + 2 {
+            return None;
+        }
+
+        let height = self.game.board.height(&size) + 1;
+        let mut layout = Layout::vertical().center();
+        layout.add_child(
+            log.render(&self.game.board, height),
+            Constraint::Length(height),
+        );
+        Some(layout)
+    }
+
+    /// Renders screen listing all the config errors
+    fn render_config_errors(&self) -> Layout {
+        let mut lines = vec![];
+        if let Some(path) = Config::path() {
+            lines.push(path.to_string_lossy().fg(self.game.board.theme.dim));
+        }
+        lines.extend(
+            self.config_errors.iter().map(|e| e.to_string().to_span()),
+        );
+        lines.push("".to_span());
+        lines.push(tr("config-errors.defaults").fg(self.game.board.theme.dim));
+        lines.push(tr("continue").fg(self.game.board.theme.dim));
+
+        let width = lines.iter().map(|l| l.get_text().len()).max();
+        let height = lines.len() + 2;
+        let mut block = Block::vertical()
+            .title(tr("config-errors.title").fg(self.game.board.theme.error))
+            .border_color(self.game.board.theme.dim);
+        for line in lines {
+            block.add_child(line, Constraint::Length(1));
+        }
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(width.unwrap_or(0) + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(height));
+        main
+    }
+
+    /// Renders screen announcing the winner of the match
+    fn render_match_over(&self, winner: usize) -> Layout {
+        let wins = self
+            .game
+            .series
+            .as_ref()
+            .map(|s| s.wins)
+            .unwrap_or_default();
+        let lines = [
+            trf("match.won", &[&(winner + 1)]).modifier(Modifier::BOLD),
+            format!("{}:{}", wins.0, wins.1).to_span(),
+            "".to_span(),
+            tr("match.keys").fg(self.game.board.theme.dim),
+        ];
+
+        let width = lines.iter().map(|l| l.get_text().len()).max();
+        let height = lines.len() + 2;
+        let mut block = Block::vertical()
+            .title(tr("match.title").fg(self.game.board.theme.accent))
+            .border_color(self.game.board.theme.dim);
+        for line in lines {
+            block.add_child(line, Constraint::Length(1));
+        }
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(width.unwrap_or(0) + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(height));
+        main
+    }
+
+    /// Renders screen shown instead of the board while paused
+    fn render_paused(&self) -> Layout {
+        let lines = [
+            tr("paused.title").modifier(Modifier::BOLD),
+            "".to_span(),
+            tr("paused.text").fg(self.game.board.theme.dim),
+        ];
+
+        let width = lines.iter().map(|l| l.get_text().len()).max();
+        let height = lines.len() + 2;
+        let mut block =
+            Block::vertical().border_color(self.game.board.theme.dim);
+        for line in lines {
+            block.add_child(line, Constraint::Length(1));
+        }
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(width.unwrap_or(0) + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(height));
+        main
+    }
+
+    /// Renders screen describing rules of the current game
+    fn render_rules(&self) -> Layout {
+        let mut lines: Vec<_> = self
+            .game
+            .rules()
+            .lines()
+            .into_iter()
+            .map(|l| l.to_span())
+            .collect();
+        lines.push("".to_span());
+        lines.push(tr("continue").fg(self.game.board.theme.dim));
+
+        let width = lines.iter().map(|l| l.get_text().len()).max();
+        let height = lines.len() + 2;
+        let mut block = Block::vertical()
+            .title(tr("rules.title").fg(self.game.board.theme.accent))
+            .border_color(self.game.board.theme.dim);
+        for line in lines {
+            block.add_child(line, Constraint::Length(1));
+        }
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(width.unwrap_or(0) + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(height));
+        main
+    }
+
+    /// Renders status bar, which shows the current status message, hint
+    /// based on the game state or the help
+    fn render_status(&self, width: usize) -> Paragraph {
+        let hint = match self.winner() {
+            _ if self.demo.is_some() => None,
+            Some(_) if self.tournament.is_some() => Some(tr("hint.next-game")),
+            Some(_) if self.game.series.is_none() => Some(tr("hint.rematch")),
+            None if self.swap_prompt => Some(tr("hint.swap")),
+            None if self.game.board.lifted.is_some() => {
+                Some(tr("hint.move-piece"))
+            }
+            None if self.game.board.must_move(self.game.player) => {
+                Some(tr("hint.pick-up"))
+            }
+            _ => None,
+        };
+        let prompt = match &self.command_line {
+            Some(line) => Some(line.text()),
+            None => self.prompt.as_ref().map(|p| trf("place-at", &[p])),
+        };
+        let Some(msg) = prompt.as_deref().or(self.status.current()).or(hint)
+        else {
+            return Self::render_help(
+                self.demo.is_some(),
+                self.game.mode,
+                self.controls,
+                self.players,
+                self.game.board.theme.dim,
+                width,
+            );
+        };
+        // Longer message would wrap and couldn't fit into single line
+        let msg: String = msg.chars().take(width).collect();
+        Paragraph::new(vec![msg.fg(self.game.board.theme.dim).into()])
+    }
+
+    /// Renders help with all the keybinds
+    /// Only the keybinds fitting on a single line of given width are shown.
+    fn render_help(
+        demo: bool,
+        mode: Mode,
+        controls: Controls,
+        players: Option<PlayerKeys>,
+        color: Color,
+        width: usize,
+    ) -> Paragraph {
+        let split = players.filter(|_| !demo).map(|p| p.help());
+        let keys: &[(&str, &str)] = match demo {
+            true => &[
+                ("m", "help.mark-cell"),
+                ("v", "help.line"),
+                ("c", "help.clear"),
+                ("Esc|q", "help.stop"),
+            ],
+            false => &[
+                ("Enter", "help.place"),
+                ("r", "help.restart"),
+                ("R", "help.reset-score"),
+                ("L", "help.log"),
+                ("e", "help.export"),
+                ("y", "help.copy"),
+                ("n", "help.labels"),
+                ("t", "help.threats"),
+                ("b", "help.eval-bar"),
+                ("g", "help.place-at"),
+                ("H", "help.hint"),
+                ("?", "help.rules"),
+                ("p", "help.pause"),
+                ("^P", "help.commands"),
+                (":", "help.command-line"),
+                ("Esc|q", "help.quit"),
+            ],
+        };
+        let mut items = vec![controls.help()];
+        items.extend(
+            keys.iter()
+                .map(|(key, label)| format!("[{key}]{}", tr(label))),
+        );
+        match mode {
+            _ if demo => {}
+            Mode::OrderChaos | Mode::Wild => {
+                items.insert(2, format!("[Tab]{}", tr("help.mark")))
+            }
+            Mode::Notakto => {
+                items.insert(2, format!("[Tab]{}", tr("help.board")))
+            }
+            Mode::Normal => {}
+        }
+        // Each player moves and places with their own keys
+        if let Some(split) = split {
+            items.splice(0..2, split);
+        }
+
+        let mut len = 0;
+        Paragraph::new(
+            items
+                .iter()
+                .take_while(|item| {
+                    len += item.len() + 2;
+                    len <= width + 2
+                })
+                .map(|item| item.fg(color).into())
+                .collect(),
+        )
         .separator("  ")
     }
 }
@@ -0,0 +1,273 @@
+use std::{
+    cmp::{max, min},
+    time::{Duration, Instant},
+};
+
+use termint::{enums::Color, widgets::StrSpanExtension};
+
+use crate::{
+    board::Board,
+    bot::{self, Bot},
+    cell::Cell,
+    engine::{Engine, Factory, Options, Registry},
+    error::Error,
+    game::Game,
+    mode::Mode,
+    rng::Rng,
+    win_len::WinLen,
+};
+
+/// Randomness of the bundled AI openings, so the games differ
+const TEMPERATURE: f64 = 0.5;
+
+/// Board size and win length the games are played on
+pub type ArenaSize = (usize, usize, usize);
+
+/// Sizes played when none are given
+pub const DEFAULT_SIZES: &[ArenaSize] = &[(3, 3, 3), (7, 7, 4), (15, 15, 5)];
+
+/// Player of the arena, either the engine given by its name or an external
+/// bot given by its command
+struct Contestant {
+    spec: String,
+    name: String,
+    time: Option<Duration>,
+    /// Factory of the registered engine, bot is spawned when not set
+    factory: Option<Factory>,
+    engine: Box<dyn Engine>,
+}
+
+impl Contestant {
+    /// Creates [`Contestant`] from the given engine name or bot command,
+    /// engines are looked up in the given registry
+    fn new(
+        spec: &str,
+        time: Option<Duration>,
+        engines: &Registry,
+    ) -> Result<Self, Error> {
+        let factory = engines.find(spec).ok();
+        let engine = Contestant::spawn(spec, time, factory)?;
+        let name = match factory {
+            Some(_) => format!("ai-{spec}"),
+            None => engine.name().to_string(),
+        };
+        Ok(Self {
+            spec: spec.to_string(),
+            name,
+            time,
+            factory,
+            engine,
+        })
+    }
+
+    /// Starts the engine again, bot might be stuck after an error
+    fn restart(&mut self) -> Result<(), Error> {
+        self.engine = Contestant::spawn(&self.spec, self.time, self.factory)?;
+        Ok(())
+    }
+
+    /// Creates engine using the given factory or spawns the bot command
+    fn spawn(
+        spec: &str,
+        time: Option<Duration>,
+        factory: Option<Factory>,
+    ) -> Result<Box<dyn Engine>, Error> {
+        let options = Options {
+            seed: Rng::time_seed(),
+            temperature: TEMPERATURE,
+            time,
+            ..Options::default()
+        };
+        match factory {
+            Some(factory) => Ok(factory(&options)),
+            None => {
+                let time = time.unwrap_or(bot::DEFAULT_TIME);
+                Ok(Box::new(Bot::spawn(spec, time)?))
+            }
+        }
+    }
+}
+
+/// Results of the games on a single board size, both players are indexed
+/// by their order on the command line
+#[derive(Debug, Clone, Default)]
+struct Score {
+    wins: [usize; 2],
+    draws: usize,
+    moves: [usize; 2],
+    time: [Duration; 2],
+}
+
+impl Score {
+    /// Adds results of the other score
+    fn add(&mut self, other: &Score) {
+        for i in 0..2 {
+            self.wins[i] += other.wins[i];
+            self.moves[i] += other.moves[i];
+            self.time[i] += other.time[i];
+        }
+        self.draws += other.draws;
+    }
+
+    /// Gets average move time of the given player in milliseconds
+    fn average(&self, player: usize) -> f64 {
+        match self.moves[player] {
+            0 => 0.,
+            moves => self.time[player].as_secs_f64() * 1000. / moves as f64,
+        }
+    }
+
+    /// Prints the results in a single row
+    fn print(&self, name: &str) {
+        println!(
+            "{:<10} {:>6} {:>6} {:>6} {:>9.1}ms {:>9.1}ms",
+            name,
+            self.wins[0],
+            self.wins[1],
+            self.draws,
+            self.average(0),
+            self.average(1)
+        );
+    }
+}
+
+/// Plays given number of games between the two players on each board size
+/// and prints the results. Players are names of the given engines or bot
+/// commands, they alternate who starts.
+pub fn run(
+    players: &[String],
+    games: usize,
+    sizes: &[ArenaSize],
+    time: Option<Duration>,
+    engines: &Registry,
+) -> Result<(), Error> {
+    let [first, second] = players else {
+        return Err(Error::parse("arena needs exactly two players"));
+    };
+    let mut players = [
+        Contestant::new(first, time, engines)?,
+        Contestant::new(second, time, engines)?,
+    ];
+
+    println!("1: {}", players[0].name);
+    println!("2: {}", players[1].name);
+    println!(
+        "{} {:>6} {:>6} {:>6} {:>11} {:>11}",
+        format!("{:<10}", "size").fg(Color::Green),
+        "1 wins",
+        "2 wins",
+        "draws",
+        "1 avg",
+        "2 avg"
+    );
+    let mut total = Score::default();
+    for (w, h, win) in sizes {
+        let mut score = Score::default();
+        for game in 0..games {
+            let board = Board::new(*w, *h, *win);
+            play(&mut players, board, game % 2, &mut score)?;
+        }
+        score.print(&format!("{w}x{h}:{win}"));
+        total.add(&score);
+    }
+    total.print("total");
+    Ok(())
+}
+
+/// Plays single game, where the player with the given index starts.
+/// Player, who fails to play a legal move in time, loses.
+fn play(
+    players: &mut [Contestant; 2],
+    board: Board,
+    first: usize,
+    score: &mut Score,
+) -> Result<(), Error> {
+    let mut game = Game::new(board, Mode::Normal);
+    let mut turn = first;
+    loop {
+        let start = Instant::now();
+        let res = players[turn]
+            .engine
+            .choose_move(&game.board, game.current_player());
+        score.time[turn] += start.elapsed();
+        score.moves[turn] += 1;
+
+        let pos = match res {
+            Ok(Some(pos)) => pos,
+            Ok(None) => {
+                score.draws += 1;
+                return Ok(());
+            }
+            Err(e) => {
+                let msg =
+                    format!("{}: {e}, game forfeited", players[turn].name);
+                println!("{}", msg.fg(Color::Yellow));
+                players[turn].restart()?;
+                score.wins[1 - turn] += 1;
+                return Ok(());
+            }
+        };
+        game.play(pos)?;
+        match game.result() {
+            Some(Cell::Empty) => score.draws += 1,
+            Some(_) => score.wins[turn] += 1,
+            None => {
+                turn = 1 - turn;
+                continue;
+            }
+        }
+        return Ok(());
+    }
+}
+
+/// Parses board size with optional win length (e.g. `7x7:4`)
+pub fn parse_size(val: &str) -> Result<ArenaSize, Error> {
+    let invalid =
+        || Error::parse(format!("size expected (e.g. 7x7:4), got '{val}'"));
+    let (size, win) = match val.split_once(':') {
+        Some((size, win)) => (size, Some(win)),
+        None => (val, None),
+    };
+    let (w, h) = size.split_once('x').ok_or_else(invalid)?;
+    let (w, h) = match (w.parse::<usize>(), h.parse::<usize>()) {
+        (Ok(w), Ok(h)) if w >= 3 && h >= 3 => (w, h),
+        _ => Err(invalid())?,
+    };
+    let win = match win {
+        Some(win) => win.parse::<usize>().map_err(|_| invalid())?,
+        None => min(max(w, h), 5),
+    };
+    WinLen::new(win).check(w, h)?;
+    Ok((w, h, win))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("7x7:4").unwrap(), (7, 7, 4));
+        assert_eq!(parse_size("3x4").unwrap(), (3, 4, 4));
+        assert_eq!(parse_size("20x9").unwrap(), (20, 9, 5));
+        assert!(parse_size("2x3").is_err());
+        assert!(parse_size("5x5:6").is_err());
+        assert!(parse_size("5x5:2").is_err());
+        assert!(parse_size("5:5").is_err());
+    }
+
+    #[test]
+    fn plays_all_games() {
+        let engines = Registry::default();
+        let mut players = [
+            Contestant::new("easy", None, &engines).unwrap(),
+            Contestant::new("medium", None, &engines).unwrap(),
+        ];
+        let mut score = Score::default();
+        for game in 0..4 {
+            let board = Board::new(3, 3, 3);
+            play(&mut players, board, game % 2, &mut score).unwrap();
+        }
+        assert_eq!(score.wins[0] + score.wins[1] + score.draws, 4);
+    }
+}
@@ -1,41 +1,861 @@
+use std::{
+    cmp::{max, min},
+    iter::Peekable,
+    time::Duration,
+};
+
 use termint::{
     enums::Color,
-    geometry::Coords,
     help,
     widgets::{Grad, StrSpanExtension},
 };
 
-use crate::error::Error;
+use crate::{
+    arena::{parse_size, ArenaSize, DEFAULT_SIZES},
+    color_mode::ColorMode,
+    completions::Shell,
+    engine::Registry,
+    error::Error,
+    marks::Marks,
+    mode::Mode,
+    notation::Origin,
+    pos::Pos,
+    preset::Preset,
+    rng::Rng,
+    server::DEFAULT_PORT,
+    slots,
+    sound::SoundMode,
+    tune,
+    win_len::WinLen,
+};
+
+/// Values a flag parameter can have, used by the shell completions
+#[derive(Debug, Clone, Copy)]
+pub enum Values {
+    None,
+    Any,
+    File,
+    List(&'static [&'static str]),
+    /// Names of the registered AI engines
+    Engines,
+}
 
-/// Parses given arguments and checks for arguments conditions
+impl Values {
+    /// Gets the listed values, empty when the values aren't listed. AI
+    /// names are listed from the given engines.
+    pub fn list(&self, engines: &Registry) -> Vec<&'static str> {
+        match self {
+            Values::List(vals) => vals.to_vec(),
+            Values::Engines => engines.names(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Definition of the flag used by the help and the shell completions
+#[derive(Debug, Clone, Copy)]
+pub struct Flag {
+    pub names: &'static [&'static str],
+    pub params: &'static [&'static str],
+    pub values: Values,
+    pub help: &'static str,
+}
+
+impl Flag {
+    /// Creates new [`Flag`] without parameters
+    const fn new(names: &'static [&'static str], help: &'static str) -> Self {
+        Self {
+            names,
+            params: &[],
+            values: Values::None,
+            help,
+        }
+    }
+
+    /// Creates new [`Flag`] with given parameters and their values
+    const fn param(
+        names: &'static [&'static str],
+        params: &'static [&'static str],
+        values: Values,
+        help: &'static str,
+    ) -> Self {
+        Self {
+            names,
+            params,
+            values,
+            help,
+        }
+    }
+}
+
+/// Maximum number of the notakto boards
+const MAX_BOARDS: usize = 5;
+/// Number of the arena games on each board size, when not given
+const DEFAULT_GAMES: usize = 10;
+
+/// Subcommands with their flags
+pub const SUBCOMMANDS: &[(&str, &[Flag])] = &[
+    ("arena", ARENA_FLAGS),
+    ("bench", BENCH_FLAGS),
+    ("completions", &[]),
+    ("serve", SERVE_FLAGS),
+    ("tournament", &[]),
+    ("tune", TUNE_FLAGS),
+];
+
+/// Flags of the game
+pub const FLAGS: &[Flag] = &[
+    Flag::param(
+        &["-s", "--size"],
+        &["width", "height"],
+        Values::Any,
+        "Sets size of the game",
+    ),
+    Flag::param(&["-w", "--win"], &["len"], Values::Any, "Sets win length"),
+    Flag::param(
+        &["--win-rows"],
+        &["len"],
+        Values::Any,
+        "Sets win length in rows",
+    ),
+    Flag::param(
+        &["--win-cols"],
+        &["len"],
+        Values::Any,
+        "Sets win length in columns",
+    ),
+    Flag::param(
+        &["--win-diag"],
+        &["len"],
+        Values::Any,
+        "Sets win length in diagonals",
+    ),
+    Flag::param(
+        &["-b", "--blocks"],
+        &["count"],
+        Values::Any,
+        "Randomly blocks given number of cells",
+    ),
+    Flag::param(
+        &["--handicap"],
+        &["count"],
+        Values::Any,
+        "Places marks of the second player before the start",
+    ),
+    Flag::param(
+        &["--pieces"],
+        &["count"],
+        Values::Any,
+        "Limits pieces of each player, placed pieces are then moved",
+    ),
+    Flag::param(
+        &["--decay"],
+        &["moves"],
+        Values::Any,
+        "Removes each mark after given number of further moves",
+    ),
+    Flag::param(
+        &["--fog"],
+        &["radius"],
+        Values::Any,
+        "Shows only cells within given distance from own marks",
+    ),
+    Flag::param(
+        &["--assist"],
+        &["hints"],
+        Values::Any,
+        "Gives each player hints per game, asks before letting opponent win",
+    ),
+    Flag::param(
+        &["--best-of"],
+        &["rounds"],
+        Values::Any,
+        "Plays match, first to win most of the rounds wins",
+    ),
+    Flag::new(
+        &["--swap-rule"],
+        "Second player can swap sides after the first move",
+    ),
+    Flag::new(
+        &["--points"],
+        "Plays until the board is full, each completed line scores",
+    ),
+    Flag::new(
+        &["--infinite"],
+        "Board grows when a mark is placed near its edge",
+    ),
+    Flag::param(
+        &["--preset"],
+        &["name"],
+        Values::List(Preset::NAMES),
+        "Sets board size, win length and mode, more in the config",
+    ),
+    Flag::param(
+        &["--mode"],
+        &["mode"],
+        Values::List(&["normal", "order-chaos", "wild", "notakto"]),
+        "Sets game variant, players place either mark except normal",
+    ),
+    Flag::param(
+        &["--boards"],
+        &["count"],
+        Values::Any,
+        "Sets number of the notakto boards (1 to 5, default 3)",
+    ),
+    Flag::new(
+        &["--daily"],
+        "Plays daily challenge against the AI, same board for everyone",
+    ),
+    Flag::param(
+        &["--export"],
+        &["path"],
+        Values::File,
+        "Exports finished games as SVG or PNG image, or as .ttt record",
+    ),
+    Flag::param(
+        &["--import"],
+        &["path"],
+        Values::File,
+        "Loads game record (.ttt) and replays it, --delay between moves",
+    ),
+    Flag::param(
+        &["--save-as"],
+        &["name"],
+        Values::Any,
+        "Saves the game to the named slot on quit",
+    ),
+    Flag::param(
+        &["--load"],
+        &["name"],
+        Values::Any,
+        "Loads game from the named slot, like --import",
+    ),
+    Flag::param(
+        &["--record"],
+        &["path"],
+        Values::File,
+        "Records the actions of the session to the file on quit",
+    ),
+    Flag::param(
+        &["--playback"],
+        &["path"],
+        Values::File,
+        "Plays back the recorded session, --speed like 2x",
+    ),
+    Flag::param(
+        &["--speed"],
+        &["factor"],
+        Values::Any,
+        "Sets speed of the playback (default 1x)",
+    ),
+    Flag::new(&["--relative"], "Shows distances from the selected cell"),
+    Flag::new(
+        &["--accessible"],
+        "Plays in plain text for screen readers instead of the TUI",
+    ),
+    Flag::param(
+        &["--lang"],
+        &["code"],
+        Values::Any,
+        "Sets language of the texts, like cs (default from LANG)",
+    ),
+    Flag::param(
+        &["--color-mode"],
+        &["mode"],
+        Values::List(&["full", "colorblind", "mono"]),
+        "Sets how colors are used",
+    ),
+    Flag::param(
+        &["--marks"],
+        &["style"],
+        Values::List(&["ascii", "wide", "emoji"]),
+        "Sets glyphs the marks are drawn with",
+    ),
+    Flag::param(
+        &["--numbering"],
+        &["origin"],
+        Values::List(&["top-left", "bottom-left"]),
+        "Sets corner the rows are numbered from",
+    ),
+    Flag::new(
+        &["--rtl"],
+        "Letters columns from the right, row numbers are on the right",
+    ),
+    Flag::new(&["-d", "--demo"], "Two AI players play against each other"),
+    Flag::param(
+        &["--delay"],
+        &["ms"],
+        Values::Any,
+        "Sets delay between demo moves in ms",
+    ),
+    Flag::param(
+        &["-a", "--ai"],
+        &["name"],
+        Values::Engines,
+        "Sets AI engine (difficulty or other registered engine)",
+    ),
+    Flag::param(
+        &["--ai-time"],
+        &["time"],
+        Values::Any,
+        "Sets AI time per move (e.g. 500ms or 2s)",
+    ),
+    Flag::param(
+        &["--bot"],
+        &["command"],
+        Values::Any,
+        "Plays against external bot program, time per move is --ai-time",
+    ),
+    Flag::new(
+        &["--deterministic"],
+        "Disables randomness of the AI and blocked cells",
+    ),
+    Flag::param(
+        &["--seed"],
+        &["seed"],
+        Values::Any,
+        "Sets seed of the random boards and AI, day of the daily challenge",
+    ),
+    Flag::param(
+        &["--tt-size"],
+        &["mb"],
+        Values::Any,
+        "Sets AI transposition table size in MB",
+    ),
+    Flag::param(
+        &["--sound"],
+        &["mode"],
+        Values::List(&["off", "bell", "on"]),
+        "Sets sound mode",
+    ),
+    Flag::param(
+        &["-t", "--theme"],
+        &["file"],
+        Values::File,
+        "Imports base16 or terminal color scheme",
+    ),
+    Flag::new(&["-h", "--help"], "Prints this help"),
+];
+
+/// Flags of the bench subcommand
+const BENCH_FLAGS: &[Flag] = &[
+    Flag::param(
+        &["--compare"],
+        &["file"],
+        Values::File,
+        "Compares results with saved baseline",
+    ),
+    Flag::param(
+        &["--save"],
+        &["file"],
+        Values::File,
+        "Saves results to the given file",
+    ),
+];
+
+/// Flags of the arena subcommand
+const ARENA_FLAGS: &[Flag] = &[
+    Flag::param(
+        &["--games"],
+        &["count"],
+        Values::Any,
+        "Sets number of games on each board size",
+    ),
+    Flag::param(
+        &["--sizes"],
+        &["sizes"],
+        Values::Any,
+        "Sets board sizes with win lengths (e.g. 3x3,7x7:4)",
+    ),
+    Flag::param(
+        &["--time"],
+        &["time"],
+        Values::Any,
+        "Sets time per move (e.g. 500ms or 2s)",
+    ),
+];
+
+/// Flags of the serve subcommand
+const SERVE_FLAGS: &[Flag] = &[Flag::param(
+    &["--port"],
+    &["port"],
+    Values::Any,
+    "Sets local port the engine listens on",
+)];
+
+/// Flags of the tune subcommand
+const TUNE_FLAGS: &[Flag] = &[
+    Flag::param(
+        &["--rounds"],
+        &["count"],
+        Values::Any,
+        "Sets number of hill climbing rounds",
+    ),
+    Flag::param(
+        &["--games"],
+        &["count"],
+        Values::Any,
+        "Sets number of games on each board size in a round",
+    ),
+    Flag::param(
+        &["--sizes"],
+        &["sizes"],
+        Values::Any,
+        "Sets board sizes with win lengths (e.g. 7x7:4,15x15)",
+    ),
+    Flag::param(
+        &["--save"],
+        &["file"],
+        Values::File,
+        "Saves weights to the given file instead of the loaded one",
+    ),
+];
+
+/// Subcommand run instead of the game
 #[derive(Debug)]
+pub enum Subcommand {
+    /// Games between two AI difficulties or bot commands
+    Arena {
+        players: Vec<String>,
+        games: usize,
+        sizes: Vec<ArenaSize>,
+        time: Option<Duration>,
+    },
+    Bench {
+        compare: Option<String>,
+        save: Option<String>,
+    },
+    Completions(Shell),
+    /// Engine server listening on the given port
+    Serve(u16),
+    /// Tournament of the given players, saved one is resumed without players
+    Tournament(Vec<String>),
+    /// Self-play tuning of the medium AI weights
+    Tune {
+        rounds: usize,
+        games: usize,
+        sizes: Vec<ArenaSize>,
+        save: Option<String>,
+    },
+}
+
+/// Parses given arguments and checks for arguments conditions
+#[derive(Debug, Default)]
 pub struct Args {
-    pub size: Option<Coords>,
+    pub size: Option<Pos>,
     pub win_len: Option<usize>,
+    pub win_rows: Option<usize>,
+    pub win_cols: Option<usize>,
+    pub win_diag: Option<usize>,
+    pub demo: bool,
+    pub delay: Option<usize>,
+    /// Name of the AI engine
+    pub ai: Option<String>,
+    pub ai_time: Option<Duration>,
+    /// Command starting the external bot opponent
+    pub bot: Option<String>,
+    pub theme: Option<String>,
+    pub blocks: usize,
+    pub handicap: usize,
+    pub swap_rule: bool,
+    pub points: bool,
+    pub infinite: bool,
+    pub pieces: Option<usize>,
+    pub decay: Option<usize>,
+    pub fog: Option<usize>,
+    /// Number of hints per game in the assisted mode
+    pub assist: Option<usize>,
+    pub mode: Mode,
+    /// Name of the preset, which is applied once the config is loaded
+    pub preset: Option<String>,
+    pub boards: Option<usize>,
+    pub daily: bool,
+    pub best_of: Option<usize>,
+    pub relative: bool,
+    pub marks: Option<Marks>,
+    /// Corner the rows are numbered from, config is used when not set
+    pub origin: Option<Origin>,
+    pub rtl: bool,
+    pub color_mode: Option<ColorMode>,
+    /// Code of the language of the texts, system language when not set
+    pub lang: Option<String>,
+    pub accessible: bool,
+    pub export: Option<String>,
+    /// Path of the game record to replay
+    pub import: Option<String>,
+    /// Name of the slot the game is saved to on quit
+    pub save_as: Option<String>,
+    /// Name of the slot to load the game from
+    pub load: Option<String>,
+    /// Path of the file the session's actions are recorded to
+    pub record: Option<String>,
+    /// Path of the recorded session to play back
+    pub playback: Option<String>,
+    /// Speed of the playback, delays are divided by it
+    pub speed: Option<f64>,
+    pub deterministic: bool,
+    pub seed: Option<u64>,
+    pub tt_size: Option<usize>,
+    pub sound: Option<SoundMode>,
+    pub cmd: Option<Subcommand>,
     pub help: bool,
+    /// AI engines selectable by their name
+    pub engines: Registry,
 }
 
 impl Args {
-    /// Parses arguments
-    pub fn parse(args: std::env::Args) -> Result<Args, Error> {
-        let mut parsed = Self::default();
+    /// Parses arguments, AI is selected from the given engines
+    pub fn parse<I>(args: I, engines: Registry) -> Result<Args, Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Self {
+            engines,
+            ..Self::default()
+        };
 
-        let mut args_iter = args.into_iter();
-        args_iter.next();
-        while let Some(arg) = args_iter.next() {
-            match arg.as_str() {
-                "-s" | "--size" => parsed.parse_size(&mut args_iter)?,
-                "-w" | "--win" => parsed.parse_win(&mut args_iter)?,
-                "-h" | "--help" => parsed.help = true,
-                arg => Err(format!("unexpected argument: '{arg}'"))?,
+        let mut args_iter = args.into_iter().skip(1).peekable();
+        if args_iter.next_if(|a| a == "arena").is_some() {
+            parsed.cmd = Some(
+                Args::parse_arena(&mut args_iter)
+                    .map_err(|e| e.arg(Some("arena")))?,
+            );
+        } else if args_iter.next_if(|a| a == "bench").is_some() {
+            parsed.cmd = Some(
+                Args::parse_bench(&mut args_iter)
+                    .map_err(|e| e.arg(Some("bench")))?,
+            );
+        } else if args_iter.next_if(|a| a == "completions").is_some() {
+            let shell = Args::get_val(&mut args_iter)
+                .and_then(|s| s.parse())
+                .map_err(|e| e.arg(Some("completions")))?;
+            parsed.cmd = Some(Subcommand::Completions(shell));
+        } else if args_iter.next_if(|a| a == "serve").is_some() {
+            parsed.cmd = Some(
+                Args::parse_serve(&mut args_iter)
+                    .map_err(|e| e.arg(Some("serve")))?,
+            );
+        } else if args_iter.next_if(|a| a == "tournament").is_some() {
+            let mut players = vec![];
+            while let Some(name) = args_iter.next_if(|a| !a.starts_with('-')) {
+                players.push(name);
             }
+            parsed.cmd = Some(Subcommand::Tournament(players));
+        } else if args_iter.next_if(|a| a == "tune").is_some() {
+            parsed.cmd = Some(
+                Args::parse_tune(&mut args_iter)
+                    .map_err(|e| e.arg(Some("tune")))?,
+            );
         }
 
+        while let Some(arg) = args_iter.next() {
+            parsed
+                .parse_flag(&arg, &mut args_iter)
+                .map_err(|e| e.arg(Some(&arg)))?;
+        }
+
+        parsed.check().map_err(|e| e.arg(None))?;
         Ok(parsed)
     }
 
+    /// Checks whether the parsed arguments can be combined
+    fn check(&self) -> Result<(), Error> {
+        if let Some(size) = self.size {
+            let win = self.win_len.unwrap_or(min(max(size.x, size.y), 5));
+            WinLen::new(win)
+                .with(self.win_rows, self.win_cols, self.win_diag)
+                .check(size.x, size.y)?;
+        }
+        let tournament = matches!(self.cmd, Some(Subcommand::Tournament(_)));
+        if tournament && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "tournament can't be played in the demo or accessible mode",
+            ))?;
+        }
+        if tournament && self.best_of.is_some() {
+            Err(Error::parse(
+                "tournament matches can't be played as best-of",
+            ))?;
+        }
+        if self.daily && (tournament || self.demo || self.accessible) {
+            Err(Error::parse(
+                "daily challenge can't be played in the tournament, demo or \
+                accessible mode",
+            ))?;
+        }
+        if self.mode != Mode::Normal
+            && (self.demo || self.accessible || self.daily)
+        {
+            Err(Error::parse(format!(
+                "{} mode can't be played in the demo, accessible or daily \
+                mode",
+                self.mode.name()
+            )))?;
+        }
+        if self.mode == Mode::Notakto && self.changes_board() {
+            Err(Error::parse(
+                "notakto is played on 3x3 boards without special rules",
+            ))?;
+        }
+        if self.mode != Mode::Notakto && self.boards.is_some() {
+            Err(Error::parse(
+                "multiple boards can be played only in the notakto mode",
+            ))?;
+        }
+        if self.pieces.is_some() && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "pieces limit can't be used in the demo or accessible mode",
+            ))?;
+        }
+        if self.pieces.is_some() && (self.mode != Mode::Normal || self.points)
+        {
+            Err(Error::parse(
+                "pieces limit can't be combined with variants or points",
+            ))?;
+        }
+        if self.decay.is_some() && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "decay can't be used in the demo or accessible mode",
+            ))?;
+        }
+        if self.decay.is_some()
+            && (self.mode != Mode::Normal
+                || self.points
+                || self.pieces.is_some()
+                || self.handicap > 0)
+        {
+            Err(Error::parse(
+                "decay can't be combined with variants, points, pieces limit \
+                or handicap",
+            ))?;
+        }
+        if self.fog.is_some() && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "fog of war can't be used in the demo or accessible mode",
+            ))?;
+        }
+        if self.fog.is_some() && self.mode != Mode::Normal {
+            Err(Error::parse("fog of war can't be combined with variants"))?;
+        }
+        if self.infinite && self.accessible {
+            Err(Error::parse(
+                "infinite board can't be played in the accessible mode",
+            ))?;
+        }
+        if self.infinite
+            && (self.points || self.pieces.is_some() || self.decay.is_some())
+        {
+            Err(Error::parse(
+                "infinite board can't be combined with points, pieces limit \
+                or decay",
+            ))?;
+        }
+        if self.assist.is_some() && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "assisted mode can't be used in the demo or accessible mode",
+            ))?;
+        }
+        if self.assist.is_some()
+            && (self.mode != Mode::Normal
+                || self.points
+                || self.pieces.is_some()
+                || self.decay.is_some()
+                || self.fog.is_some())
+        {
+            Err(Error::parse(
+                "assisted mode can't be combined with variants, points, \
+                pieces limit, decay or fog of war",
+            ))?;
+        }
+        if self.bot.is_some()
+            && (tournament || self.demo || self.accessible || self.daily)
+        {
+            Err(Error::parse(
+                "bot can't be played in the tournament, demo, accessible or \
+                daily mode",
+            ))?;
+        }
+        if self.bot.is_some()
+            && (self.mode != Mode::Normal
+                || self.swap_rule
+                || self.pieces.is_some()
+                || self.decay.is_some()
+                || self.fog.is_some())
+        {
+            Err(Error::parse(
+                "bot can't play variants, swap rule, pieces limit, decay or \
+                fog of war",
+            ))?;
+        }
+        if self.record.is_some() && self.playback.is_some() {
+            Err(Error::parse(
+                "session can't be both recorded and played back",
+            ))?;
+        }
+        if (self.record.is_some() || self.playback.is_some())
+            && (self.demo || self.accessible)
+        {
+            Err(Error::parse(
+                "session can't be recorded or played back in the demo or \
+                accessible mode",
+            ))?;
+        }
+        if self.speed.is_some() && self.playback.is_none() {
+            Err(Error::parse("speed can be set only for the playback"))?;
+        }
+        if self.import.is_some() && self.load.is_some() {
+            Err(Error::parse(
+                "game can't be both imported and loaded from a slot",
+            ))?;
+        }
+        if self.save_as.is_some() && (self.demo || self.accessible) {
+            Err(Error::parse(
+                "game can't be saved in the demo or accessible mode",
+            ))?;
+        }
+        let import = self.import.is_some() || self.load.is_some();
+        if import
+            && (tournament
+                || self.demo
+                || self.accessible
+                || self.daily
+                || self.bot.is_some())
+        {
+            Err(Error::parse(
+                "game record can't be replayed in the tournament, demo, \
+                accessible, daily or bot mode",
+            ))?;
+        }
+        if import && (self.mode != Mode::Normal || self.changes_board()) {
+            Err(Error::parse("game record sets the board and rules itself"))?;
+        }
+        if self.mode.free_marks() && (self.points || self.swap_rule) {
+            Err(Error::parse(format!(
+                "{} mode can't be combined with points or swap rule",
+                self.mode.name()
+            )))?;
+        }
+        if self.daily && self.changes_board() {
+            Err(Error::parse("daily challenge board can't be changed"))?;
+        }
+        Ok(())
+    }
+
+    /// Parses the given flag and its parameters from the arguments
+    fn parse_flag<T>(&mut self, arg: &str, args: &mut T) -> Result<(), Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        match arg {
+            "-s" | "--size" => self.parse_size(args)?,
+            "-w" | "--win" => self.win_len = Some(Args::get_win(args)?),
+            "--win-rows" => self.win_rows = Some(Args::get_win(args)?),
+            "--win-cols" => self.win_cols = Some(Args::get_win(args)?),
+            "--win-diag" => self.win_diag = Some(Args::get_win(args)?),
+            "-d" | "--demo" => self.demo = true,
+            "--delay" => self.delay = Some(Args::get_num(args)?),
+            "-a" | "--ai" => {
+                let name = Args::get_val(args)?;
+                self.engines.find(&name)?;
+                self.ai = Some(name);
+            }
+            "--ai-time" => self.ai_time = Some(Args::get_time(args)?),
+            "--bot" => self.bot = Some(Args::get_val(args)?),
+            "-t" | "--theme" => self.theme = Some(Args::get_val(args)?),
+            "-b" | "--blocks" => self.blocks = Args::get_num(args)?,
+            "--handicap" => self.handicap = Args::get_num(args)?,
+            "--swap-rule" => self.swap_rule = true,
+            "--points" => self.points = true,
+            "--infinite" => self.infinite = true,
+            "--pieces" => self.parse_pieces(args)?,
+            "--decay" => self.parse_decay(args)?,
+            "--fog" => self.parse_fog(args)?,
+            "--assist" => self.assist = Some(Args::get_num(args)?),
+            "--mode" => self.mode = Args::get_val(args)?.parse()?,
+            "--preset" => self.preset = Some(Args::get_val(args)?),
+            "--boards" => self.parse_boards(args)?,
+            "--daily" => self.daily = true,
+            "--relative" => self.relative = true,
+            "--accessible" => self.accessible = true,
+            "--lang" => self.lang = Some(Args::get_val(args)?),
+            "--color-mode" => {
+                self.color_mode = Some(Args::get_val(args)?.parse()?)
+            }
+            "--marks" => self.marks = Some(Args::get_val(args)?.parse()?),
+            "--numbering" => self.origin = Some(Args::get_val(args)?.parse()?),
+            "--rtl" => self.rtl = true,
+            "--deterministic" => self.deterministic = true,
+            "--seed" => self.seed = Some(Args::get_num(args)? as u64),
+            "--tt-size" => self.tt_size = Some(Args::get_num(args)?),
+            "--export" => self.export = Some(Args::get_val(args)?),
+            "--import" => self.import = Some(Args::get_val(args)?),
+            "--save-as" => self.save_as = Some(Args::get_slot(args)?),
+            "--load" => self.load = Some(Args::get_slot(args)?),
+            "--record" => self.record = Some(Args::get_val(args)?),
+            "--playback" => self.playback = Some(Args::get_val(args)?),
+            "--speed" => self.parse_speed(args)?,
+            "--best-of" => self.parse_best_of(args)?,
+            "--sound" => self.sound = Some(Args::get_val(args)?.parse()?),
+            "-h" | "--help" => self.help = true,
+            _ => Err(Error::parse("unexpected argument"))?,
+        }
+        Ok(())
+    }
+
+    /// Gets seed of the random generators, deterministic games use zero
+    /// and seed is based on the current time otherwise
+    pub fn get_seed(&self) -> u64 {
+        match (self.seed, self.deterministic) {
+            (Some(seed), _) => seed,
+            (None, true) => 0,
+            (None, false) => Rng::time_seed(),
+        }
+    }
+
+    /// Sets board size, win length and mode of the selected preset, user
+    /// presets are given by the config. Values given by the other arguments
+    /// take precedence.
+    pub fn apply_preset(
+        &mut self,
+        user: &[(String, Preset)],
+    ) -> Result<(), Error> {
+        let Some(name) = &self.preset else {
+            return Ok(());
+        };
+        let Some(preset) = Preset::find(name, user) else {
+            return Err(Error::parse(format!("unknown preset '{name}'")))?;
+        };
+        if self.mode == Mode::Normal {
+            self.mode = preset.mode;
+        }
+        // Notakto is always played on 3x3 boards
+        if self.mode != Mode::Notakto {
+            self.size.get_or_insert(preset.size);
+            self.win_len.get_or_insert(preset.win_len);
+        }
+        self.check().map_err(|e| e.arg(None))
+    }
+
+    /// Checks whether arguments change the board or the rules of the game
+    fn changes_board(&self) -> bool {
+        self.size.is_some()
+            || self.win_len.is_some()
+            || self.win_rows.is_some()
+            || self.win_cols.is_some()
+            || self.win_diag.is_some()
+            || self.blocks > 0
+            || self.handicap > 0
+            || self.swap_rule
+            || self.points
+            || self.infinite
+            || self.pieces.is_some()
+            || self.decay.is_some()
+            || self.fog.is_some()
+            || self.best_of.is_some()
+    }
+
     /// Displays help
-    pub fn help() {
+    pub fn help(&self) {
         println!(
             "Welcome to help for {} by {}\n",
             "tictactoe".fg(Color::Green),
@@ -45,11 +865,159 @@ impl Args {
             "Usage":
             "tictactoe" => "Opens 3x3 game with win length set to 3\n"
             "tictactoe" ["options"] => "Behaves according to options\n"
+            "tictactoe arena" ["players"] ["arena options"] => "Plays games between two AI difficulties or bot commands\n"
+            "tictactoe bench" ["bench options"] => "Runs AI and render benchmarks\n"
+            "tictactoe completions" ["shell"] => "Prints completions for bash, zsh or fish\n"
+            "tictactoe serve" ["serve options"] => "Serves the engine over JSON-RPC on local port\n"
+            "tictactoe tournament" ["players"] ["options"] => "Plays tournament of up to 8 players, resumes saved one without players\n"
+            "tictactoe tune" ["tune options"] => "Tunes medium AI weights by self-play\n"
             "Options":
-            "-s  --size" => "Sets size of the game\n"
-            "-w  --win" => "Sets win length\n"
-            "-h  --help" => "Prints this help"
         );
+        self.help_flags(FLAGS);
+        println!();
+        help!("Arena options":);
+        self.help_flags(ARENA_FLAGS);
+        println!();
+        help!("Bench options":);
+        self.help_flags(BENCH_FLAGS);
+        println!();
+        help!("Serve options":);
+        self.help_flags(SERVE_FLAGS);
+        println!();
+        help!("Tune options":);
+        self.help_flags(TUNE_FLAGS);
+    }
+
+    /// Displays help of the given flags
+    fn help_flags(&self, flags: &[Flag]) {
+        for (i, flag) in flags.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            print!("  {}", flag.names.join("  ").fg(Color::Yellow));
+            for param in flag.params {
+                print!(" [{param}]");
+            }
+            match flag.values {
+                Values::List(_) | Values::Engines => println!(
+                    "\n    {} ({})",
+                    flag.help,
+                    flag.values.list(&self.engines).join(", ")
+                ),
+                _ => println!("\n    {}", flag.help),
+            }
+        }
+    }
+
+    /// Parses arena subcommand players and options
+    fn parse_arena<T>(args: &mut Peekable<T>) -> Result<Subcommand, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let mut players = vec![];
+        while let Some(player) = args.next_if(|a| !a.starts_with('-')) {
+            players.push(player);
+        }
+        if players.len() != 2 {
+            Err(Error::parse("exactly two players expected"))?;
+        }
+
+        let (mut games, mut time) = (DEFAULT_GAMES, None);
+        let mut sizes = DEFAULT_SIZES.to_vec();
+        while let Some(arg) = args.next() {
+            let flag = |e: Error| e.arg(Some(&arg));
+            match arg.as_str() {
+                "--games" => games = Args::get_num(args).map_err(flag)?,
+                "--sizes" => {
+                    sizes = Args::get_val(args)
+                        .and_then(|s| s.split(',').map(parse_size).collect())
+                        .map_err(flag)?
+                }
+                "--time" => time = Some(Args::get_time(args).map_err(flag)?),
+                _ => Err(flag(Error::parse("unexpected argument")))?,
+            }
+        }
+        Ok(Subcommand::Arena {
+            players,
+            games,
+            sizes,
+            time,
+        })
+    }
+
+    /// Parses bench subcommand options
+    fn parse_bench<T>(args: &mut T) -> Result<Subcommand, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let (mut compare, mut save) = (None, None);
+        while let Some(arg) = args.next() {
+            let flag = |e: Error| e.arg(Some(&arg));
+            match arg.as_str() {
+                "--compare" => {
+                    compare = Some(Args::get_val(args).map_err(flag)?)
+                }
+                "--save" => save = Some(Args::get_val(args).map_err(flag)?),
+                _ => Err(flag(Error::parse("unexpected argument")))?,
+            }
+        }
+        Ok(Subcommand::Bench { compare, save })
+    }
+
+    /// Parses tune subcommand options
+    fn parse_tune<T>(args: &mut T) -> Result<Subcommand, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let (mut rounds, mut games) =
+            (tune::DEFAULT_ROUNDS, tune::DEFAULT_GAMES);
+        let (mut sizes, mut save) = (tune::DEFAULT_SIZES.to_vec(), None);
+        while let Some(arg) = args.next() {
+            let flag = |e: Error| e.arg(Some(&arg));
+            match arg.as_str() {
+                "--rounds" => rounds = Args::get_num(args).map_err(flag)?,
+                "--games" => games = Args::get_num(args).map_err(flag)?,
+                "--sizes" => {
+                    sizes = Args::get_val(args)
+                        .and_then(|s| s.split(',').map(parse_size).collect())
+                        .map_err(flag)?
+                }
+                "--save" => save = Some(Args::get_val(args).map_err(flag)?),
+                _ => Err(flag(Error::parse("unexpected argument")))?,
+            }
+        }
+        Ok(Subcommand::Tune {
+            rounds,
+            games,
+            sizes,
+            save,
+        })
+    }
+
+    /// Parses serve subcommand options
+    fn parse_serve<T>(args: &mut T) -> Result<Subcommand, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let mut port = DEFAULT_PORT;
+        while let Some(arg) = args.next() {
+            let flag = |e: Error| e.arg(Some(&arg));
+            match arg.as_str() {
+                "--port" => {
+                    port = Args::get_val(args)
+                        .and_then(|p| {
+                            p.parse().map_err(|_| {
+                                Error::parse(
+                                    "expected port between 0 and 65535",
+                                )
+                            })
+                        })
+                        .map_err(flag)?
+                }
+                _ => Err(flag(Error::parse("unexpected argument")))?,
+            }
+        }
+        Ok(Subcommand::Serve(port))
     }
 
     /// Parses size from the given arguments
@@ -57,48 +1025,168 @@ impl Args {
     where
         T: Iterator<Item = String>,
     {
-        let size = Coords::new(Args::get_num(args)?, Args::get_num(args)?);
+        let size = Pos::new(Args::get_num(args)?, Args::get_num(args)?);
         if size.x < 3 || size.y < 3 {
-            return Err(Error::Msg("minimum supported size is 3".into()));
+            return Err(Error::parse("minimum supported size is 3"));
         }
         self.size = Some(size);
         Ok(())
     }
 
-    /// Parses win length from the given arguments
-    fn parse_win<T>(&mut self, args: &mut T) -> Result<(), Error>
+    /// Parses number of pieces of each player from the given arguments
+    fn parse_pieces<T>(&mut self, args: &mut T) -> Result<(), Error>
     where
         T: Iterator<Item = String>,
     {
-        self.win_len = Some(Args::get_num(args)?);
-        if self.win_len.unwrap() < 3 {
-            return Err(Error::Msg(
-                "minimum supported win length is 3".into(),
-            ));
+        let pieces = Args::get_num(args)?;
+        if pieces < 3 {
+            return Err(Error::parse("each player needs at least 3 pieces"));
         }
+        self.pieces = Some(pieces);
         Ok(())
     }
 
-    /// Gets number (usize) from args
-    fn get_num<T>(args: &mut T) -> Result<usize, Error>
+    /// Parses number of moves after which the marks decay from the given
+    /// arguments
+    fn parse_decay<T>(&mut self, args: &mut T) -> Result<(), Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let decay = Args::get_num(args)?;
+        if decay == 0 {
+            return Err(Error::parse("marks have to last at least 1 move"));
+        }
+        self.decay = Some(decay);
+        Ok(())
+    }
+
+    /// Parses radius of the fog of war from the given arguments
+    fn parse_fog<T>(&mut self, args: &mut T) -> Result<(), Error>
     where
         T: Iterator<Item = String>,
     {
-        let Some(val) = args.next() else {
-            return Err(Error::Msg("missing argument parameter".into()));
+        let fog = Args::get_num(args)?;
+        if fog == 0 {
+            return Err(Error::parse("fog radius has to be at least 1"));
+        }
+        self.fog = Some(fog);
+        Ok(())
+    }
+
+    /// Parses speed of the playback, like `2x` or `0.5`
+    fn parse_speed<T>(&mut self, args: &mut T) -> Result<(), Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let val = Args::get_val(args)?;
+        let speed = val
+            .strip_suffix('x')
+            .unwrap_or(&val)
+            .parse::<f64>()
+            .ok()
+            .filter(|s| s.is_finite() && *s > 0.)
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "invalid speed '{val}', expected like 2x"
+                ))
+            })?;
+        self.speed = Some(speed);
+        Ok(())
+    }
+
+    /// Parses number of the notakto boards from the given arguments
+    fn parse_boards<T>(&mut self, args: &mut T) -> Result<(), Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let boards = Args::get_num(args)?;
+        if !(1..=MAX_BOARDS).contains(&boards) {
+            return Err(Error::parse(format!(
+                "number of boards has to be between 1 and {MAX_BOARDS}"
+            )));
+        }
+        self.boards = Some(boards);
+        Ok(())
+    }
+
+    /// Gets win length from the given arguments
+    fn get_win<T>(args: &mut T) -> Result<usize, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let len = Args::get_num(args)?;
+        if len < 3 {
+            return Err(Error::parse("minimum supported win length is 3"));
+        }
+        Ok(len)
+    }
+
+    /// Parses number of match rounds from the given arguments
+    fn parse_best_of<T>(&mut self, args: &mut T) -> Result<(), Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let rounds = Args::get_num(args)?;
+        if rounds == 0 {
+            return Err(Error::parse("match needs at least one round"));
+        }
+        self.best_of = Some(rounds);
+        Ok(())
+    }
+
+    /// Gets next argument parameter as time (e.g. 500ms or 2s), plain
+    /// number is in milliseconds
+    fn get_time<T>(args: &mut T) -> Result<Duration, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let val = Args::get_val(args)?;
+        let (num, scale) = if let Some(ms) = val.strip_suffix("ms") {
+            (ms, 0.001)
+        } else if let Some(s) = val.strip_suffix('s') {
+            (s, 1.)
+        } else {
+            (val.as_str(), 0.001)
         };
+        let time = num
+            .parse::<f64>()
+            .ok()
+            .filter(|t| t.is_finite() && *t > 0.)
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "time expected (e.g. 500ms), got '{val}'"
+                ))
+            })?;
+        Ok(Duration::from_secs_f64(time * scale))
+    }
 
+    /// Gets number (usize) from args
+    fn get_num<T>(args: &mut T) -> Result<usize, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let val = Args::get_val(args)?;
         val.parse::<usize>()
-            .map_err(|_| Error::Msg(format!("number expected, got '{val}'")))
+            .map_err(|_| Error::parse(format!("number expected, got '{val}'")))
     }
-}
 
-impl Default for Args {
-    fn default() -> Self {
-        Self {
-            size: None,
-            win_len: None,
-            help: false,
-        }
+    /// Gets next argument parameter from args
+    fn get_val<T>(args: &mut T) -> Result<String, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        args.next()
+            .ok_or_else(|| Error::parse("missing argument parameter"))
+    }
+
+    /// Gets next argument parameter from args and checks it's a valid
+    /// slot name
+    fn get_slot<T>(args: &mut T) -> Result<String, Error>
+    where
+        T: Iterator<Item = String>,
+    {
+        let name = Args::get_val(args)?;
+        slots::path(&name)?;
+        Ok(name)
     }
 }
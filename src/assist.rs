@@ -0,0 +1,112 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use crate::{ai::Ai, board::Board, cell::Cell, pos::Pos};
+
+/// Time the AI searches for the hint
+pub const HINT_TIME: Duration = Duration::from_millis(500);
+
+/// Assisted mode for beginners, which gives hints and asks to confirm
+/// moves letting the opponent win
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assist {
+    /// Number of hints each player gets per game
+    hints: usize,
+    /// Hints left to cross and circle in the current game
+    left: [usize; 2],
+}
+
+impl Assist {
+    /// Creates new [`Assist`] giving each player given number of hints
+    pub fn new(hints: usize) -> Self {
+        Self {
+            hints,
+            left: [hints; 2],
+        }
+    }
+
+    /// Gives both players all their hints again for the new game
+    pub fn restart(&mut self) {
+        self.left = [self.hints; 2];
+    }
+
+    /// Takes hint of the given player, returns number of the hints left or
+    /// [`None`], when the player has none
+    pub fn take(&mut self, player: Cell) -> Option<usize> {
+        let left = &mut self.left[(player == Cell::Circle) as usize];
+        *left = left.checked_sub(1)?;
+        Some(*left)
+    }
+
+    /// Checks whether the opponent can win next turn after the given player
+    /// plays on the given position
+    pub fn risky(board: &Board, player: Cell, pos: Pos) -> bool {
+        let mut board = board.clone();
+        if board.set(player, pos.x, pos.y).is_err() || board.state().is_some()
+        {
+            return false;
+        }
+        !board.winning_cells(player.next()).is_empty()
+    }
+}
+
+/// Hint searched on a background thread, so the game isn't blocked
+#[derive(Debug)]
+pub struct Hinting {
+    /// Number of the hints left to the player
+    pub left: usize,
+    /// Cells and the player on turn of the position the hint is for
+    position: (Vec<Cell>, Cell),
+    rx: Receiver<(Ai, Option<Pos>)>,
+}
+
+impl Hinting {
+    /// Starts searching for the hint of the given player with the given
+    /// engine for [`HINT_TIME`]
+    pub fn start(ai: Ai, board: &Board, player: Cell, left: usize) -> Self {
+        let mut ai = ai.time(Some(HINT_TIME));
+        let position = (board.cells.to_vec(), player);
+        let board = board.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (pos, _) = ai.search(&board, player);
+            _ = tx.send((ai, pos));
+        });
+        Self { left, position, rx }
+    }
+
+    /// Gets the engine back with the hint, when the search finished
+    pub fn result(&self) -> Option<(Ai, Option<Pos>)> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Checks whether the hint is for the position on the given board
+    pub fn is_for(&self, board: &Board, player: Cell) -> bool {
+        self.position.1 == player && board.cells.to_vec() == self.position.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_and_risky_moves() {
+        let mut assist = Assist::new(1);
+        assert_eq!(assist.take(Cell::Cross), Some(0));
+        assert_eq!(assist.take(Cell::Cross), None);
+        assert_eq!(assist.take(Cell::Circle), Some(0));
+        assist.restart();
+        assert_eq!(assist.take(Cell::Cross), Some(0));
+
+        let mut board = Board::new(3, 3, 3);
+        board.set(Cell::Circle, 0, 0).unwrap();
+        board.set(Cell::Circle, 1, 0).unwrap();
+        // Not blocking the circle's row loses
+        assert!(Assist::risky(&board, Cell::Cross, Pos::new(1, 1)));
+        assert!(!Assist::risky(&board, Cell::Cross, Pos::new(2, 0)));
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use termint::{
+    buffer::Buffer,
+    enums::Color,
+    geometry::{Coords, Rect},
+    widgets::{Span, StrSpanExtension, Widget},
+};
+
+use crate::{
+    ai::{Ai, Difficulty},
+    board::Board,
+    cell::Cell,
+    cells::Cells,
+    error::Error,
+    json::Json,
+    rng::Rng,
+    screen::Screen,
+};
+
+/// Minimum time spent measuring each value
+const MEASURE: Duration = Duration::from_millis(300);
+/// Relative change, which is considered a regression
+const THRESHOLD: f64 = 0.1;
+
+/// Benchmark cases: name, width, height, win length and random moves played
+const CASES: &[(&str, usize, usize, usize, usize)] = &[
+    ("3x3-empty", 3, 3, 3, 0),
+    ("7x7-mid", 7, 7, 4, 10),
+    ("15x15-mid", 15, 15, 5, 20),
+    ("60x20-mid", 60, 20, 5, 30),
+    ("100x50-mid", 100, 50, 5, 40),
+];
+/// Board sizes the cell storages are compared on
+const STORAGE_SIZES: &[usize] = &[100, 150, 200, 300, 500];
+/// Random moves played in each game of the storage comparison
+const STORAGE_MOVES: usize = 200;
+
+/// Result of a single benchmark case
+#[derive(Debug, Clone, Default)]
+pub struct BenchResult {
+    pub name: String,
+    pub nodes_per_sec: f64,
+    pub render_us: f64,
+    /// Bytes written to the terminal after moving the selection
+    pub redraw_bytes: usize,
+    /// Bytes written to the terminal when redrawing everything
+    pub full_bytes: usize,
+}
+
+/// Runs the benchmark suite, compares it with the baseline file and saves
+/// the results when requested
+pub fn run(compare: Option<&str>, save: Option<&str>) -> Result<(), Error> {
+    let baseline = match compare {
+        Some(path) => Some(load(path)?),
+        None => None,
+    };
+
+    println!(
+        "{} {:>14} {:>12} {:>16}",
+        format!("{:<12}", "bench").fg(Color::Green),
+        "nodes/s",
+        "render",
+        "redraw/full"
+    );
+    let mut results = vec![];
+    let mut regressions = 0;
+    for (id, (name, w, h, win, moves)) in CASES.iter().enumerate() {
+        let res = bench_case(name, position(*w, *h, *win, *moves, id as u64));
+        print!(
+            "{:<12} {:>14.0} {:>10.1}µs {:>16}",
+            res.name,
+            res.nodes_per_sec,
+            res.render_us,
+            format!("{}B/{}B", res.redraw_bytes, res.full_bytes)
+        );
+
+        if let Some(old) = baseline
+            .as_ref()
+            .and_then(|b| b.iter().find(|r| r.name == res.name))
+        {
+            let nodes = res.nodes_per_sec / old.nodes_per_sec - 1.;
+            let render = res.render_us / old.render_us - 1.;
+            print!("  {} {}", change(nodes, false), change(render, true));
+            if nodes < -THRESHOLD || render > THRESHOLD {
+                regressions += 1;
+                print!("  {}", "REGRESSION".fg(Color::Red));
+            }
+        }
+        println!();
+        results.push(res);
+    }
+    storage();
+
+    if let Some(path) = save {
+        fs::write(path, to_json(&results).to_string())
+            .map_err(|e| Error::save(path, e))?;
+    }
+    match regressions {
+        0 => Ok(()),
+        n => Err(Error::Regressed(n)),
+    }
+}
+
+/// Measures AI search speed and render time on the given board
+fn bench_case(name: &str, board: Board) -> BenchResult {
+    let player = match board.history.len() % 2 {
+        0 => Cell::Cross,
+        _ => Cell::Circle,
+    };
+    // Table is disabled, so the repeated searches don't reuse results
+    let mut ai = Ai::new(Difficulty::Hard).table_size(0);
+    let (nodes, time) = measure(|| ai.search(&board, player).1);
+
+    let zero = Coords::new(0, 0);
+    // Right border is drawn one column past the reported width
+    let size = Coords::new(board.width(&zero) + 1, board.height(&zero));
+    let mut buffer = Buffer::empty(Rect::from_coords(Coords::new(1, 1), size));
+    let (renders, render_time) = measure(|| {
+        board.render(&mut buffer);
+        1
+    });
+
+    // Selection moves, so only the cells around it are redrawn
+    let mut moved = board.clone();
+    moved.selected.x = (moved.selected.x + 1) % moved.size.x;
+    let mut next = Buffer::empty(buffer.rect());
+    moved.render(&mut next);
+
+    BenchResult {
+        name: name.to_string(),
+        nodes_per_sec: nodes as f64 / time.as_secs_f64(),
+        render_us: render_time.as_secs_f64() * 1e6 / renders as f64,
+        redraw_bytes: Screen::frame(&next, Some(&buffer)).len(),
+        full_bytes: Screen::frame(&next, None).len(),
+    }
+}
+
+/// Prints moves per second played with the dense and the sparse cells on
+/// big boards, including creating the board
+fn storage() {
+    println!(
+        "{} {:>14} {:>12}",
+        format!("{:<12}", "storage").fg(Color::Green),
+        "dense mv/s",
+        "sparse mv/s"
+    );
+    for size in STORAGE_SIZES {
+        let speed = |cells: fn(usize) -> Cells| {
+            let mut rng = Rng::new(*size as u64);
+            let (moves, time) = measure(|| {
+                let mut board = Board::new(*size, *size, 5);
+                board.cells = cells(size * size);
+                let mut player = Cell::Cross;
+                for _ in 0..STORAGE_MOVES {
+                    let id = rng.range(size * size);
+                    if let Ok(state) = board.set(player, id % size, id / size)
+                    {
+                        player = player.next();
+                        if state.is_some() {
+                            break;
+                        }
+                    }
+                }
+                board.history.len()
+            });
+            moves as f64 / time.as_secs_f64()
+        };
+        println!(
+            "{:<12} {:>14.0} {:>12.0}",
+            format!("{size}x{size}"),
+            speed(Cells::dense),
+            speed(Cells::sparse)
+        );
+    }
+}
+
+/// Repeatedly runs given function for at least [`MEASURE`] time. Returns
+/// the sum of the function results and the elapsed time.
+fn measure<F: FnMut() -> usize>(mut f: F) -> (usize, Duration) {
+    let start = Instant::now();
+    let mut count = 0;
+    while start.elapsed() < MEASURE {
+        count += f();
+    }
+    (count, start.elapsed())
+}
+
+/// Creates board with given number of random moves played, which didn't
+/// end the game
+fn position(w: usize, h: usize, win: usize, moves: usize, seed: u64) -> Board {
+    let mut rng = Rng::new(seed);
+    loop {
+        let mut board = Board::new(w, h, win);
+        let mut player = Cell::Cross;
+        for _ in 0..moves {
+            let id = rng.range(w * h);
+            if board.set(player, id % w, id / w).is_ok() {
+                player = player.next();
+            }
+        }
+        if board.state().is_none() {
+            return board;
+        }
+    }
+}
+
+/// Formats relative change of the value, `lower` means lower is better
+fn change(val: f64, lower: bool) -> Span {
+    let text = format!("{:>+7.1}%", val * 100.);
+    let worse = if lower {
+        val > THRESHOLD
+    } else {
+        val < -THRESHOLD
+    };
+    match worse {
+        true => text.as_str().fg(Color::Red),
+        false => text.as_str().fg(Color::Gray),
+    }
+}
+
+/// Converts results to JSON
+fn to_json(results: &[BenchResult]) -> Json {
+    Json::Arr(
+        results
+            .iter()
+            .map(|r| {
+                Json::obj([
+                    ("name", r.name.as_str().into()),
+                    ("nodes_per_sec", r.nodes_per_sec.into()),
+                    ("render_us", r.render_us.into()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+/// Loads results from the given JSON file
+fn load(path: &str) -> Result<Vec<BenchResult>, Error> {
+    let text = fs::read_to_string(path).map_err(|e| Error::load(path, e))?;
+    let json = Json::parse(&text).map_err(|e| Error::load(path, e))?;
+    let invalid = || Error::load(path, "invalid baseline");
+
+    json.as_arr()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|r| {
+            Some(BenchResult {
+                name: r.get("name")?.as_str()?.to_string(),
+                nodes_per_sec: r.get("nodes_per_sec")?.as_f64()?,
+                render_us: r.get("render_us")?.as_f64()?,
+                ..Default::default()
+            })
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(invalid)
+}
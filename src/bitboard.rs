@@ -0,0 +1,431 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+use crate::{board::Board, cell::Cell, win_len::WinLen};
+
+/// Number of words of the [`Bits`]
+const WORDS: usize = 4;
+/// Number of bits of the [`Bits`]
+pub const MAX_BITS: usize = WORDS * 64;
+/// Number of binary digits of the longest window length
+const DIGITS: usize = (usize::BITS - MAX_BITS.leading_zeros()) as usize;
+/// Directions of the lines: right, down, down-right and down-left
+const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+
+/// Set of up to [`MAX_BITS`] bits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bits([u64; WORDS]);
+
+impl Bits {
+    /// Creates [`Bits`] with the first `len` bits set
+    pub fn ones(len: usize) -> Self {
+        let mut bits = Self::default();
+        for (i, word) in bits.0.iter_mut().enumerate() {
+            *word = match len.saturating_sub(i * 64) {
+                0 => 0,
+                64.. => u64::MAX,
+                n => (1 << n) - 1,
+            };
+        }
+        bits
+    }
+
+    /// Checks whether the bit on given index is set
+    pub fn get(&self, bit: usize) -> bool {
+        self.0[bit / 64] >> (bit % 64) & 1 == 1
+    }
+
+    /// Flips the bit on given index
+    pub fn toggle(&mut self, bit: usize) {
+        self.0[bit / 64] ^= 1 << (bit % 64);
+    }
+
+    /// Checks whether no bit is set
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|w| *w == 0)
+    }
+
+    /// Gets number of the set bits
+    pub fn count(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Iterates over indexes of the set bits in order
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        self.0.into_iter().enumerate().flat_map(|(i, mut word)| {
+            std::iter::from_fn(move || {
+                let bit = word.trailing_zeros() as usize;
+                word &= word.wrapping_sub(1);
+                (bit < 64).then_some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+impl BitAnd for Bits {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a &= b);
+        self
+    }
+}
+
+impl BitOr for Bits {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a |= b);
+        self
+    }
+}
+
+impl BitXor for Bits {
+    type Output = Self;
+
+    fn bitxor(mut self, rhs: Self) -> Self::Output {
+        self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a ^= b);
+        self
+    }
+}
+
+impl Not for Bits {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        self.0.iter_mut().for_each(|a| *a = !*a);
+        self
+    }
+}
+
+impl Shr<usize> for Bits {
+    type Output = Self;
+
+    /// Moves the bits to the lower indexes
+    fn shr(self, rhs: usize) -> Self::Output {
+        let mut res = Self::default();
+        let (words, bits) = (rhs / 64, rhs % 64);
+        for i in 0..WORDS.saturating_sub(words) {
+            res.0[i] = self.0[i + words] >> bits;
+            if bits > 0 && i + words + 1 < WORDS {
+                res.0[i] |= self.0[i + words + 1] << (64 - bits);
+            }
+        }
+        res
+    }
+}
+
+impl Shl<usize> for Bits {
+    type Output = Self;
+
+    /// Moves the bits to the higher indexes, the highest bits are dropped
+    fn shl(self, rhs: usize) -> Self::Output {
+        let mut res = Self::default();
+        let (words, bits) = (rhs / 64, rhs % 64);
+        for i in words..WORDS {
+            res.0[i] = self.0[i - words] << bits;
+            if bits > 0 && i > words {
+                res.0[i] |= self.0[i - words - 1] >> (64 - bits);
+            }
+        }
+        res
+    }
+}
+
+/// Board stored as a set of bits for each of the marks, so the lines are
+/// checked a word at a time. Rows are padded with an unused column, which
+/// stops the lines from wrapping to the next row.
+#[derive(Debug, Clone)]
+pub struct Bitboard {
+    pub width: usize,
+    pub height: usize,
+    pub win_len: WinLen,
+    /// Bits of the board cells, padding is left out
+    cells: Bits,
+    cross: Bits,
+    circle: Bits,
+    blocked: Bits,
+}
+
+impl Bitboard {
+    /// Checks whether the board of given size fits in the bits
+    pub fn fits(width: usize, height: usize) -> bool {
+        (width + 1) * height <= MAX_BITS
+    }
+
+    /// Creates [`Bitboard`] with the cells of the given board, [`None`]
+    /// when the board is too big
+    pub fn new(board: &Board) -> Option<Self> {
+        let (width, height) = (board.size.x, board.size.y);
+        if !Self::fits(width, height) {
+            return None;
+        }
+        let row = Bits::ones(width);
+        let cells = (0..height).fold(Bits::default(), |cells, y| {
+            cells | row << (y * (width + 1))
+        });
+        let mut bits = Self {
+            width,
+            height,
+            win_len: board.win_len,
+            cells,
+            cross: Bits::default(),
+            circle: Bits::default(),
+            blocked: Bits::default(),
+        };
+        for (id, cell) in board.cells.occupied() {
+            bits.set(id, cell);
+        }
+        Some(bits)
+    }
+
+    /// Gets cell on the given board index
+    pub fn get(&self, id: usize) -> Cell {
+        let bit = self.bit(id);
+        if self.cross.get(bit) {
+            Cell::Cross
+        } else if self.circle.get(bit) {
+            Cell::Circle
+        } else if self.blocked.get(bit) {
+            Cell::Blocked
+        } else {
+            Cell::Empty
+        }
+    }
+
+    /// Sets cell on the given board index
+    pub fn set(&mut self, id: usize, cell: Cell) {
+        let bit = self.bit(id);
+        for marks in [&mut self.cross, &mut self.circle, &mut self.blocked] {
+            if marks.get(bit) {
+                marks.toggle(bit);
+            }
+        }
+        if let Some(marks) = self.marks_mut(cell) {
+            marks.toggle(bit);
+        }
+    }
+
+    /// Gets the empty cells
+    pub fn empty(&self) -> Bits {
+        self.cells & !(self.cross | self.circle | self.blocked)
+    }
+
+    /// Checks whether there's no empty cell
+    pub fn is_full(&self) -> bool {
+        self.empty().is_empty()
+    }
+
+    /// Gets board indexes of the empty cells next to any mark
+    pub fn near(&self) -> Vec<usize> {
+        let marks = self.cross | self.circle;
+        let s = self.width + 1;
+        let near = [1, s - 1, s, s + 1]
+            .into_iter()
+            .fold(Bits::default(), |near, d| near | marks << d | marks >> d);
+        (near & self.empty()).iter().map(|b| self.id(b)).collect()
+    }
+
+    /// Checks whether the mark on the given board index completes a line
+    pub fn wins_at(&self, id: usize) -> bool {
+        let bit = self.bit(id);
+        let Some(marks) = self.marks(self.get(id)) else {
+            return false;
+        };
+        DIRS.iter().any(|&dir| {
+            let (step, len) = (self.step(dir), self.win_len.get(dir));
+            cover(windows(marks, step, len), step, len).get(bit)
+        })
+    }
+
+    /// Evaluates position from the given player's point of view, each
+    /// window of win length with marks of only one player is scored by the
+    /// number of the marks
+    pub fn evaluate(&self, player: Cell) -> i64 {
+        self.score(player) - self.score(player.next())
+    }
+
+    /// Gets score of the windows, which contain only the player's marks
+    fn score(&self, player: Cell) -> i64 {
+        let (Some(mine), Some(theirs)) =
+            (self.marks(player), self.marks(player.next()))
+        else {
+            return 0;
+        };
+        let free = self.cells & !(theirs | self.blocked);
+        let mut score = 0;
+        for dir in DIRS {
+            let (step, len) = (self.step(dir), self.win_len.get(dir));
+            let starts = windows(free, step, len);
+            if starts.is_empty() {
+                continue;
+            }
+
+            // Counts the marks in each window, digits of the counts are
+            // stored in separate bits
+            let mut digits = [Bits::default(); DIGITS];
+            let digits = &mut digits[..DIGITS.min(bit_len(len))];
+            for i in 0..len {
+                let mut carry = (mine >> (step * i)) & starts;
+                for digit in digits.iter_mut() {
+                    let next = *digit & carry;
+                    *digit = *digit ^ carry;
+                    carry = next;
+                }
+            }
+            for count in 1..=len {
+                let windows = digits.iter().enumerate().fold(
+                    starts,
+                    |windows, (i, digit)| match count >> i & 1 {
+                        1 => windows & *digit,
+                        _ => windows & !*digit,
+                    },
+                );
+                score += 4_i64.pow(count as u32) * windows.count() as i64;
+            }
+        }
+        score
+    }
+
+    /// Gets marks of the given player
+    fn marks(&self, cell: Cell) -> Option<Bits> {
+        match cell {
+            Cell::Cross => Some(self.cross),
+            Cell::Circle => Some(self.circle),
+            _ => None,
+        }
+    }
+
+    /// Gets mutable marks of the given cell
+    fn marks_mut(&mut self, cell: Cell) -> Option<&mut Bits> {
+        match cell {
+            Cell::Cross => Some(&mut self.cross),
+            Cell::Circle => Some(&mut self.circle),
+            Cell::Blocked => Some(&mut self.blocked),
+            Cell::Empty => None,
+        }
+    }
+
+    /// Gets distance of the neighbouring bits in the given direction
+    fn step(&self, (dx, dy): (isize, isize)) -> usize {
+        (dx + dy * (self.width as isize + 1)) as usize
+    }
+
+    /// Converts board index to the bit index
+    fn bit(&self, id: usize) -> usize {
+        id + id / self.width
+    }
+
+    /// Converts bit index to the board index
+    fn id(&self, bit: usize) -> usize {
+        bit - bit / (self.width + 1)
+    }
+}
+
+impl From<&Bitboard> for Board {
+    /// Creates board with the same cells
+    fn from(bits: &Bitboard) -> Self {
+        let mut board = Board::new(bits.width, bits.height, 0);
+        board.win_len = bits.win_len;
+        for id in 0..bits.width * bits.height {
+            board.cells.set(id, bits.get(id));
+        }
+        board
+    }
+}
+
+/// Gets number of binary digits of the value
+fn bit_len(val: usize) -> usize {
+    (usize::BITS - val.leading_zeros()) as usize
+}
+
+/// Gets starts of the windows of given length, which contain only the set
+/// bits, `step` is distance between the bits of the window. The shifts are
+/// doubled, so it takes only logarithmic number of them.
+fn windows(mut bits: Bits, step: usize, len: usize) -> Bits {
+    let mut covered = 1;
+    while covered < len {
+        let n = covered.min(len - covered);
+        bits = bits & bits >> (step * n);
+        covered += n;
+    }
+    bits
+}
+
+/// Gets all the bits of the windows of given length starting at the set
+/// bits
+fn cover(mut starts: Bits, step: usize, len: usize) -> Bits {
+    let mut covered = 1;
+    while covered < len {
+        let n = covered.min(len - covered);
+        starts = starts | starts << (step * n);
+        covered += n;
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::Rng;
+
+    use super::*;
+
+    #[test]
+    fn shifts_cross_words() {
+        let mut bits = Bits::default();
+        bits.toggle(63);
+        bits.toggle(200);
+        assert_eq!((bits << 1).iter().collect::<Vec<_>>(), [64, 201]);
+        assert_eq!((bits >> 70).iter().collect::<Vec<_>>(), [130]);
+        assert_eq!((bits << 60).iter().collect::<Vec<_>>(), [123]);
+        assert_eq!(Bits::ones(130).count(), 130);
+    }
+
+    #[test]
+    fn converts_board() {
+        let mut board = Board::new(4, 3, 3);
+        board.cells.set(0, Cell::Cross);
+        board.cells.set(7, Cell::Circle);
+        board.cells.set(11, Cell::Blocked);
+        let bits = Bitboard::new(&board).unwrap();
+        assert_eq!(bits.get(7), Cell::Circle);
+        assert_eq!(bits.near(), [1, 2, 3, 4, 5, 6, 10]);
+        assert_eq!(Board::from(&bits).cells, board.cells);
+        assert!(Bitboard::new(&Board::new(16, 16, 5)).is_none());
+    }
+
+    #[test]
+    fn lines_dont_wrap() {
+        // Cross on the end of the first row and start of the second one
+        let mut board = Board::new(3, 3, 2);
+        board.cells.set(2, Cell::Cross);
+        board.cells.set(3, Cell::Cross);
+        let bits = Bitboard::new(&board).unwrap();
+        assert!(!bits.wins_at(2) && !bits.wins_at(3));
+        board.cells.set(4, Cell::Cross);
+        let bits = Bitboard::new(&board).unwrap();
+        assert!(bits.wins_at(3) && bits.wins_at(2));
+        assert!(!bits.wins_at(0));
+    }
+
+    #[test]
+    fn random_positions_match_board() {
+        let mut rng = Rng::new(7);
+        for (w, h, win) in [(3, 3, 3), (7, 7, 4), (15, 15, 5), (20, 5, 4)] {
+            let mut board = Board::new(w, h, win);
+            let mut player = Cell::Cross;
+            while board.state().is_none() {
+                let id = rng.range(w * h);
+                let Ok(state) = board.set(player, id % w, id / w) else {
+                    continue;
+                };
+                let bits = Bitboard::new(&board).unwrap();
+                assert_eq!(bits.wins_at(id), state == Some(player));
+                assert_eq!(
+                    bits.is_full(),
+                    !board.cells.contains(&Cell::Empty)
+                );
+                player = player.next();
+            }
+        }
+    }
+}
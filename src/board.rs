@@ -1,36 +1,248 @@
-use crate::{cell::Cell, error::Error};
-use std::cmp::min;
-use termint::{geometry::Coords, widgets::Widget};
+use crate::{
+    annotations::Annotations, cell::Cell, cells::Cells, error::Error,
+    locale::tr, marks::Marks, notation::Numbering, pos::Pos, rng::Rng,
+    symmetry::Symmetry, theme::Theme, win_len::WinLen, zoom::Zoom,
+};
+use std::{
+    cmp::{max, min, Ordering},
+    collections::{BTreeMap, BTreeSet},
+};
+use termint::widgets::Widget;
+
+/// Reason why a cell can't be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Illegal {
+    Ended,
+    Occupied,
+    Blocked,
+    /// All the pieces are placed, so one of them has to be picked up
+    MustMove,
+    /// Picked up piece isn't player's own
+    NotOwnPiece,
+    /// Board of the multi-board variant with a completed line
+    DeadBoard,
+    /// Cell is hidden from the player in the fog of war
+    Hidden,
+}
+
+impl Illegal {
+    /// Gets one-line description of the reason
+    pub fn reason(&self) -> &'static str {
+        tr(match self {
+            Illegal::Ended => "illegal.ended",
+            Illegal::Occupied => "illegal.occupied",
+            Illegal::Blocked => "illegal.blocked",
+            Illegal::MustMove => "illegal.must-move",
+            Illegal::NotOwnPiece => "illegal.not-own-piece",
+            Illegal::DeadBoard => "illegal.dead-board",
+            Illegal::Hidden => "illegal.hidden",
+        })
+    }
+
+    /// Checks whether the reason comes from the variant rules. Such cells
+    /// can look playable, so they are rendered dimmed.
+    pub fn by_rule(&self) -> bool {
+        !matches!(self, Illegal::Ended | Illegal::Occupied)
+    }
+}
+
+impl From<Illegal> for Error {
+    fn from(value: Illegal) -> Self {
+        match value {
+            Illegal::Ended => Error::GameEnded,
+            illegal => Error::InvalidMove(illegal.reason().to_string()),
+        }
+    }
+}
+
+/// Directions in which lines are checked
+const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+
+/// Moves per cell after which the game with decaying marks is a draw
+pub const DECAY_ROUNDS: usize = 3;
+
+/// Number of cells kept visible around the selected cell when scrolling
+const SCROLL_MARGIN: usize = 2;
+
+/// Winning sequence given by its first cell and direction
+pub type Segment = (Pos, (isize, isize));
+
+/// Change of the board, which its owner can react to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardEvent {
+    /// Player's mark placed on the position
+    Placed(Cell, Pos),
+    /// Piece moved from the first position to the second one
+    Moved(Pos, Pos),
+    /// Game state changed, the game ended unless it's [`None`]
+    State(Option<Cell>),
+}
 
 /// Represents tictactoe board
 #[derive(Debug, Clone)]
 pub struct Board {
-    pub cells: Vec<Cell>,
-    pub selected: Coords,
-    pub size: Coords,
-    pub win_len: usize,
-    pub win: Option<(Coords, (isize, isize))>,
+    pub cells: Cells,
+    pub selected: Pos,
+    pub size: Pos,
+    pub win_len: WinLen,
+    /// All the winning sequences
+    pub win: Vec<Segment>,
+    pub theme: Theme,
+    pub history: Vec<(Cell, Pos)>,
+    /// Whether to show relative distances from the selected cell
+    pub relative: bool,
+    /// Whether row and column names are shown along the board edges
+    pub labels: bool,
+    pub numbering: Numbering,
+    /// Whether cells completing a line next turn are marked
+    pub threats: bool,
+    pub marks: Marks,
+    pub annotations: Annotations,
+    /// Whether game continues until the board is full and the player with
+    /// more completed lines wins
+    pub points: bool,
+    /// Whether players can place either mark on their turn
+    pub free_marks: bool,
+    /// Number of pieces of each player, pieces are moved once all of them
+    /// are placed
+    pub pieces: Option<usize>,
+    /// Piece picked up to be moved to another cell
+    pub lifted: Option<Pos>,
+    /// Whether the selected cell is shown, only the played board is
+    /// focused in the multi-board variant
+    pub focused: bool,
+    /// Whether the board is one of the multi-board variant, which is dead
+    /// once a line is completed on it
+    pub multi: bool,
+    /// Number of further moves after which a placed mark disappears
+    pub decay: Option<usize>,
+    /// Distance from own marks within which the player sees the board
+    pub fog: Option<usize>,
+    /// Player whose view of the board is rendered in the fog of war
+    pub viewer: Cell,
+    /// Marks placed before the start of the game
+    pub handicap: Vec<Pos>,
+    /// Whether the board grows, when a mark is placed near its edge
+    pub infinite: bool,
+    /// Number of the visible cells, when the board doesn't fit the screen
+    pub view: Option<Pos>,
+    /// First visible cell of the view
+    pub scroll: Pos,
+    pub zoom: Zoom,
+    /// Number of moves played since each mark was played by the mark index,
+    /// kept only when the marks decay
+    age: BTreeMap<usize, usize>,
+    /// Size of the board before the infinite board grew
+    start_size: Pos,
     state: Option<Cell>,
+    /// Events not taken by the owner yet
+    events: Vec<BoardEvent>,
 }
 
 impl Board {
     /// Creates new [`Board`]
     pub fn new(width: usize, height: usize, win_len: usize) -> Self {
         Self {
-            cells: vec![Cell::Empty; width * height],
-            selected: Coords::new(width / 2, height / 2),
-            size: Coords::new(width, height),
-            win_len,
-            win: None,
+            cells: Cells::new(width * height),
+            selected: Pos::new(width / 2, height / 2),
+            size: Pos::new(width, height),
+            win_len: WinLen::new(win_len),
+            win: vec![],
+            theme: Theme::default(),
+            history: vec![],
+            relative: false,
+            labels: false,
+            numbering: Numbering::default(),
+            threats: false,
+            marks: Marks::default(),
+            annotations: Annotations::default(),
+            points: false,
+            free_marks: false,
+            pieces: None,
+            lifted: None,
+            focused: true,
+            multi: false,
+            decay: None,
+            fog: None,
+            viewer: Cell::Cross,
+            handicap: vec![],
+            infinite: false,
+            view: None,
+            scroll: Pos::new(0, 0),
+            zoom: Zoom::default(),
+            age: BTreeMap::new(),
+            start_size: Pos::new(width, height),
             state: None,
+            events: vec![],
         }
     }
 
     /// Restarts the game
     pub fn restart(&mut self) {
-        self.cells = vec![Cell::Empty; self.size.x * self.size.y];
+        if self.infinite && self.size != self.start_size {
+            self.size = self.start_size;
+            self.selected = Pos::new(self.size.x / 2, self.size.y / 2);
+            self.scroll = Pos::new(0, 0);
+        }
+        self.cells = self.cells.empty(self.size.x * self.size.y);
+        self.age.clear();
         self.state = None;
-        self.win = None;
+        self.win.clear();
+        self.handicap.clear();
+        self.lifted = None;
+        self.history.clear();
+        self.annotations.clear();
+        self.events.clear();
+    }
+
+    /// Blocks given number of randomly chosen empty cells
+    pub fn add_blocks(&mut self, count: usize, rng: &mut Rng) {
+        for _ in 0..count {
+            let empty = self.cells.count(Cell::Empty);
+            if empty == 0 {
+                break;
+            }
+            let id = self.nth_empty(rng.range(empty), &BTreeSet::new());
+            self.cells.set(id, Cell::Blocked);
+        }
+        self.update_state();
+    }
+
+    /// Places given number of player marks on randomly chosen empty cells,
+    /// cells completing a line are skipped
+    pub fn add_handicap(&mut self, player: Cell, count: usize, rng: &mut Rng) {
+        let mut skipped = BTreeSet::new();
+        while self.handicap.len() < count {
+            let empty = self.cells.count(Cell::Empty) - skipped.len();
+            if empty == 0 {
+                break;
+            }
+            let id = self.nth_empty(rng.range(empty), &skipped);
+            self.cells.set(id, player);
+            if self.check_state().is_some() {
+                self.cells.set(id, Cell::Empty);
+                skipped.insert(id);
+                continue;
+            }
+            self.handicap.push(Pos::from_index(id, self.size.x));
+        }
+        self.update_state();
+    }
+
+    /// Gets index of the n-th empty cell, the skipped empty cells aren't
+    /// counted. Only the non-empty cells are gone through.
+    fn nth_empty(&self, mut n: usize, skipped: &BTreeSet<usize>) -> usize {
+        let mut taken: Vec<_> =
+            self.cells.occupied().map(|(id, _)| id).collect();
+        taken.extend(skipped);
+        taken.sort_unstable();
+        for id in taken {
+            if id > n {
+                break;
+            }
+            n += 1;
+        }
+        n
     }
 
     /// Sets cell on given coordinates to given value
@@ -40,19 +252,159 @@ impl Board {
         x: usize,
         y: usize,
     ) -> Result<Option<Cell>, Error> {
-        if self.state.is_some() {
-            return Err(Error::Msg("game ended".into()));
+        let mut pos = Pos::new(x, y);
+        self.legality(pos)?;
+        if self.infinite {
+            pos = self.grow_around(pos);
         }
 
-        let id = x + y * self.size.x;
+        self.cells.set(pos.index(self.size.x), cell);
+        self.history.push((cell, pos));
+        self.events.push(BoardEvent::Placed(cell, pos));
+        self.decay_marks(pos.index(self.size.x));
+        self.update_state();
+        Ok(self.state)
+    }
+
+    /// Gets number of moves after which the mark on given position
+    /// disappears, [`None`] when marks don't decay or the cell isn't mark
+    pub fn expires_in(&self, pos: Pos) -> Option<usize> {
+        let id = pos.index(self.size.x);
         match self.cells[id] {
-            Cell::Empty => {
-                self.cells[id] = cell;
-                self.state = self.check_state();
-                Ok(self.state)
+            Cell::Cross | Cell::Circle => {
+                let age = self.age.get(&id).copied().unwrap_or_default();
+                self.decay.map(|decay| decay.saturating_sub(age))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether the cell on given coordinates can be played
+    pub fn legality(&self, pos: Pos) -> Result<(), Illegal> {
+        match self.cells[pos.index(self.size.x)] {
+            Cell::Blocked => Err(Illegal::Blocked),
+            Cell::Cross | Cell::Circle => Err(Illegal::Occupied),
+            _ if self.state.is_some() && self.multi => Err(Illegal::DeadBoard),
+            _ if self.state.is_some() => Err(Illegal::Ended),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether the given player can play the cell on given
+    /// coordinates, including the rules depending on the player. In the
+    /// moving phase, playing a cell picks up or moves the piece.
+    pub fn legality_for(&self, pos: Pos, player: Cell) -> Result<(), Illegal> {
+        let cell = self.cell(pos);
+        if self.state.is_some() {
+            return self.legality(pos);
+        }
+        // Lifted piece is put back
+        if self.lifted == Some(pos) {
+            return Ok(());
+        }
+        // Hidden mark must not be told apart from the empty cell
+        if cell != Cell::Blocked && !self.sees(player, pos) {
+            return match cell {
+                Cell::Empty => Ok(()),
+                _ => Err(Illegal::Hidden),
+            };
+        }
+        if self.lifted.is_some() || !self.must_move(player) {
+            return self.legality(pos);
+        }
+        match cell {
+            Cell::Empty => Err(Illegal::MustMove),
+            cell if cell == player => Ok(()),
+            _ => Err(Illegal::NotOwnPiece),
+        }
+    }
+    /// Gets number of lines completed by the given player
+    pub fn completed(&self, player: Cell) -> usize {
+        self.win
+            .iter()
+            .filter(|(pos, _)| self.cells[pos.index(self.size.x)] == player)
+            .count()
+    }
+
+    /// Gets mask of the cells visible to the given player. Only cells near
+    /// the player's marks are visible in the fog of war until the game ends.
+    pub fn visible(&self, player: Cell) -> Vec<bool> {
+        let Some(radius) = self.fog.filter(|_| self.state.is_none()) else {
+            return vec![true; self.cells.len()];
+        };
+
+        let mut visible = vec![false; self.cells.len()];
+        for (id, cell) in self.cells.occupied() {
+            if cell == Cell::Blocked {
+                visible[id] = true;
+            }
+            if cell != player {
+                continue;
+            }
+            let pos = Pos::from_index(id, self.size.x);
+            let xs = pos.x.saturating_sub(radius)
+                ..=(pos.x + radius).min(self.size.x - 1);
+            for y in pos.y.saturating_sub(radius)
+                ..=(pos.y + radius).min(self.size.y - 1)
+            {
+                let row = y * self.size.x;
+                visible[row + xs.start()..=row + xs.end()].fill(true);
             }
-            _ => Err(Error::Msg(String::from("Not empty cell"))),
         }
+        visible
+    }
+
+    /// Checks whether the given player sees the cell on given position,
+    /// which is the cell near the player's marks in the fog of war
+    pub fn sees(&self, player: Cell, pos: Pos) -> bool {
+        let Some(radius) = self.fog.filter(|_| self.state.is_none()) else {
+            return true;
+        };
+        let xs = pos.x.saturating_sub(radius)..=pos.x + radius;
+        let ys = pos.y.saturating_sub(radius)..=pos.y + radius;
+        ys.filter(|y| *y < self.size.y).any(|y| {
+            xs.clone()
+                .filter(|x| *x < self.size.x)
+                .any(|x| self.cell(Pos::new(x, y)) == player)
+        })
+    }
+
+    /// Checks whether the player placed all the pieces, so the pieces have
+    /// to be moved
+    pub fn must_move(&self, player: Cell) -> bool {
+        self.pieces
+            .is_some_and(|pieces| self.cells.count(player) >= pieces)
+    }
+
+    /// Picks up the player's piece on the selected cell, so it can be moved
+    pub fn lift_selected(&mut self, player: Cell) -> Result<(), Error> {
+        let pos = self.selected;
+        if self.state.is_some() {
+            Err(Illegal::Ended)?;
+        }
+        if self.cells[pos.index(self.size.x)] != player {
+            Err(Illegal::NotOwnPiece)?;
+        }
+        self.lifted = Some(pos);
+        Ok(())
+    }
+
+    /// Moves the lifted piece to the selected cell
+    pub fn move_selected(&mut self) -> Result<Option<Cell>, Error> {
+        let from = self
+            .lifted
+            .ok_or(Error::InvalidMove("no piece is picked up".into()))?;
+        let to = self.selected;
+        self.legality(to)?;
+
+        let cell = self.cells[from.index(self.size.x)];
+        self.cells.set(from.index(self.size.x), Cell::Empty);
+        self.cells.set(to.index(self.size.x), cell);
+        self.lifted = None;
+        self.history.push((cell, to));
+        self.events.push(BoardEvent::Moved(from, to));
+        self.update_state();
+        Ok(self.state)
     }
 
     /// Sets selected cell to given value
@@ -61,10 +413,37 @@ impl Board {
     }
 
     /// Sets selected cell
-    pub fn select(&mut self, coords: Coords) {
+    pub fn select(&mut self, coords: Pos) {
         self.selected = coords;
     }
 
+    /// Shows only the given number of cells, when the board is larger, and
+    /// scrolls the view, so the selected cell stays visible
+    pub fn fit_view(&mut self, view: Pos) {
+        if view.x >= self.size.x && view.y >= self.size.y {
+            self.view = None;
+            self.scroll = Pos::new(0, 0);
+            return;
+        }
+        let view =
+            Pos::new(min(view.x, self.size.x), min(view.y, self.size.y));
+        self.scroll = Pos::new(
+            Board::scroll_axis(
+                self.scroll.x,
+                self.selected.x,
+                view.x,
+                self.size.x,
+            ),
+            Board::scroll_axis(
+                self.scroll.y,
+                self.selected.y,
+                view.y,
+                self.size.y,
+            ),
+        );
+        self.view = Some(view);
+    }
+
     /// Moves selected up
     pub fn up(&mut self) {
         self.selected.y = self.selected.y.saturating_sub(1);
@@ -85,72 +464,341 @@ impl Board {
         self.selected.x = min(self.selected.x + 1, self.size.x - 1);
     }
 
+    /// Gets position of the most recently placed or moved mark, [`None`]
+    /// before the first move
+    pub fn last_move(&self) -> Option<Pos> {
+        self.history.last().map(|(_, pos)| *pos)
+    }
+
     /// Gets game state
     pub fn state(&self) -> Option<Cell> {
         self.state
     }
+
+    /// Takes the events which happened since the last call, in order
+    pub fn take_events(&mut self) -> Vec<BoardEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Gets cell on the given position
+    pub fn cell(&self, pos: Pos) -> Cell {
+        self.cells[pos.index(self.size.x)]
+    }
+
+    /// Iterates over the rows of cells from the top
+    pub fn rows(&self) -> impl Iterator<Item = Vec<Cell>> + '_ {
+        let w = self.size.x;
+        (0..self.size.y)
+            .map(move |y| (0..w).map(|x| self.cells[x + y * w]).collect())
+    }
+
+    /// Iterates over the columns from the left, each column is given by
+    /// the positions of its cells from the top
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        let size = self.size;
+        (0..size.x).map(move |x| Pos::col(x, size))
+    }
+
+    /// Iterates over the diagonals going down to the right and then the
+    /// ones going down to the left, including the single cell ones. Each
+    /// diagonal is given by the positions of its cells from the top.
+    pub fn diagonals(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        Pos::diagonals(false, self.size).chain(Pos::diagonals(true, self.size))
+    }
+
+    /// Iterates over all the segments of the given length fitting on the
+    /// board, ordered by their first cell, with positions of their cells
+    pub fn lines(
+        &self,
+        win_len: WinLen,
+    ) -> impl Iterator<Item = (Segment, impl Iterator<Item = Pos>)> {
+        let size = self.size;
+        Pos::all(size).flat_map(move |pos| Board::segments(pos, win_len, size))
+    }
+
+    /// Checks whether the game is played by the standard rules, so the
+    /// engine can search its moves
+    pub fn standard(&self) -> bool {
+        !self.free_marks
+            && !self.points
+            && self.pieces.is_none()
+            && self.decay.is_none()
+    }
+
+    /// Gets empty cells, where the given player's mark would complete a
+    /// line. Lines with cells hidden from the viewer are skipped, so the
+    /// fog of war keeps the hidden marks secret.
+    pub fn winning_cells(&self, player: Cell) -> Vec<Pos> {
+        if self.state.is_some() {
+            return vec![];
+        }
+        let visible = self.visible(self.viewer);
+        let w = self.size.x;
+        let mut res = vec![];
+        for (_, line) in self.lines(self.win_len) {
+            let line: Vec<_> = line.collect();
+            if line.iter().any(|p| !visible[p.index(w)]) {
+                continue;
+            }
+            let mut empty = line
+                .iter()
+                .filter(|p| self.cells[p.index(w)] == Cell::Empty);
+            let (Some(pos), None) = (empty.next(), empty.next()) else {
+                continue;
+            };
+            let own = line.iter().filter(|p| self.cells[p.index(w)] == player);
+            if own.count() + 1 == line.len() && !res.contains(pos) {
+                res.push(*pos);
+            }
+        }
+        res
+    }
+
+    /// Gets the lexicographically smallest rotation or reflection of the
+    /// cells and the symmetry transforming the board to it
+    pub fn canonical(&self) -> (Vec<Cell>, Symmetry) {
+        Symmetry::all(self.size)
+            .into_iter()
+            .map(|sym| {
+                let mut cells = vec![Cell::Empty; self.cells.len()];
+                for (id, cell) in self.cells.occupied() {
+                    let pos = Pos::from_index(id, self.size.x);
+                    let pos = sym.apply(pos, self.size);
+                    cells[pos.index(self.size.x)] = cell;
+                }
+                (cells, sym)
+            })
+            .min_by(|a, b| a.0.cmp(&b.0))
+            .unwrap_or_else(|| (self.cells.to_vec(), Symmetry::default()))
+    }
 }
 
 impl Board {
-    /// Checks game state
+    /// Grows the infinite board, so there's space for the longest line
+    /// around the given position in every direction. Gets the position
+    /// shifted by the columns and rows added on the left and the top.
+    fn grow_around(&mut self, pos: Pos) -> Pos {
+        let margin = DIRS.iter().map(|d| self.win_len.get(*d)).max();
+        let margin = margin.unwrap_or_default();
+        let left = margin.saturating_sub(pos.x);
+        let top = margin.saturating_sub(pos.y);
+        let right = (pos.x + margin + 1).saturating_sub(self.size.x);
+        let bottom = (pos.y + margin + 1).saturating_sub(self.size.y);
+        if left + top + right + bottom == 0 {
+            return pos;
+        }
+
+        let size =
+            Pos::new(self.size.x + left + right, self.size.y + top + bottom);
+        let shift = |p: Pos| Pos::new(p.x + left, p.y + top);
+        let mut cells = self.cells.empty(size.x * size.y);
+        let mut age = BTreeMap::new();
+        for (id, cell) in self.cells.occupied() {
+            let new = shift(Pos::from_index(id, self.size.x)).index(size.x);
+            cells.set(new, cell);
+            if let Some(a) = self.age.get(&id) {
+                age.insert(new, *a);
+            }
+        }
+        self.cells = cells;
+        self.age = age;
+        self.size = size;
+
+        // View keeps showing the same cells
+        self.selected = shift(self.selected);
+        self.scroll = shift(self.scroll);
+        self.lifted = self.lifted.map(shift);
+        for (_, p) in self.history.iter_mut() {
+            *p = shift(*p);
+        }
+        for p in self.handicap.iter_mut() {
+            *p = shift(*p);
+        }
+        let notes = &mut self.annotations;
+        notes.anchor = notes.anchor.map(shift);
+        for p in notes.cells.iter_mut() {
+            *p = shift(*p);
+        }
+        for (a, b) in notes.lines.iter_mut() {
+            (*a, *b) = (shift(*a), shift(*b));
+        }
+        for event in self.events.iter_mut() {
+            match event {
+                BoardEvent::Placed(_, p) => *p = shift(*p),
+                BoardEvent::Moved(a, b) => (*a, *b) = (shift(*a), shift(*b)),
+                BoardEvent::State(_) => {}
+            }
+        }
+        shift(pos)
+    }
+
+    /// Ages all the marks except the one just played on given index and
+    /// removes the marks played given number of moves ago
+    fn decay_marks(&mut self, played: usize) {
+        let Some(decay) = self.decay else {
+            return;
+        };
+        let marks: Vec<_> = self.cells.occupied().collect();
+        for (id, cell) in marks {
+            if !matches!(cell, Cell::Cross | Cell::Circle) {
+                continue;
+            }
+            let age = self.age.entry(id).or_default();
+            *age = match id == played {
+                true => 0,
+                false => *age + 1,
+            };
+            if *age >= decay {
+                self.age.remove(&id);
+                self.cells.set(id, Cell::Empty);
+            }
+        }
+    }
+
+    /// Checks game state and reports it, when it changed
+    fn update_state(&mut self) {
+        let state = self.check_state();
+        if state != self.state {
+            self.events.push(BoardEvent::State(state));
+        }
+        self.state = state;
+    }
+
+    /// Checks game state and collects all the winning sequences
     fn check_state(&mut self) -> Option<Cell> {
-        let mut draw = true;
-        for y in 0..self.size.y {
-            for x in 0..self.size.x {
-                if self.cells[x + y * self.size.x] == Cell::Empty {
-                    draw = false;
-                    continue;
-                }
+        // Winning sequence starts with a mark, so the empty cells are
+        // skipped
+        let (w, size) = (self.size.x, self.size);
+        self.win = self
+            .cells
+            .occupied()
+            .flat_map(|(id, _)| {
+                Board::segments(Pos::from_index(id, w), self.win_len, size)
+            })
+            .filter_map(|(seg, cells)| {
+                self.check_win(seg, cells).then_some(seg)
+            })
+            .collect();
 
-                if x + self.win_len <= self.size.x
-                    && self.check_win(x, y, 1, 0)
-                {
-                    return Some(self.cells[x + y * self.size.x]);
-                }
+        if self.points {
+            let full = !self.cells.contains(&Cell::Empty);
+            let (cross, circle) =
+                (self.completed(Cell::Cross), self.completed(Cell::Circle));
+            return full.then_some(match cross.cmp(&circle) {
+                Ordering::Greater => Cell::Cross,
+                Ordering::Less => Cell::Circle,
+                Ordering::Equal => Cell::Empty,
+            });
+        }
+        if let Some((pos, _)) = self.win.first() {
+            return Some(self.cells[pos.index(self.size.x)]);
+        }
+        if let Some(decay) = self.decay {
+            return self.decay_draw(decay).then_some(Cell::Empty);
+        }
+        // Infinite board always has space for another line
+        if self.infinite {
+            return None;
+        }
+        // Moved pieces can open the blocked lines again, so it's a draw
+        // only when nobody can move
+        if self.pieces.is_some() {
+            return (!self.cells.contains(&Cell::Empty))
+                .then_some(Cell::Empty);
+        }
+        (!self.winnable()).then_some(Cell::Empty)
+    }
 
-                if y + self.win_len <= self.size.y {
-                    if self.check_win(x, y, 0, 1) {
-                        return Some(self.cells[x + y * self.size.x]);
-                    }
+    /// Checks whether the game with decaying marks is a draw. Board doesn't
+    /// fill up and lines open again, so it's a draw when the marks decay
+    /// before any line can be completed or when the moves limit is reached.
+    fn decay_draw(&self, decay: usize) -> bool {
+        // Player has at most every other of the marks still on the board
+        let kept = decay.div_ceil(2);
+        let shortest = DIRS.iter().map(|d| self.win_len.get(*d)).min();
+        let limit = self.cells.len() * DECAY_ROUNDS;
+        !self.cells.contains(&Cell::Empty)
+            || shortest.is_some_and(|len| kept < len)
+            || self.history.len() >= limit
+    }
 
-                    if x + self.win_len <= self.size.x
-                        && self.check_win(x, y, 1, 1)
-                    {
-                        return Some(self.cells[x + y * self.size.x]);
-                    }
+    /// Checks whether any player can still complete a line. Line can be
+    /// completed only when it contains marks of a single player and the
+    /// player has enough moves left to fill the rest of it.
+    fn winnable(&self) -> bool {
+        let empty = self.cells.count(Cell::Empty);
+        let next = self.history.last().map(|(c, _)| c.next());
+        let moves = |cell: Cell| match next {
+            _ if self.free_marks => empty,
+            Some(next) if next != cell => empty / 2,
+            _ => empty.div_ceil(2),
+        };
 
-                    if x + 1 >= self.win_len && self.check_win(x, y, -1, 1) {
-                        return Some(self.cells[x + y * self.size.x]);
-                    }
+        self.lines(self.win_len).any(|(_, cells)| {
+            let mut owner = Cell::Empty;
+            let mut missing = 0;
+            for pos in cells {
+                match self.cell(pos) {
+                    Cell::Empty => missing += 1,
+                    Cell::Blocked => return false,
+                    cell if owner == Cell::Empty => owner = cell,
+                    cell if cell != owner => return false,
+                    _ => {}
                 }
             }
-        }
-
-        (draw).then_some(Cell::Empty)
+            match owner {
+                Cell::Empty => {
+                    moves(Cell::Cross).max(moves(Cell::Circle)) >= missing
+                }
+                owner => moves(owner) >= missing,
+            }
+        })
     }
 
-    /// Checks win from given position and with given direction
+    /// Checks whether the segment with given cells is completed by a
+    /// player. Win continuing a longer sequence of the same player isn't
+    /// counted again.
     fn check_win(
-        &mut self,
-        mut x: usize,
-        mut y: usize,
-        xd: isize,
-        yd: isize,
+        &self,
+        (pos, (dx, dy)): Segment,
+        mut cells: impl Iterator<Item = Pos>,
     ) -> bool {
-        let pos = Coords::new(x, y);
-        let cell = self.cells[x + y * self.size.x];
-        x = (x as isize + xd) as usize;
-        y = (y as isize + yd) as usize;
-        for _ in 1..self.win_len {
-            if self.cells[x + y * self.size.x] != cell {
-                return false;
-            }
-            x = (x as isize + xd) as usize;
-            y = (y as isize + yd) as usize;
+        let cell = self.cell(pos);
+        if matches!(cell, Cell::Empty | Cell::Blocked) {
+            return false;
         }
+        let prev = pos.step((-dx, -dy), self.size).map(|p| self.cell(p));
+        prev != Some(cell) && cells.all(|p| self.cell(p) == cell)
+    }
+
+    /// Iterates over the segments of given length starting on the given
+    /// position, which fit on the board of given size
+    fn segments(
+        pos: Pos,
+        win_len: WinLen,
+        size: Pos,
+    ) -> impl Iterator<Item = (Segment, impl Iterator<Item = Pos>)> {
+        DIRS.into_iter().filter_map(move |dir| {
+            let len = win_len.get(dir);
+            pos.offset(dir, len.checked_sub(1)?, size)?;
+            Some(((pos, dir), pos.ray(dir, size).take(len)))
+        })
+    }
 
-        self.win = Some((pos, (xd, yd)));
-        true
+    /// Gets scroll in single direction, which moves the view only as much
+    /// as needed to keep the margin around the selected cell
+    fn scroll_axis(
+        scroll: usize,
+        sel: usize,
+        view: usize,
+        size: usize,
+    ) -> usize {
+        let margin = min(SCROLL_MARGIN, view.saturating_sub(1) / 2);
+        let scroll = min(scroll, sel.saturating_sub(margin));
+        let scroll = max(scroll, (sel + margin + 1).saturating_sub(view));
+        min(scroll, size - view)
     }
 }
 
@@ -159,3 +807,520 @@ impl From<Board> for Box<dyn Widget> {
         Box::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Board sizes and win lengths the properties are checked on, including
+    /// narrow boards and win lengths longer than the shorter side
+    const PROPERTY_SIZES: [(usize, usize, usize); 8] = [
+        (3, 3, 3),
+        (3, 3, 4),
+        (3, 10, 3),
+        (10, 3, 3),
+        (3, 10, 5),
+        (4, 6, 5),
+        (5, 5, 5),
+        (7, 7, 4),
+    ];
+    /// Number of random games played on each of the sizes
+    const PROPERTY_GAMES: u64 = 200;
+
+    /// Checks whether the player has a run of the win length in any
+    /// direction, independently of the state checking
+    fn has_run(board: &Board, player: Cell) -> bool {
+        let (w, h) = (board.size.x as isize, board.size.y as isize);
+        DIRS.iter().any(|&(dx, dy)| {
+            let len = board.win_len.get((dx, dy)) as isize;
+            (0..w * h).any(|id| {
+                (0..len).all(|i| {
+                    let (x, y) = (id % w + dx * i, id / w + dy * i);
+                    (0..w).contains(&x)
+                        && y < h
+                        && board.cells[(x + y * w) as usize] == player
+                })
+            })
+        })
+    }
+
+    /// Gets random empty cell of the board
+    fn random_empty(board: &Board, rng: &mut Rng) -> Option<Pos> {
+        let empty: Vec<_> = (0..board.cells.len())
+            .filter(|id| board.cells[*id] == Cell::Empty)
+            .collect();
+        let id = *empty.get(rng.range(empty.len()))?;
+        Some(Pos::from_index(id, board.size.x))
+    }
+
+    /// Plays random moves until the game ends and checks the reported
+    /// state after each of them. Returns the played moves.
+    fn random_game(board: &mut Board, rng: &mut Rng) -> Vec<Pos> {
+        let mut player = Cell::Cross;
+        let mut moves = vec![];
+        loop {
+            let pos = random_empty(board, rng)
+                .expect("game with full board has to be ended");
+            let state = board.set(player, pos.x, pos.y).unwrap();
+            moves.push(pos);
+            assert_eq!(state, board.state());
+            match state {
+                Some(Cell::Empty) | None => {
+                    assert!(!has_run(board, Cell::Cross));
+                    assert!(!has_run(board, Cell::Circle));
+                }
+                Some(winner) => assert!(has_run(board, winner)),
+            }
+            if state.is_some() {
+                return moves;
+            }
+            player = player.next();
+        }
+    }
+
+    /// Plays given moves alternately starting with cross
+    fn play(board: &mut Board, moves: &[(usize, usize)]) -> Option<Cell> {
+        let mut player = Cell::Cross;
+        let mut state = None;
+        for (x, y) in moves {
+            assert_eq!(state, None, "game ended before ({x}, {y})");
+            state = board.set(player, *x, *y).unwrap();
+            player = player.next();
+        }
+        state
+    }
+
+    fn empty_cells(board: &Board) -> usize {
+        board.cells.iter().filter(|c| **c == Cell::Empty).count()
+    }
+
+    #[test]
+    fn open_board_is_not_draw() {
+        let mut board = Board::new(3, 3, 3);
+        assert_eq!(play(&mut board, &[(1, 1)]), None);
+    }
+
+    #[test]
+    fn draw_before_board_is_full() {
+        let mut board = Board::new(3, 3, 3);
+        let moves = [(2, 1), (0, 1), (0, 2), (2, 0), (1, 0), (1, 2)];
+        // Only the empty diagonal is left, but cross has just two moves
+        assert_eq!(play(&mut board, &moves), Some(Cell::Empty));
+        assert_eq!(empty_cells(&board), 3);
+    }
+
+    #[test]
+    fn every_line_blocked_is_draw() {
+        let mut board = Board::new(5, 5, 5);
+        // Every row, column and diagonal contains both players
+        let moves = [
+            (0, 0),
+            (1, 0),
+            (2, 1),
+            (3, 1),
+            (4, 2),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 4),
+        ];
+        assert_eq!(play(&mut board, &moves), Some(Cell::Empty));
+        assert_eq!(empty_cells(&board), 15);
+    }
+
+    #[test]
+    fn lines_with_blocked_cells_are_draw() {
+        let mut board = Board::new(7, 7, 4);
+        for y in 0..7 {
+            for x in 0..7 {
+                if (x + y) % 3 == 0 || (x + 2 * y) % 3 == 0 {
+                    board.cells.set(x + y * 7, Cell::Blocked);
+                }
+            }
+        }
+        board.state = board.check_state();
+        assert_eq!(board.state(), Some(Cell::Empty));
+        assert!(empty_cells(&board) > 0);
+    }
+
+    #[test]
+    fn not_enough_moves_left_is_draw() {
+        let mut board = Board::new(5, 1, 3);
+        assert_eq!(play(&mut board, &[(2, 0)]), None);
+        // Cross needs two more cells, but gets only one of the three
+        let mut board = Board::new(4, 1, 3);
+        assert_eq!(play(&mut board, &[(0, 0)]), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn rotated_positions_are_same_canonical() {
+        let mut board = Board::new(4, 4, 3);
+        play(&mut board, &[(0, 1), (2, 0)]);
+        let mut rotated = Board::new(4, 4, 3);
+        play(&mut rotated, &[(2, 0), (3, 2)]);
+        let mut reflected = Board::new(4, 4, 3);
+        play(&mut reflected, &[(3, 1), (1, 0)]);
+
+        let (cells, sym) = board.canonical();
+        assert_eq!(cells, rotated.canonical().0);
+        assert_eq!(cells, reflected.canonical().0);
+        let pos = sym.apply(Pos::new(0, 1), board.size);
+        assert_eq!(cells[pos.x + pos.y * 4], Cell::Cross);
+        assert_eq!(sym.revert(pos, board.size), Pos::new(0, 1));
+    }
+
+    #[test]
+    fn rectangle_is_not_transposed() {
+        let mut board = Board::new(5, 3, 3);
+        play(&mut board, &[(4, 2)]);
+        let (cells, _) = board.canonical();
+        assert_eq!(cells.len(), 15);
+        assert_eq!(cells[0], Cell::Cross);
+    }
+
+    #[test]
+    fn lines_cover_the_board() {
+        let mut board = Board::new(4, 3, 3);
+        play(&mut board, &[(3, 0), (1, 2)]);
+        let last: Vec<_> = board.rows().map(|row| row[3]).collect();
+        assert_eq!(last, [Cell::Cross, Cell::Empty, Cell::Empty]);
+        assert_eq!(board.cols().count(), 4);
+        assert_eq!(board.diagonals().flatten().count(), 24);
+
+        // 6 in rows, 4 in columns and 2 in each of the diagonals
+        assert_eq!(board.lines(board.win_len).count(), 14);
+        let (_, cells) = board
+            .lines(WinLen::new(3))
+            .find(|(seg, _)| *seg == (Pos::new(3, 0), (-1, 1)))
+            .unwrap();
+        assert_eq!(board.cell(cells.last().unwrap()), Cell::Circle);
+    }
+
+    #[test]
+    fn events_report_moves_and_end() {
+        let mut board = Board::new(3, 3, 3);
+        play(&mut board, &[(0, 0), (0, 1)]);
+        assert_eq!(
+            board.take_events(),
+            [
+                BoardEvent::Placed(Cell::Cross, Pos::new(0, 0)),
+                BoardEvent::Placed(Cell::Circle, Pos::new(0, 1)),
+            ]
+        );
+        assert!(board.take_events().is_empty());
+
+        play(&mut board, &[(1, 0), (1, 1), (2, 0)]);
+        let events = board.take_events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[3], BoardEvent::State(Some(Cell::Cross)));
+    }
+
+    #[test]
+    fn winner_is_detected() {
+        let mut board = Board::new(7, 7, 4);
+        let moves = [(0, 0), (0, 1), (1, 1), (0, 2), (2, 2), (0, 3), (3, 3)];
+        assert_eq!(play(&mut board, &moves), Some(Cell::Cross));
+    }
+
+    #[test]
+    fn win_length_differs_by_direction() {
+        let mut board = Board::new(5, 5, 5);
+        board.win_len = WinLen::new(5).with(None, None, Some(3));
+        // Row of three isn't enough, but diagonal of three is
+        let moves = [(0, 4), (4, 0), (1, 4), (3, 1), (2, 4), (2, 2)];
+        assert_eq!(play(&mut board, &moves), Some(Cell::Circle));
+    }
+
+    #[test]
+    fn all_completed_lines_are_stored() {
+        let mut board = Board::new(3, 3, 3);
+        let moves = [
+            (0, 0),
+            (2, 0),
+            (2, 2),
+            (0, 2),
+            (1, 0),
+            (0, 1),
+            (1, 2),
+            (2, 1),
+            (1, 1),
+        ];
+        // Last cross completes both the column and the diagonal
+        assert_eq!(play(&mut board, &moves), Some(Cell::Cross));
+        assert_eq!(
+            board.win,
+            [(Pos::new(0, 0), (1, 1)), (Pos::new(1, 0), (0, 1))]
+        );
+    }
+
+    #[test]
+    fn longer_line_is_single_win() {
+        let mut board = Board::new(5, 3, 3);
+        let moves = [(0, 0), (0, 2), (1, 0), (1, 2), (3, 0), (3, 2), (2, 0)];
+        assert_eq!(play(&mut board, &moves), Some(Cell::Cross));
+        assert_eq!(board.win, [(Pos::new(0, 0), (1, 0))]);
+    }
+
+    #[test]
+    fn points_game_ends_when_board_is_full() {
+        let mut board = Board::new(3, 3, 3);
+        board.points = true;
+        let moves = [(0, 0), (0, 2), (1, 0), (1, 2), (2, 0)];
+        // Cross completed the top row, but the game goes on
+        assert_eq!(play(&mut board, &moves), None);
+        assert_eq!(board.completed(Cell::Cross), 1);
+
+        board.restart();
+        let moves = [moves.as_slice(), &[(0, 1), (1, 1), (2, 2), (2, 1)]];
+        assert_eq!(play(&mut board, &moves.concat()), Some(Cell::Empty));
+        assert_eq!(board.completed(Cell::Cross), 1);
+        assert_eq!(board.completed(Cell::Circle), 1);
+    }
+
+    #[test]
+    fn handicap_never_ends_game() {
+        for seed in 0..20 {
+            let mut board = Board::new(3, 3, 3);
+            board.add_handicap(Cell::Circle, 9, &mut Rng::new(seed));
+            assert_eq!(board.state(), None);
+            assert!(board.history.is_empty());
+            assert!((1..9).contains(&board.handicap.len()));
+        }
+    }
+
+    #[test]
+    fn winning_cells_complete_lines() {
+        let mut board = Board::new(4, 4, 3);
+        play(&mut board, &[(0, 0), (3, 3), (1, 1), (3, 2), (0, 2)]);
+        let mut cross = board.winning_cells(Cell::Cross);
+        cross.sort_by_key(|p| p.index(4));
+        assert_eq!(cross, [Pos::new(2, 0), Pos::new(0, 1), Pos::new(2, 2)]);
+        assert_eq!(board.winning_cells(Cell::Circle), [Pos::new(3, 1)]);
+
+        // Circle's line isn't visible to cross in the fog of war
+        board.fog = Some(1);
+        assert_eq!(board.winning_cells(Cell::Circle), []);
+    }
+
+    #[test]
+    fn moved_piece_completes_line() {
+        let mut board = Board::new(3, 3, 3);
+        board.pieces = Some(3);
+        for (cell, x, y) in [
+            (Cell::Cross, 0, 0),
+            (Cell::Circle, 1, 1),
+            (Cell::Cross, 0, 2),
+            (Cell::Circle, 2, 0),
+            (Cell::Cross, 2, 2),
+            (Cell::Circle, 1, 0),
+        ] {
+            board.set(cell, x, y).unwrap();
+        }
+        assert!(board.must_move(Cell::Cross));
+
+        board.select(Pos::new(1, 1));
+        assert!(board.lift_selected(Cell::Cross).is_err());
+        board.select(Pos::new(0, 0));
+        board.lift_selected(Cell::Cross).unwrap();
+        board.select(Pos::new(1, 2));
+        assert_eq!(board.move_selected().unwrap(), Some(Cell::Cross));
+        assert_eq!(board.cells[0], Cell::Empty);
+        assert_eq!(board.last_move(), Some(Pos::new(1, 2)));
+    }
+
+    #[test]
+    fn rules_give_illegal_reasons() {
+        let mut board = Board::new(3, 3, 3);
+        board.pieces = Some(1);
+        play(&mut board, &[(0, 0), (2, 2)]);
+        let cross =
+            |b: &Board, x, y| b.legality_for(Pos::new(x, y), Cell::Cross);
+        assert_eq!(cross(&board, 1, 1), Err(Illegal::MustMove));
+        assert_eq!(cross(&board, 2, 2), Err(Illegal::NotOwnPiece));
+        assert_eq!(cross(&board, 0, 0), Ok(()));
+
+        let mut board = Board::new(5, 5, 3);
+        board.fog = Some(1);
+        play(&mut board, &[(0, 0), (4, 4)]);
+        assert_eq!(cross(&board, 4, 4), Err(Illegal::Hidden));
+        assert_eq!(cross(&board, 3, 4), Ok(()));
+        assert_eq!(cross(&board, 0, 0), Err(Illegal::Occupied));
+
+        let mut board = Board::new(3, 3, 3);
+        board.multi = true;
+        play(&mut board, &[(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)]);
+        assert_eq!(cross(&board, 2, 2), Err(Illegal::DeadBoard));
+        assert!(Illegal::DeadBoard.by_rule() && !Illegal::Occupied.by_rule());
+    }
+
+    #[test]
+    fn decayed_marks_disappear() {
+        let mut board = Board::new(4, 4, 3);
+        board.decay = Some(5);
+        let moves = [(0, 0), (3, 0), (0, 1), (3, 1), (1, 3)];
+        assert_eq!(play(&mut board, &moves), None);
+        assert_eq!(board.expires_in(Pos::new(0, 0)), Some(1));
+
+        // First cross disappears, so the column isn't completed
+        board.set(Cell::Circle, 2, 3).unwrap();
+        assert_eq!(board.set(Cell::Cross, 0, 2).unwrap(), None);
+        assert_eq!(board.cells[0], Cell::Empty);
+        assert_eq!(empty_cells(&board), 11);
+    }
+
+    #[test]
+    fn short_decay_is_draw() {
+        let mut board = Board::new(5, 5, 4);
+        board.decay = Some(6);
+        assert_eq!(play(&mut board, &[(2, 2)]), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn fog_hides_far_cells_until_end() {
+        let mut board = Board::new(5, 5, 3);
+        board.fog = Some(1);
+        play(&mut board, &[(0, 0), (4, 4), (1, 0), (4, 3)]);
+        let visible = board.visible(Cell::Cross);
+        assert!(visible[2] && visible[6]);
+        assert!(!visible[3] && !visible[24]);
+
+        board.set(Cell::Cross, 2, 0).unwrap();
+        assert!(board.visible(Cell::Cross).iter().all(|v| *v));
+    }
+
+    #[test]
+    fn view_follows_selection() {
+        let mut board = Board::new(200, 200, 5);
+        board.select(Pos::new(0, 0));
+        board.fit_view(Pos::new(20, 10));
+        assert_eq!(board.scroll, Pos::new(0, 0));
+
+        board.select(Pos::new(25, 9));
+        board.fit_view(Pos::new(20, 10));
+        assert_eq!(board.scroll, Pos::new(8, 2));
+        // View doesn't move while the selection stays inside of it
+        board.select(Pos::new(20, 5));
+        board.fit_view(Pos::new(20, 10));
+        assert_eq!(board.scroll, Pos::new(8, 2));
+
+        board.select(Pos::new(199, 199));
+        board.fit_view(Pos::new(20, 10));
+        assert_eq!(board.scroll, Pos::new(180, 190));
+        board.fit_view(Pos::new(200, 200));
+        assert_eq!(board.view, None);
+    }
+
+    #[test]
+    fn reported_state_matches_board() {
+        for (w, h, win) in PROPERTY_SIZES {
+            for seed in 0..PROPERTY_GAMES {
+                let mut rng = Rng::new(seed);
+                let mut board = Board::new(w, h, win);
+                random_game(&mut board, &mut rng);
+                let Some(Cell::Empty) = board.state() else {
+                    continue;
+                };
+
+                // Nobody completes a line however the drawn game continues
+                let mut player = board.history.last().unwrap().0.next();
+                while let Some(pos) = random_empty(&board, &mut rng) {
+                    board.cells.set(pos.x + pos.y * w, player);
+                    player = player.next();
+                }
+                assert!(!has_run(&board, Cell::Cross), "{w}x{h}:{win}");
+                assert!(!has_run(&board, Cell::Circle), "{w}x{h}:{win}");
+            }
+        }
+    }
+
+    #[test]
+    fn restart_matches_new_board() {
+        for (w, h, win) in PROPERTY_SIZES {
+            let mut rng = Rng::new(w as u64 * h as u64 + win as u64);
+            let mut board = Board::new(w, h, win);
+            let moves = random_game(&mut board, &mut rng);
+            board.restart();
+            assert!(board.cells.iter().all(|c| *c == Cell::Empty));
+            assert!(board.history.is_empty() && board.win.is_empty());
+            assert_eq!(board.state(), None);
+
+            let mut fresh = Board::new(w, h, win);
+            let mut player = Cell::Cross;
+            for pos in moves {
+                let state = board.set(player, pos.x, pos.y).unwrap();
+                assert_eq!(state, fresh.set(player, pos.x, pos.y).unwrap());
+                player = player.next();
+            }
+            assert_eq!(board.win, fresh.win);
+        }
+    }
+
+    #[test]
+    fn sparse_storage_matches_dense() {
+        for (w, h, win) in PROPERTY_SIZES {
+            for seed in 0..PROPERTY_GAMES / 10 {
+                let mut rng = Rng::new(seed);
+                let mut dense = Board::new(w, h, win);
+                let mut sparse = Board::new(w, h, win);
+                sparse.cells = Cells::sparse(w * h);
+                for board in [&mut dense, &mut sparse] {
+                    board.fog = Some(1);
+                    board.add_blocks(w / 2, &mut Rng::new(seed));
+                }
+                let mut player = Cell::Cross;
+                while dense.state().is_none() {
+                    assert_eq!(sparse.visible(player), dense.visible(player));
+                    let pos = random_empty(&dense, &mut rng).unwrap();
+                    let state = dense.set(player, pos.x, pos.y).unwrap();
+                    assert_eq!(
+                        state,
+                        sparse.set(player, pos.x, pos.y).unwrap(),
+                        "{w}x{h}:{win}"
+                    );
+                    player = player.next();
+                }
+                assert_eq!(sparse.cells, dense.cells);
+                assert_eq!(sparse.win, dense.win);
+            }
+        }
+    }
+
+    #[test]
+    fn infinite_board_grows_near_edges() {
+        let mut board = Board::new(5, 5, 3);
+        board.infinite = true;
+        board.select(Pos::new(0, 0));
+        board.set_selected(Cell::Cross).unwrap();
+        // Longest line fits around the mark, which is shifted with the
+        // selection
+        assert_eq!(board.size, Pos::new(8, 8));
+        assert_eq!(board.last_move(), Some(Pos::new(3, 3)));
+        assert_eq!(board.selected, Pos::new(3, 3));
+
+        board.set(Cell::Circle, 7, 3).unwrap();
+        assert_eq!(board.size, Pos::new(11, 8));
+        assert_eq!(board.cell(Pos::new(3, 3)), Cell::Cross);
+        assert_eq!(board.cell(Pos::new(7, 3)), Cell::Circle);
+        play(&mut board, &[(4, 4), (7, 4), (5, 5)]);
+        assert_eq!(board.state(), Some(Cell::Cross));
+
+        board.restart();
+        assert_eq!(board.size, Pos::new(5, 5));
+    }
+
+    #[test]
+    fn illegal_moves_are_categorized() {
+        let mut board = Board::new(3, 3, 3);
+        play(&mut board, &[(0, 0), (0, 1), (1, 0)]);
+        let occupied = board.set(Cell::Circle, 0, 0).unwrap_err();
+        assert!(matches!(occupied, Error::InvalidMove(_)));
+        assert_eq!(occupied.code(), 3);
+
+        board.set(Cell::Circle, 1, 1).unwrap();
+        board.set(Cell::Cross, 2, 0).unwrap();
+        let ended = board.set(Cell::Circle, 2, 2).unwrap_err();
+        assert!(matches!(ended, Error::GameEnded));
+        assert_eq!(ended.to_string(), "game ended");
+    }
+}
@@ -0,0 +1,62 @@
+use termint::{
+    buffer::Buffer,
+    geometry::{Coords, Rect},
+    widgets::Widget,
+};
+
+use crate::board::Board;
+
+/// Gap between the boards
+const GAP: usize = 2;
+
+/// Boards of the multi-board variant rendered next to each other
+#[derive(Debug)]
+pub struct BoardRow {
+    boards: Vec<Board>,
+}
+
+impl BoardRow {
+    /// Creates new [`BoardRow`] with given boards
+    pub fn new(boards: Vec<Board>) -> Self {
+        Self { boards }
+    }
+}
+
+impl Widget for BoardRow {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut x = buffer.x();
+        for board in &self.boards {
+            let size = buffer.size();
+            // Right border isn't included in the board width
+            let width = board.width(&size) + 1;
+            let mut bbuffer = Buffer::empty(Rect::new(
+                x,
+                buffer.y(),
+                width,
+                board.height(&size),
+            ));
+            board.render(&mut bbuffer);
+            buffer.union(bbuffer);
+            x += width + GAP;
+        }
+    }
+
+    fn height(&self, size: &Coords) -> usize {
+        self.boards
+            .iter()
+            .map(|b| b.height(size))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn width(&self, size: &Coords) -> usize {
+        let boards: usize = self.boards.iter().map(|b| b.width(size)).sum();
+        boards + self.boards.len().saturating_sub(1) * (GAP + 1)
+    }
+}
+
+impl From<BoardRow> for Box<dyn Widget> {
+    fn from(value: BoardRow) -> Self {
+        Box::new(value)
+    }
+}
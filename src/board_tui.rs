@@ -1,182 +1,589 @@
-use crate::{board::Board, cell::Cell};
+use std::cmp::max;
+
+use crate::{
+    annotations::Annotations,
+    board::{Board, Segment},
+    cell::Cell,
+    pos::Pos,
+    theme::WinStyle,
+    zoom::Zoom,
+};
 use termint::{
-    buffer::Buffer, enums::Color, geometry::Coords, style::Style,
+    buffer::Buffer,
+    enums::{Color, Modifier},
+    geometry::{Coords, Rect},
+    style::Style,
     widgets::Widget,
 };
 
+/// Fills the second column of a wide mark. It has zero width, so the
+/// following columns aren't shifted by the wide mark.
+const WIDE_FILL: char = '\u{200B}';
+
+/// Number of moves before the decaying mark disappears from which it's
+/// dimmed and the countdown is shown
+const EXPIRE_SOON: usize = 2;
+
+/// Minimum width of the compact board, so the game state above it fits
+const MIN_COMPACT_WIDTH: usize = 12;
+
 impl Widget for Board {
     fn render(&self, buffer: &mut Buffer) {
-        self.render_inner(buffer);
-        self.render_outer(buffer);
-        self.render_cells(buffer);
-        self.render_sel(buffer);
-        self.render_win(buffer);
+        let gutter = self.gutter();
+        if gutter == Pos::default() {
+            return self.render_grid(buffer);
+        }
+
+        // Right border is drawn one column past the grid width, row labels
+        // are right of it in the right-to-left numbering
+        let (w, h) = self.grid_size();
+        let left = match self.numbering.rtl {
+            true => 0,
+            false => gutter.x,
+        };
+        let mut grid = Buffer::empty(Rect::new(
+            buffer.x() + left,
+            buffer.y() + gutter.y,
+            w + self.zoom.border(),
+            h,
+        ));
+        self.render_grid(&mut grid);
+        self.render_labels(buffer, gutter, left);
+        buffer.union(grid);
     }
 
     fn height(&self, _size: &Coords) -> usize {
-        self.size.y * 2 + 1
+        self.grid_size().1 + self.gutter().y
     }
 
     fn width(&self, _size: &Coords) -> usize {
-        self.size.x * 4
+        self.grid_size().0 + self.gutter().x
     }
 }
 
 impl Board {
-    /// Renders the line over the winning sequence
-    fn render_win(&self, buffer: &mut Buffer) {
-        let Some(pd) = self.win else {
+    /// Gets width of the row labels and height of the column labels, both
+    /// are zero when the labels aren't shown
+    pub fn gutter(&self) -> Pos {
+        match self.labels {
+            true => Pos::new(self.size.y.to_string().len() + 1, 1),
+            false => Pos::default(),
+        }
+    }
+
+    /// Gets size of the rendered grid without the labels
+    fn grid_size(&self) -> (usize, usize) {
+        let (cw, ch) = self.zoom.cell();
+        let view = self.view.unwrap_or(self.size);
+        let width = match self.zoom {
+            Zoom::Compact => max(view.x * cw, MIN_COMPACT_WIDTH),
+            _ => view.x * cw,
+        };
+        (width, view.y * ch + self.zoom.border())
+    }
+
+    /// Renders the visible part of the board
+    fn render_grid(&self, buffer: &mut Buffer) {
+        match self.view {
+            Some(view) => self.render_view(buffer, view),
+            None => self.render_board(buffer),
+        }
+    }
+
+    /// Renders names of the visible columns above the grid and names of
+    /// the visible rows beside it, labels of the selection are accented
+    fn render_labels(&self, buffer: &mut Buffer, gutter: Pos, left: usize) {
+        let (cw, ch) = self.zoom.cell();
+        let view = self.view.unwrap_or(self.size);
+        let style = |sel: bool| match sel && self.focused {
+            true => Style::new().fg(self.theme.accent),
+            false => Style::new().fg(self.theme.dim),
+        };
+
+        for i in 0..view.x {
+            let x = self.scroll.x + i;
+            let name = self.numbering.col(x, self.size.x);
+            // Longer names would overwrite the neighbouring ones
+            if name.len() > max(cw - self.zoom.border(), 1) {
+                continue;
+            }
+            let pos =
+                Coords::new(buffer.x() + left + i * cw + cw / 2, buffer.y());
+            buffer.set_str_styled(name, &pos, style(x == self.selected.x));
+        }
+        // Row names are aligned towards the grid
+        let (x, w) = match self.numbering.rtl {
+            true => (self.grid_size().0 + self.zoom.border() + 1, 0),
+            false => (0, gutter.x - 1),
+        };
+        for i in 0..view.y {
+            let y = self.scroll.y + i;
+            let name = format!("{:>w$}", self.numbering.row(y, self.size.y));
+            let pos = Coords::new(
+                buffer.x() + x,
+                buffer.y() + gutter.y + i * ch + ch / 2,
+            );
+            buffer.set_str_styled(name, &pos, style(y == self.selected.y));
+        }
+    }
+
+    /// Renders the whole board
+    fn render_board(&self, buffer: &mut Buffer) {
+        let compact = self.zoom == Zoom::Compact;
+        if !compact {
+            self.render_inner(buffer);
+            self.render_outer(buffer);
+        }
+        // Distances wouldn't fit the compact cells
+        if self.relative && !compact {
+            self.render_relative(buffer);
+        }
+        self.render_annotations(buffer);
+        if self.focused && !compact {
+            self.render_sel(buffer);
+        }
+        self.render_win(buffer);
+        // Win line would otherwise overwrite part of the wide marks
+        self.render_cells(buffer);
+        if self.threats {
+            self.render_threats(buffer);
+        }
+        if self.focused {
+            self.render_sel_cell(buffer);
+        }
+    }
+
+    /// Renders only the visible part of the board, which is clipped from
+    /// the whole rendered board, and arrows where the board continues
+    fn render_view(&self, buffer: &mut Buffer, view: Pos) {
+        let (cw, ch) = self.zoom.cell();
+        let border = self.zoom.border();
+        let mut board = Buffer::empty(Rect::new(
+            0,
+            0,
+            self.size.x * cw + border,
+            self.size.y * ch + border,
+        ));
+        self.render_board(&mut board);
+
+        let (sx, sy) = (self.scroll.x * cw, self.scroll.y * ch);
+        for y in 0..view.y * ch + border {
+            for x in 0..view.x * cw + border {
+                let cell = board.content()
+                    [board.index_of(&Coords::new(sx + x, sy + y))];
+                buffer.set(cell, &Coords::new(buffer.x() + x, buffer.y() + y));
+            }
+        }
+
+        // Arrows are drawn over the border, compact board has none
+        if border == 0 {
             return;
+        }
+        let (right, bottom) = (view.x * cw, view.y * ch);
+        let style = Style::new().fg(self.theme.accent);
+        let mut arrow = |val: char, x: usize, y: usize| {
+            let pos = Coords::new(buffer.x() + x, buffer.y() + y);
+            buffer.set_str_styled(val.to_string(), &pos, style);
         };
+        if self.scroll.y > 0 {
+            arrow('▲', right / 2, 0);
+        }
+        if self.scroll.y + view.y < self.size.y {
+            arrow('▼', right / 2, bottom);
+        }
+        if self.scroll.x > 0 {
+            arrow('◀', 0, bottom / 2);
+        }
+        if self.scroll.x + view.x < self.size.x {
+            arrow('▶', right, bottom / 2);
+        }
+    }
+
+    /// Gets position of the top left corner of the cell's border
+    fn corner(&self, buffer: &Buffer, pos: Pos) -> Coords {
+        let (cw, ch) = self.zoom.cell();
+        Coords::new(buffer.x() + pos.x * cw, buffer.y() + pos.y * ch)
+    }
+
+    /// Gets position of the cell's center, where its mark is rendered
+    fn center(&self, buffer: &Buffer, pos: Pos) -> Coords {
+        let (cw, ch) = self.zoom.cell();
+        let corner = self.corner(buffer, pos);
+        Coords::new(corner.x + cw / 2, corner.y + ch / 2)
+    }
+
+    /// Gets all the positions inside of the cell's border
+    fn inner(
+        &self,
+        buffer: &Buffer,
+        pos: Pos,
+    ) -> impl Iterator<Item = Coords> {
+        let (cw, ch) = self.zoom.cell();
+        let border = self.zoom.border();
+        let corner = self.corner(buffer, pos);
+        (corner.y + border..corner.y + ch).flat_map(move |y| {
+            (corner.x + border..corner.x + cw).map(move |x| Coords::new(x, y))
+        })
+    }
+
+    /// Gets cells of the winning sequence
+    fn segment_cells(&self, (pos, dir): Segment) -> Vec<Pos> {
+        pos.ray(dir, self.size)
+            .take(self.win_len.get(dir))
+            .collect()
+    }
 
-        match pd.1 {
-            (1, 0) => self.cross_hor(buffer, &pd.0),
-            (0, 1) => self.cross_win(buffer, pd, '|', ' ', ' ', (2, 0)),
-            (1, 1) => self.cross_win(buffer, pd, '\\', '`', '⹁', (0, 0)),
-            (-1, 1) => self.cross_win(buffer, pd, '/', ',', '\'', (4, 0)),
+    /// Renders lines over all the winning sequences
+    fn render_win(&self, buffer: &mut Buffer) {
+        for pd in &self.win {
+            self.render_segment(buffer, *pd);
+        }
+    }
+
+    /// Marks the winning sequence in the theme's win style, compact board
+    /// highlights the marks instead
+    fn render_segment(&self, buffer: &mut Buffer, pd: Segment) {
+        match (self.theme.win_style, self.zoom, pd.1) {
+            (_, Zoom::Compact, _) => {}
+            (WinStyle::Block, ..) => self.block_win(buffer, pd),
+            (WinStyle::Tint, ..) => self.tint_win(buffer, pd),
+            (_, Zoom::Large, _) => self.line_win(buffer, pd),
+            (_, _, (1, 0)) => self.cross_hor(buffer, &pd.0),
+            (_, _, (0, 1)) => self.cross_win(buffer, pd, '|', (2, 0)),
+            (_, _, (1, 1)) => self.cross_win(buffer, pd, '\\', (0, 0)),
+            (_, _, (-1, 1)) => self.cross_win(buffer, pd, '/', (4, 0)),
             _ => {}
         }
+
+        let Some(bg) = self.theme.win_bg else {
+            return;
+        };
+        for pos in self.segment_cells(pd) {
+            for c in self.inner(buffer, pos).collect::<Vec<_>>() {
+                buffer.set_bg(bg, &c);
+            }
+        }
     }
 
     /// Renders selected border
     fn render_sel(&self, buffer: &mut Buffer) {
-        let sel_x = buffer.x() + self.selected.x * 4;
-        let sel_y = buffer.y() + self.selected.y * 2;
+        let (cw, ch) = self.zoom.cell();
+        let corner = self.corner(buffer, self.selected);
 
         let (top, bottom) = match (self.selected.x, self.selected.y) {
-            (0, 0) => ("┏━━━┱", "┡━━━╃"),
-            (0, y) if y + 1 == self.size.y => ("┢━━━╅", "┗━━━┹"),
-            (x, 0) if x + 1 == self.size.x => ("┲━━━┓", "╄━━━┩"),
+            (0, 0) => ("┏┱", "┡╃"),
+            (0, y) if y + 1 == self.size.y => ("┢╅", "┗┹"),
+            (x, 0) if x + 1 == self.size.x => ("┲┓", "╄┩"),
             (x, y) if x + 1 == self.size.x && y + 1 == self.size.y => {
-                ("╆━━━┪", "┺━━━┛")
+                ("╆┪", "┺┛")
             }
-            (_, 0) => ("┲━━━┱", "╄━━━╃"),
-            (_, y) if y + 1 == self.size.y => ("╆━━━╅", "┺━━━┹"),
-            (0, _) => ("┢━━━╅", "┡━━━╃"),
-            (x, _) if x + 1 == self.size.x => ("╆━━━┪", "╄━━━┩"),
-            _ => ("╆━━━╅", "╄━━━╃"),
+            (_, 0) => ("┲┱", "╄╃"),
+            (_, y) if y + 1 == self.size.y => ("╆╅", "┺┹"),
+            (0, _) => ("┢╅", "┡╃"),
+            (x, _) if x + 1 == self.size.x => ("╆┪", "╄┩"),
+            _ => ("╆╅", "╄╃"),
+        };
+        // Corners are joined by the line of the cell's width
+        let line = |corners: &str| {
+            let (left, right) = corners.split_at(corners.len() / 2);
+            format!("{left}{}{right}", "━".repeat(cw - 1))
         };
-        buffer.set_str(top, &Coords::new(sel_x, sel_y));
-        buffer.set_str(bottom, &Coords::new(sel_x, sel_y + 2));
-        buffer.set_val('┃', &Coords::new(sel_x, sel_y + 1));
-        buffer.set_val('┃', &Coords::new(sel_x + 4, sel_y + 1));
+        let style = Style::new().fg(self.theme.selection);
+        buffer.set_str_styled(line(top), &corner, style);
+        let pos = Coords::new(corner.x, corner.y + ch);
+        buffer.set_str_styled(line(bottom), &pos, style);
+        let color = self.theme.selection;
+        for y in corner.y + 1..corner.y + ch {
+            Self::render_cell(buffer, '┃', color, &Coords::new(corner.x, y));
+            let pos = Coords::new(corner.x + cw, y);
+            Self::render_cell(buffer, '┃', color, &pos);
+        }
+    }
+
+    /// Renders background and modifier of the selected cell's content.
+    /// Compact board has no selection border, so the cell is inversed.
+    fn render_sel_cell(&self, buffer: &mut Buffer) {
+        let modifier = match self.zoom {
+            Zoom::Compact => self.theme.selected() | Modifier::INVERSED,
+            _ => self.theme.selected(),
+        };
+        for pos in self.inner(buffer, self.selected).collect::<Vec<_>>() {
+            if let Some(bg) = self.theme.selection_bg {
+                buffer.set_bg(bg, &pos);
+            }
+            if modifier != 0 {
+                buffer.set_style(Style::new().modifier(modifier), &pos);
+            }
+        }
     }
 
     /// Renders cells
     fn render_cells(&self, buffer: &mut Buffer) {
-        let mut coords = Coords::new(buffer.x() + 2, buffer.y() + 1);
+        let visible = self.visible(self.viewer);
+        let compact = self.zoom == Zoom::Compact;
+        let win: Vec<_> = match compact {
+            true => self
+                .win
+                .iter()
+                .flat_map(|s| self.segment_cells(*s))
+                .collect(),
+            false => vec![],
+        };
+        let last = self.last_move();
         let mut id = 0;
-        for _ in 0..self.size.y {
-            for _ in 0..self.size.x {
-                match self.cells[id] {
-                    Cell::Cross => buffer.set_str_styled(
-                        "X",
-                        &coords,
-                        Style::new().fg(Color::Green),
-                    ),
-                    Cell::Circle => buffer.set_str_styled(
-                        "O",
-                        &coords,
-                        Style::new().fg(Color::Red),
-                    ),
-                    Cell::Empty => {}
+        for y in 0..self.size.y {
+            for x in 0..self.size.x {
+                let coords = self.center(buffer, Pos::new(x, y));
+                // Wide mark starts one column left to stay centered
+                let width = match compact {
+                    true => 1,
+                    false => self.marks.width(),
+                };
+                let pos = Coords::new(coords.x + 1 - width, coords.y);
+                if !visible[id] {
+                    let style = Style::new()
+                        .fg(self.theme.grid)
+                        .modifier(Modifier::DIM);
+                    buffer.set_str_styled("?", &coords, style);
+                    id += 1;
+                    continue;
+                }
+                let cell = self.cells[id];
+                let expires = self
+                    .expires_in(Pos::new(x, y))
+                    .filter(|moves| *moves <= EXPIRE_SOON);
+                if cell == Cell::Empty && compact {
+                    let style = Style::new().fg(self.theme.grid);
+                    buffer.set_str_styled("·", &coords, style);
+                }
+                if cell != Cell::Empty {
+                    // Handicap is dimmed until the first move, the lifted
+                    // piece until it's moved and the mark before it decays
+                    let dim = (self.history.is_empty()
+                        && self.handicap.contains(&Pos::new(x, y)))
+                        || self.lifted == Some(Pos::new(x, y))
+                        || expires.is_some();
+                    let modifier = match dim {
+                        true => Modifier::DIM,
+                        false => 0,
+                    };
+                    // Compact board has no win line, marks are inversed
+                    let modifier = match win.contains(&Pos::new(x, y)) {
+                        true => modifier | Modifier::INVERSED,
+                        false => modifier,
+                    };
+                    // Last move stands out, so it's seen what just happened
+                    let modifier = match last == Some(Pos::new(x, y)) {
+                        true => modifier | self.theme.last_move(),
+                        false => modifier,
+                    };
+                    let style = Style::new()
+                        .fg(self.theme.cell(cell))
+                        .modifier(self.theme.weight(cell) | modifier);
+                    let symbol = match compact {
+                        true => cell.symbol(),
+                        false => self.marks.symbol(cell),
+                    };
+                    buffer.set_val(symbol, &pos);
+                    buffer.set_style(style, &pos);
+                    if width > 1 {
+                        buffer.set_val(WIDE_FILL, &coords);
+                    }
+                }
+                if let Some(moves) = expires.filter(|_| !compact) {
+                    let style = Style::new()
+                        .fg(self.theme.grid)
+                        .modifier(Modifier::DIM);
+                    let pos = Coords::new(coords.x + 1, coords.y);
+                    buffer.set_str_styled(moves.to_string(), &pos, style);
+                }
+                let legality = self.legality_for(Pos::new(x, y), self.viewer);
+                if legality.is_err_and(|e| e.by_rule()) {
+                    let style = Style::new()
+                        .fg(self.theme.grid)
+                        .modifier(Modifier::DIM);
+                    buffer.set_style(style, &pos);
                 }
                 id += 1;
-                coords.x += 4;
             }
-            coords.y += 2;
-            coords.x = buffer.x() + 2;
+        }
+    }
+
+    /// Marks cells, where the viewer would win, and cells, where the
+    /// opponent would win next turn
+    fn render_threats(&self, buffer: &mut Buffer) {
+        let threats = [
+            (self.viewer.next(), self.theme.error, Modifier::BOLD),
+            (self.viewer, self.theme.success, 0),
+        ];
+        // Own win is drawn last, since it's more important than blocking
+        for (player, color, modifier) in threats {
+            let style = Style::new().fg(color).modifier(modifier);
+            for pos in self.winning_cells(player) {
+                let center = self.center(buffer, pos);
+                buffer.set_str_styled("•", &center, style);
+            }
+        }
+    }
+
+    /// Renders distances from the selected cell to the empty cells in the
+    /// selected row and column
+    fn render_relative(&self, buffer: &mut Buffer) {
+        let visible = self.visible(self.viewer);
+        let sel = self.selected;
+        let row = (0..self.size.x).map(|x| (x, sel.y, x.abs_diff(sel.x)));
+        let col = (0..self.size.y).map(|y| (sel.x, y, y.abs_diff(sel.y)));
+        let style = Style::new().fg(self.theme.grid);
+        for (x, y, dist) in row.chain(col) {
+            let id = x + y * self.size.x;
+            if dist == 0 || self.cells[id] != Cell::Empty || !visible[id] {
+                continue;
+            }
+            let hint = dist.to_string();
+            let center = self.center(buffer, Pos::new(x, y));
+            let pos =
+                Coords::new(center.x - (hint.len() > 1) as usize, center.y);
+            buffer.set_str_styled(&hint, &pos, style);
+        }
+    }
+
+    /// Renders spectator annotations
+    fn render_annotations(&self, buffer: &mut Buffer) {
+        let anno = &self.annotations;
+        for (start, end) in anno.lines.iter() {
+            for pos in Annotations::line_cells(*start, *end) {
+                if self.cells[pos.index(self.size.x)] == Cell::Empty {
+                    let c = self.center(buffer, pos);
+                    Self::render_cell(buffer, '•', self.theme.highlight, &c);
+                }
+            }
+        }
+        for pos in anno.cells.iter().chain(anno.anchor.iter()) {
+            for c in self.inner(buffer, *pos).collect::<Vec<_>>() {
+                let style = self.theme.highlighted();
+                buffer.set_style(style, &c);
+            }
         }
     }
 
     /// Renders outer borders
     fn render_outer(&self, buffer: &mut Buffer) {
-        let bottom = self.size.y * 2;
-        let right = self.size.x * 4;
+        let (cw, ch) = self.zoom.cell();
+        let bottom = self.size.y * ch;
+        let right = self.size.x * cw;
 
+        let line = "─".repeat(cw - 1);
         buffer.set_str_styled(
-            "───┬".repeat(self.size.x),
+            format!("{line}┬").repeat(self.size.x),
             &Coords::new(buffer.x() + 1, buffer.y()),
-            Style::new().fg(Color::Gray),
+            Style::new().fg(self.theme.grid),
         );
         buffer.set_str_styled(
-            "───┴".repeat(self.size.x),
+            format!("{line}┴").repeat(self.size.x),
             &Coords::new(buffer.x() + 1, buffer.y() + bottom),
-            Style::new().fg(Color::Gray),
+            Style::new().fg(self.theme.grid),
         );
 
         let mut leftc = Coords::new(buffer.x(), buffer.y() + 1);
         let mut rightc = Coords::new(buffer.x() + right, buffer.y() + 1);
-        for _ in buffer.y()..buffer.y() + self.size.y {
-            Board::border_part('│', buffer, &leftc);
-            leftc.y += 1;
-            Board::border_part('├', buffer, &leftc);
+        for _ in 0..self.size.y {
+            for _ in 1..ch {
+                self.border_part('│', buffer, &leftc);
+                leftc.y += 1;
+                self.border_part('│', buffer, &rightc);
+                rightc.y += 1;
+            }
+            self.border_part('├', buffer, &leftc);
             leftc.y += 1;
-
-            Board::border_part('│', buffer, &rightc);
-            rightc.y += 1;
-            Board::border_part('┤', buffer, &rightc);
+            self.border_part('┤', buffer, &rightc);
             rightc.y += 1;
         }
 
         let mut pos = buffer.pos();
-        Board::border_part('┌', buffer, &pos);
+        self.border_part('┌', buffer, &pos);
         pos.x += right;
-        Board::border_part('┐', buffer, &pos);
+        self.border_part('┐', buffer, &pos);
         pos.y += bottom;
-        Board::border_part('┘', buffer, &pos);
+        self.border_part('┘', buffer, &pos);
         pos.x -= right;
-        Board::border_part('└', buffer, &pos);
+        self.border_part('└', buffer, &pos);
     }
 
     /// Renders inner borders
     fn render_inner(&self, buffer: &mut Buffer) {
-        let line = "───┼".repeat(self.size.x);
+        let (cw, ch) = self.zoom.cell();
+        let line = format!("{}┼", "─".repeat(cw - 1)).repeat(self.size.x);
         for y in 1..self.size.y {
             buffer.set_str_styled(
                 &line,
-                &Coords::new(buffer.x() + 1, buffer.y() + y * 2),
-                Style::new().fg(Color::Gray),
+                &Coords::new(buffer.x() + 1, buffer.y() + y * ch),
+                Style::new().fg(self.theme.grid),
             );
         }
 
-        let line = "   │".repeat(self.size.x);
+        let line = format!("{}│", " ".repeat(cw - 1)).repeat(self.size.x);
         for y in 0..self.size.y {
-            buffer.set_str_styled(
-                &line,
-                &Coords::new(buffer.x() + 1, buffer.y() + y * 2 + 1),
-                Style::new().fg(Color::Gray),
-            )
+            for row in 1..ch {
+                buffer.set_str_styled(
+                    &line,
+                    &Coords::new(buffer.x() + 1, buffer.y() + y * ch + row),
+                    Style::new().fg(self.theme.grid),
+                )
+            }
         }
     }
 
     /// Renders part of the border
-    fn border_part(val: char, buffer: &mut Buffer, pos: &Coords) {
+    fn border_part(&self, val: char, buffer: &mut Buffer, pos: &Coords) {
         buffer.set_val(val, pos);
-        buffer.set_fg(Color::Gray, pos);
+        buffer.set_fg(self.theme.grid, pos);
+    }
+
+    /// Renders the winning line between centers of the first and the last
+    /// cell of the sequence on the large board
+    fn line_win(&self, buffer: &mut Buffer, (pos, (dx, dy)): Segment) {
+        let (cw, ch) = self.zoom.cell();
+        let color = self.win_color(&pos);
+        let len = self.win_len.get((dx, dy)) - 1;
+        let start = self.center(buffer, pos);
+        let (val, steps) = match (dx, dy) {
+            (_, 0) => ('-', len * cw),
+            (0, _) => ('|', len * ch),
+            (1, _) => ('\\', len * ch),
+            _ => ('/', len * ch),
+        };
+        for i in 0..=steps {
+            let pos = match dy {
+                0 => Coords::new(start.x + i, start.y),
+                _ => Coords::new(
+                    (start.x as isize + dx * (i * cw / ch) as isize) as usize,
+                    start.y + i,
+                ),
+            };
+            Self::render_cell(buffer, val, color, &pos);
+        }
     }
 
     /// Renders horizontal win
-    fn cross_hor(&self, buffer: &mut Buffer, pos: &Coords) {
+    fn cross_hor(&self, buffer: &mut Buffer, pos: &Pos) {
         let color = self.win_color(pos);
         let mut pos = Coords::new(
             buffer.x() + pos.x * 4 + 1,
             buffer.y() + pos.y * 2 + 1,
         );
-        for _ in 0..self.win_len * 2 {
+        for _ in 0..self.win_len.rows * 2 {
             Self::render_cell(buffer, '-', color, &pos);
             pos.x += 2;
         }
     }
 
-    /// Crosses the winning sequence
+    /// Crosses the winning sequence by the line character on the grid
+    /// between its marks
     fn cross_win(
         &self,
         buffer: &mut Buffer,
-        (pos, (dx, dy)): (Coords, (isize, isize)),
-        bc: char,
-        fc: char,
-        ac: char,
+        (pos, (dx, dy)): Segment,
+        val: char,
         (ox, oy): (usize, usize),
     ) {
         let color = self.win_color(&pos);
@@ -185,31 +592,187 @@ impl Board {
             buffer.y() + pos.y * 2 + oy,
         );
 
-        for _ in 0..self.win_len {
-            Self::render_cell(buffer, bc, color, &p);
-            p.x = (p.x as isize + dx * 2) as usize;
-            p.y = (p.y as isize + dy) as usize;
+        for _ in 0..=self.win_len.get((dx, dy)) {
+            Self::render_cell(buffer, val, color, &p);
+            p.x = (p.x as isize + dx * 4) as usize;
+            p.y = (p.y as isize + dy * 2) as usize;
+        }
+    }
 
-            Self::render_cell(buffer, fc, color, &Coords::new(p.x - 1, p.y));
-            Self::render_cell(buffer, ac, color, &Coords::new(p.x + 1, p.y));
-            p.x = (p.x as isize + dx * 2) as usize;
-            p.y = (p.y as isize + dy) as usize;
+    /// Fills the cells of the winning sequence by blocks, the marks are
+    /// rendered over them
+    fn block_win(&self, buffer: &mut Buffer, pd: Segment) {
+        let color = self.win_color(&pd.0);
+        for pos in self.segment_cells(pd) {
+            for c in self.inner(buffer, pos).collect::<Vec<_>>() {
+                Self::render_cell(buffer, '█', color, &c);
+            }
         }
-        Self::render_cell(buffer, bc, color, &p);
     }
 
-    /// Gets win line color
-    fn win_color(&self, pos: &Coords) -> Color {
-        match self.cells[pos.x + pos.y * self.size.x] {
-            Cell::Cross => Color::Green,
-            Cell::Circle => Color::Red,
-            Cell::Empty => Color::Default,
+    /// Tints background of the cells of the winning sequence
+    fn tint_win(&self, buffer: &mut Buffer, pd: Segment) {
+        let style = self.theme.win_tint();
+        for pos in self.segment_cells(pd) {
+            for c in self.inner(buffer, pos).collect::<Vec<_>>() {
+                buffer.set_style(style, &c);
+            }
         }
     }
 
+    /// Gets win line color
+    fn win_color(&self, pos: &Pos) -> Color {
+        self.theme.cell(self.cells[pos.index(self.size.x)])
+    }
+
     /// Renders value and color to cell on given position
     fn render_cell(buffer: &mut Buffer, val: char, col: Color, pos: &Coords) {
         buffer.set_val(val, pos);
         buffer.set_fg(col, pos);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::notation::{Numbering, Origin};
+
+    use super::*;
+
+    /// Renders the board and gets its characters, one line per row
+    fn grid(board: &Board) -> String {
+        let zero = Coords::new(0, 0);
+        // Right border is drawn one column past the reported width
+        let (w, h) = (board.width(&zero) + 1, board.height(&zero));
+        let mut buffer = Buffer::empty(Rect::new(1, 1, w, h));
+        board.render(&mut buffer);
+
+        let chars: Vec<_> = buffer.content().iter().map(|c| c.val).collect();
+        chars
+            .chunks(w)
+            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    /// Compares the rendered board with the saved snapshot of given name,
+    /// snapshots are (re)created when `UPDATE_SNAPSHOTS` is set
+    fn snapshot(name: &str, board: &Board) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src/snapshots")
+            .join(format!("{name}.txt"));
+        let grid = grid(board);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &grid).unwrap();
+            return;
+        }
+        let saved = fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!("missing snapshot {name}, run with UPDATE_SNAPSHOTS=1")
+        });
+        assert_eq!(grid, saved, "snapshot {name} differs");
+    }
+
+    /// Creates board with given moves played alternately starting with
+    /// cross
+    fn played(
+        w: usize,
+        h: usize,
+        win: usize,
+        moves: &[(usize, usize)],
+    ) -> Board {
+        let mut board = Board::new(w, h, win);
+        let mut player = Cell::Cross;
+        for (x, y) in moves {
+            board.set(player, *x, *y).unwrap();
+            player = player.next();
+        }
+        board
+    }
+
+    #[test]
+    fn selection_in_corners() {
+        let corners = [
+            ("top_left", (0, 0)),
+            ("top_right", (2, 0)),
+            ("bottom_left", (0, 2)),
+            ("bottom_right", (2, 2)),
+        ];
+        for (name, (x, y)) in corners {
+            let mut board = played(3, 3, 3, &[(1, 1), (0, 1)]);
+            board.select(Pos::new(x, y));
+            snapshot(&format!("selection_{name}"), &board);
+        }
+    }
+
+    #[test]
+    fn win_in_each_direction() {
+        let wins: [(&str, &[(usize, usize)]); 4] = [
+            ("row", &[(0, 1), (0, 0), (1, 1), (1, 0), (2, 1)]),
+            ("column", &[(1, 0), (0, 0), (1, 1), (2, 0), (1, 2)]),
+            ("diagonal", &[(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)]),
+            ("anti_diagonal", &[(2, 0), (0, 0), (1, 1), (1, 0), (0, 2)]),
+        ];
+        for (name, moves) in wins {
+            let board = played(3, 3, 3, moves);
+            assert!(board.state().is_some_and(|s| s != Cell::Empty));
+            snapshot(&format!("win_{name}"), &board);
+        }
+    }
+
+    #[test]
+    fn win_styles() {
+        let moves = [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)];
+        let mut board = played(3, 3, 3, &moves);
+        board.theme.win_style = WinStyle::Block;
+        snapshot("win_block", &board);
+
+        // Tint changes only the background, so the grid stays untouched
+        board.theme.win_style = WinStyle::Tint;
+        snapshot("win_tint", &board);
+    }
+
+    #[test]
+    fn large_boards() {
+        let moves = [(7, 7), (8, 8), (6, 7), (8, 7), (0, 0), (14, 14)];
+        let mut board = played(15, 15, 5, &moves);
+        board.select(Pos::new(8, 6));
+        snapshot("large_15x15", &board);
+
+        // Scrolled view shows arrows towards the hidden cells
+        let mut board = played(100, 50, 5, &[(50, 25), (51, 26)]);
+        board.select(Pos::new(50, 25));
+        board.fit_view(Pos::new(10, 6));
+        snapshot("large_100x50_view", &board);
+    }
+
+    #[test]
+    fn labels_around_board() {
+        let mut board = played(12, 3, 3, &[(0, 0), (11, 2)]);
+        board.labels = true;
+        assert_eq!(board.gutter(), Pos::new(2, 1));
+        snapshot("labels_12x3", &board);
+
+        board.zoom = Zoom::Compact;
+        snapshot("labels_compact", &board);
+
+        board.zoom = Zoom::Normal;
+        board.numbering = Numbering {
+            origin: Origin::BottomLeft,
+            rtl: true,
+        };
+        snapshot("labels_rtl", &board);
+    }
+
+    #[test]
+    fn zoom_levels() {
+        let moves = [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)];
+        for zoom in [Zoom::Compact, Zoom::Large] {
+            let mut board = played(3, 3, 3, &moves);
+            board.zoom = zoom;
+            snapshot(&format!("zoom_{}", zoom.name()), &board);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{board::Board, cell::Cell, pos::Pos, win_len::WinLen};
+
+/// Opening book of the standard 3x3 game with win length 3. Positions are
+/// canonical (see [`Board::canonical`]), cells are listed by rows from the
+/// top left as `X` (first player), `O` or `.`. Each position has all its
+/// perfect moves, center first and edges last.
+const BOOK: &[(&str, &[usize])] = &[
+    (".........", &[4, 0, 2, 6, 8, 1, 3, 5, 7]),
+    ("....X....", &[0, 2, 6, 8]),
+    (".X.......", &[4, 0, 2, 7]),
+    ("X........", &[4]),
+    (".O..X....", &[0, 2, 6, 8, 3, 5]),
+    (".X.....O.", &[4, 0, 2, 6, 8, 3, 5]),
+    (".X..O....", &[0, 2, 6, 8, 3, 5]),
+    (".X.O.....", &[4, 0]),
+    ("O....X...", &[2]),
+    ("O...X....", &[2, 6, 8, 1, 3, 5, 7]),
+    ("OX.......", &[4, 6, 8, 3]),
+    ("X.......O", &[2, 6]),
+    ("X....O...", &[4, 2, 6]),
+    ("X...O....", &[2, 6, 8, 1, 3, 5, 7]),
+    ("X.O......", &[6, 8, 3]),
+    ("XO.......", &[4, 6, 3]),
+    (".X..O..X.", &[0, 2, 6, 8, 3, 5]),
+    (".X..X..O.", &[0, 2, 6, 8]),
+    (".X.O...X.", &[4]),
+    (".X.OX....", &[0, 2, 6, 8, 5, 7]),
+    (".X.X.O...", &[0, 6]),
+    (".X.XO....", &[0, 2, 6]),
+    ("O....X.X.", &[2, 6]),
+    ("O...XX...", &[3]),
+    ("OX.....X.", &[4]),
+    ("OX...X...", &[6]),
+    ("OX..X....", &[7]),
+    ("OX.X.....", &[4, 5, 7]),
+    ("X....X..O", &[4, 6, 3]),
+    ("X....X.O.", &[4]),
+    ("X...O...X", &[1, 3, 5, 7]),
+    ("X...OX...", &[2, 8, 1, 7]),
+    ("X...X...O", &[2, 6]),
+    ("X...XO...", &[2, 6, 8, 1, 3, 7]),
+    ("X.O.....X", &[4, 6, 1, 3, 5, 7]),
+    ("X.O....X.", &[8]),
+    ("X.O..X...", &[4, 3]),
+    ("X.O.X....", &[8]),
+    ("X.X....O.", &[4, 6, 8, 1, 3, 5]),
+    ("X.X...O..", &[4, 8, 1, 3, 5, 7]),
+    ("X.X.O....", &[1]),
+    ("XO......X", &[4]),
+    ("XO.....X.", &[6, 8]),
+    ("XO....X..", &[4, 2, 8, 3, 5, 7]),
+    ("XO...X...", &[4]),
+    ("XO..X....", &[2, 6, 8, 3, 5, 7]),
+    ("XOX......", &[4]),
+    ("XX......O", &[2]),
+    ("XX.....O.", &[2]),
+    ("XX....O..", &[4, 2, 8, 3, 5, 7]),
+    ("XX...O...", &[2]),
+    ("XX..O....", &[2]),
+    ("XX.O.....", &[4, 2, 6, 8, 5, 7]),
+    ("XXO......", &[8, 5]),
+];
+
+/// Gets perfect moves of the given player from the opening book, `None`
+/// when the board is not the standard 3x3 or the position isn't in the book
+pub fn moves(board: &Board, player: Cell) -> Option<Vec<Pos>> {
+    if board.size != Pos::new(3, 3) || board.win_len != WinLen::new(3) {
+        return None;
+    }
+
+    // Book contains positions, where the first player plays cross
+    let count = |c| board.cells.count(c);
+    let mut board = board.clone();
+    if (player == Cell::Cross) != (count(player) == count(player.next())) {
+        let marks: Vec<_> = board.cells.occupied().collect();
+        for (id, cell) in marks {
+            if matches!(cell, Cell::Cross | Cell::Circle) {
+                board.cells.set(id, cell.next());
+            }
+        }
+    }
+
+    let (cells, sym) = board.canonical();
+    let key = cells
+        .iter()
+        .map(|cell| match cell {
+            Cell::Empty => Some('.'),
+            Cell::Blocked => None,
+            c => Some(c.symbol()),
+        })
+        .collect::<Option<String>>()?;
+    let (_, moves) = BOOK.iter().find(|(pos, _)| *pos == key)?;
+    Some(
+        moves
+            .iter()
+            .map(|id| sym.revert(Pos::from_index(*id, 3), board.size))
+            .collect(),
+    )
+}
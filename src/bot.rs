@@ -0,0 +1,204 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    board::Board,
+    cell::Cell,
+    error::Error,
+    notation::{parse_pos, pos_name},
+    pos::Pos,
+};
+
+/// Version of the bot protocol sent in the handshake
+const VERSION: usize = 1;
+/// Time the bot has to answer the handshake
+const HANDSHAKE_TIME: Duration = Duration::from_secs(5);
+/// Time per move, when it's not given
+pub const DEFAULT_TIME: Duration = Duration::from_secs(5);
+/// Extra time for the bot's answer to arrive after its time runs out
+const GRACE: Duration = Duration::from_secs(1);
+
+/// External program playing as an opponent. It's spawned as a subprocess
+/// and plays using the line-based protocol over its stdin and stdout:
+///
+/// ```text
+/// > tictactoe 1
+/// < ready [name]
+/// > position <width> <height> <win-rows> <win-cols> <win-diag> <cells>
+/// > go <X|O> <time in ms>
+/// < move <cell>
+/// > quit
+/// ```
+///
+/// Cells are rows of `.`, `X`, `O` and `#` (blocked) separated by `/`, cell
+/// names are like `b3`. Other lines from the bot are ignored.
+#[derive(Debug)]
+pub struct Bot {
+    pub name: String,
+    /// Time the bot has for each move
+    pub time: Duration,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl Bot {
+    /// Spawns the bot from the given command line and waits for it to get
+    /// ready
+    pub fn spawn(cmd: &str, time: Duration) -> Result<Self, Error> {
+        let mut parts = cmd.split_whitespace();
+        let prog = parts
+            .next()
+            .ok_or_else(|| Error::parse("bot command is empty"))?;
+        let mut child = Command::new(prog)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("cannot start bot '{prog}': {e}"),
+                )
+            })?;
+
+        let broken = |msg: &str| Error::Protocol(msg.into());
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| broken("cannot write to the bot"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| broken("cannot read from the bot"))?;
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut bot = Self {
+            name: prog.to_string(),
+            time,
+            child,
+            stdin,
+            lines,
+        };
+        bot.send(&format!("tictactoe {VERSION}"))?;
+        let ready = bot.expect("ready", HANDSHAKE_TIME)?;
+        if !ready.is_empty() {
+            bot.name = ready;
+        }
+        Ok(bot)
+    }
+
+    /// Gets move of the given player on the board chosen by the bot
+    pub fn best_move(
+        &mut self,
+        board: &Board,
+        player: Cell,
+    ) -> Result<Pos, Error> {
+        self.send(&format!(
+            "position {} {} {} {} {} {}",
+            board.size.x,
+            board.size.y,
+            board.win_len.rows,
+            board.win_len.cols,
+            board.win_len.diag,
+            Bot::cells(board)
+        ))?;
+        self.send(&format!(
+            "go {} {}",
+            player.symbol(),
+            self.time.as_millis()
+        ))?;
+
+        let name = self.expect("move", self.time + GRACE)?;
+        let pos = parse_pos(&name)
+            .filter(|p| p.x < board.size.x && p.y < board.size.y)
+            .ok_or_else(|| {
+                Error::InvalidMove(format!("bot played unknown cell '{name}'"))
+            })?;
+        match board.legality(pos) {
+            Ok(_) => Ok(pos),
+            Err(e) => Err(Error::InvalidMove(format!(
+                "bot played {}, {}",
+                pos_name(&pos),
+                e.reason()
+            ))),
+        }
+    }
+
+    /// Sends line to the bot
+    fn send(&mut self, line: &str) -> Result<(), Error> {
+        writeln!(self.stdin, "{line}")
+            .and_then(|_| self.stdin.flush())
+            .map_err(|_| Error::Protocol(format!("bot {} stopped", self.name)))
+    }
+
+    /// Waits for the line starting with the given command and gets the
+    /// rest of it
+    fn expect(&mut self, cmd: &str, time: Duration) -> Result<String, Error> {
+        let end = Instant::now() + time;
+        loop {
+            let left = end.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(left) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => Err(Error::Protocol(
+                    format!("bot {} didn't answer '{cmd}' in time", self.name),
+                ))?,
+                Err(RecvTimeoutError::Disconnected) => {
+                    Err(Error::Protocol(format!("bot {} stopped", self.name)))?
+                }
+            };
+            let mut parts = line.trim().splitn(2, ' ');
+            if parts.next() == Some(cmd) {
+                return Ok(parts.next().unwrap_or_default().trim().into());
+            }
+        }
+    }
+
+    /// Gets cells of the board in the protocol format
+    fn cells(board: &Board) -> String {
+        let rows: Vec<String> = board
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|c| match c {
+                        Cell::Empty => '.',
+                        c => c.symbol(),
+                    })
+                    .collect()
+            })
+            .collect();
+        rows.join("/")
+    }
+}
+
+impl Drop for Bot {
+    fn drop(&mut self) {
+        _ = self.send("quit");
+        // Bot gets a moment to quit by itself before it's killed
+        let end = Instant::now() + Duration::from_millis(200);
+        while Instant::now() < end {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        _ = self.child.kill();
+        _ = self.child.wait();
+    }
+}
@@ -1,9 +1,10 @@
 /// Represents cell value
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum Cell {
     Cross,
     Circle,
     Empty,
+    Blocked,
 }
 
 impl Cell {
@@ -14,4 +15,14 @@ impl Cell {
             _ => Cell::Cross,
         }
     }
+
+    /// Gets symbol representing the cell
+    pub fn symbol(self) -> char {
+        match self {
+            Cell::Cross => 'X',
+            Cell::Circle => 'O',
+            Cell::Empty => ' ',
+            Cell::Blocked => '#',
+        }
+    }
 }
@@ -0,0 +1,167 @@
+use std::{collections::BTreeMap, ops::Index};
+
+use crate::cell::Cell;
+
+/// Number of cells from which the boards store only the non-empty cells
+pub const SPARSE_LEN: usize = 160 * 160;
+
+/// Cells of the board by their index. Huge boards, which stay mostly empty,
+/// store only the non-empty cells.
+#[derive(Debug, Clone)]
+pub enum Cells {
+    Dense(Vec<Cell>),
+    /// Non-empty cells ordered by index and number of all the cells
+    Sparse(BTreeMap<usize, Cell>, usize),
+}
+
+impl Cells {
+    /// Creates given number of empty cells, the storage is chosen by the
+    /// number of cells
+    pub fn new(len: usize) -> Self {
+        match len < SPARSE_LEN {
+            true => Self::dense(len),
+            false => Self::sparse(len),
+        }
+    }
+
+    /// Creates given number of empty cells stored all
+    pub fn dense(len: usize) -> Self {
+        Self::Dense(vec![Cell::Empty; len])
+    }
+
+    /// Creates given number of empty cells, which aren't stored
+    pub fn sparse(len: usize) -> Self {
+        Self::Sparse(BTreeMap::new(), len)
+    }
+
+    /// Creates empty cells stored the same way as these ones
+    pub fn empty(&self, len: usize) -> Self {
+        match self {
+            Self::Dense(_) => Self::dense(len),
+            Self::Sparse(..) => Self::sparse(len),
+        }
+    }
+
+    /// Gets number of the cells
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Dense(cells) => cells.len(),
+            Self::Sparse(_, len) => *len,
+        }
+    }
+
+    /// Checks whether there are no cells
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets cell on the given index
+    pub fn set(&mut self, id: usize, cell: Cell) {
+        match self {
+            Self::Dense(cells) => cells[id] = cell,
+            Self::Sparse(cells, len) => {
+                assert!(id < *len, "cell {id} out of {len} cells");
+                match cell {
+                    Cell::Empty => _ = cells.remove(&id),
+                    cell => _ = cells.insert(id, cell),
+                }
+            }
+        }
+    }
+
+    /// Iterates over all the cells
+    pub fn iter(&self) -> impl Iterator<Item = &Cell> {
+        (0..self.len()).map(|id| &self[id])
+    }
+
+    /// Iterates over the non-empty cells with their indexes in order
+    pub fn occupied(&self) -> Box<dyn Iterator<Item = (usize, Cell)> + '_> {
+        match self {
+            Self::Dense(cells) => Box::new(
+                cells
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, c)| *c != Cell::Empty),
+            ),
+            Self::Sparse(cells, _) => {
+                Box::new(cells.iter().map(|(id, c)| (*id, *c)))
+            }
+        }
+    }
+
+    /// Gets number of the given cells
+    pub fn count(&self, cell: Cell) -> usize {
+        match (self, cell) {
+            (Self::Dense(cells), cell) => {
+                cells.iter().filter(|c| **c == cell).count()
+            }
+            (Self::Sparse(cells, len), Cell::Empty) => len - cells.len(),
+            (Self::Sparse(cells, _), cell) => {
+                cells.values().filter(|c| **c == cell).count()
+            }
+        }
+    }
+
+    /// Checks whether there's the given cell
+    pub fn contains(&self, cell: &Cell) -> bool {
+        match (self, cell) {
+            (Self::Dense(cells), cell) => cells.contains(cell),
+            (Self::Sparse(cells, len), Cell::Empty) => cells.len() < *len,
+            (Self::Sparse(cells, _), cell) => {
+                cells.values().any(|c| c == cell)
+            }
+        }
+    }
+
+    /// Gets all the cells
+    pub fn to_vec(&self) -> Vec<Cell> {
+        match self {
+            Self::Dense(cells) => cells.clone(),
+            Self::Sparse(..) => self.iter().copied().collect(),
+        }
+    }
+}
+
+impl Index<usize> for Cells {
+    type Output = Cell;
+
+    fn index(&self, id: usize) -> &Self::Output {
+        match self {
+            Self::Dense(cells) => &cells[id],
+            Self::Sparse(cells, len) => {
+                assert!(id < *len, "cell {id} out of {len} cells");
+                cells.get(&id).unwrap_or(&Cell::Empty)
+            }
+        }
+    }
+}
+
+impl PartialEq for Cells {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.occupied().eq(other.occupied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_matches_dense() {
+        let (mut dense, mut sparse) = (Cells::dense(10), Cells::sparse(10));
+        for (id, cell) in
+            [(3, Cell::Cross), (7, Cell::Circle), (3, Cell::Empty)]
+        {
+            dense.set(id, cell);
+            sparse.set(id, cell);
+        }
+        assert_eq!(dense, sparse);
+        assert_eq!(dense.to_vec(), sparse.to_vec());
+        let occupied: Vec<_> = sparse.occupied().collect();
+        assert_eq!(occupied, [(7, Cell::Circle)]);
+        assert_eq!(sparse.count(Cell::Empty), dense.count(Cell::Empty));
+        assert!(!sparse.contains(&Cell::Cross));
+        assert!(matches!(Cells::new(SPARSE_LEN), Cells::Sparse(..)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::{stdout, Write};
+
+use crate::error::Error;
+
+/// Copies the text to the system clipboard using the clipboard program,
+/// falls back to the terminal when none is available
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> Result<(), Error> {
+    use std::process::{Command, Stdio};
+
+    const PROGRAMS: [&[&str]; 5] = [
+        &["wl-copy"],
+        &["xclip", "-selection", "clipboard"],
+        &["xsel", "--clipboard", "--input"],
+        &["pbcopy"],
+        &["clip.exe"],
+    ];
+    for prog in PROGRAMS {
+        let child = Command::new(prog[0])
+            .args(&prog[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = child else {
+            continue;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        if child.wait().is_ok_and(|s| s.success()) && written {
+            return Ok(());
+        }
+    }
+    copy_osc52(text)
+}
+
+/// Clipboard programs are disabled, text is copied by the terminal
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(text: &str) -> Result<(), Error> {
+    copy_osc52(text)
+}
+
+/// Asks the terminal to copy the text using the OSC 52 escape sequence,
+/// which works over SSH too, but not all terminals support it
+fn copy_osc52(text: &str) -> Result<(), Error> {
+    let mut out = stdout();
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Encodes the data using base64 with padding
+fn base64(data: &[u8]) -> String {
+    const CHARS: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => {
+                    res.push(CHARS[(n >> (18 - 6 * i) & 63) as usize] as char)
+                }
+                false => res.push('='),
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"X.O\n"), "WC5PCg==");
+    }
+}
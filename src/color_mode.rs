@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use termint::enums::Color;
+
+use crate::{error::Error, theme::Theme};
+
+/// How colors are used for rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Colors of the theme
+    #[default]
+    Full,
+    /// Player colors distinguishable with color blindness, cross is bold
+    Colorblind,
+    /// No colors, selection is underlined
+    Mono,
+}
+
+impl ColorMode {
+    /// All the color modes
+    pub const ALL: &'static [ColorMode] =
+        &[ColorMode::Full, ColorMode::Colorblind, ColorMode::Mono];
+
+    /// Gets name of the color mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorMode::Full => "full",
+            ColorMode::Colorblind => "colorblind",
+            ColorMode::Mono => "mono",
+        }
+    }
+
+    /// Adjusts colors of the given theme to the color mode
+    pub fn apply(&self, theme: Theme) -> Theme {
+        let theme = Theme {
+            mode: *self,
+            ..theme
+        };
+        match self {
+            ColorMode::Full => theme,
+            ColorMode::Colorblind => Theme {
+                cross: Color::Blue,
+                circle: Color::Yellow,
+                error: Color::Magenta,
+                success: Color::Cyan,
+                ..theme
+            },
+            ColorMode::Mono => Theme {
+                cross: Color::Default,
+                circle: Color::Default,
+                grid: Color::Default,
+                selection: Color::Default,
+                selection_bg: None,
+                win_bg: None,
+                dim: Color::Default,
+                accent: Color::Default,
+                error: Color::Default,
+                success: Color::Default,
+                highlight: Color::Default,
+                ..theme
+            },
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ColorMode::ALL
+            .iter()
+            .find(|m| m.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "expected full, colorblind or mono, got '{s}'"
+                ))
+            })
+    }
+}
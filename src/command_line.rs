@@ -0,0 +1,254 @@
+use crate::{
+    engine::Registry, error::Error, palette::Command, pos::Pos, slots,
+    theme::Theme,
+};
+
+/// Names and usages of the commands, which can be typed in the
+/// [`CommandLine`]. Usage of the `ai` command lists the registered engines.
+const COMMANDS: &[(&str, &str)] = &[
+    ("ai", "ai <engine|off>"),
+    ("eval", "eval"),
+    ("labels", "labels"),
+    ("load", "load [name]"),
+    ("new", "new"),
+    ("pause", "pause"),
+    ("quit", "quit"),
+    ("resize", "resize <width> <height>"),
+    ("rules", "rules"),
+    ("save", "save <name>"),
+    ("theme", "theme <name>"),
+    ("threats", "threats"),
+    ("win", "win <length>"),
+    ("zoom", "zoom"),
+];
+
+/// Command typed in the [`CommandLine`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineCommand {
+    /// Changes size of the board, the game is restarted
+    Resize(Pos),
+    /// Changes the win length, the game is restarted
+    Win(usize),
+    Theme(Box<Theme>),
+    /// Saves the game to the slot with given name
+    Save(String),
+    /// Loads the game from the slot, opens the load screen without name
+    Load(Option<String>),
+    /// Plays against the AI engine with given name, [`None`] turns it off
+    Ai(Option<String>),
+    /// Executes the palette command
+    Run(Command),
+}
+
+impl LineCommand {
+    /// Parses the command, AI is selected from the given engines
+    pub fn parse(text: &str, engines: &Registry) -> Result<Self, Error> {
+        let mut words = text.split_whitespace();
+        let Some(name) = words.next() else {
+            return Err(Error::parse("no command given"))?;
+        };
+        let args: Vec<_> = words.collect();
+
+        let cmd = match (name, args.as_slice()) {
+            ("resize", [w, h]) => {
+                let size = Pos::new(num(w)?, num(h)?);
+                if size.x < 3 || size.y < 3 {
+                    Err(Error::parse("minimum supported size is 3"))?;
+                }
+                LineCommand::Resize(size)
+            }
+            ("win", [len]) => match num(len)? {
+                len if len < 3 => {
+                    Err(Error::parse("minimum supported win length is 3"))?
+                }
+                len => LineCommand::Win(len),
+            },
+            ("theme", [theme]) => Theme::preset(theme)
+                .map(|t| LineCommand::Theme(Box::new(t)))
+                .ok_or_else(|| {
+                    Error::parse(format!(
+                        "unknown theme '{theme}', expected {}",
+                        Theme::PRESETS.join(", ")
+                    ))
+                })?,
+            ("save", [slot]) => LineCommand::Save(slot.to_string()),
+            ("load", []) => LineCommand::Load(None),
+            ("load", [slot]) => LineCommand::Load(Some(slot.to_string())),
+            ("ai", ["off"]) => LineCommand::Ai(None),
+            ("ai", [name]) => {
+                engines.find(name)?;
+                LineCommand::Ai(Some(name.to_string()))
+            }
+            ("eval", []) => LineCommand::Run(Command::EvalBar),
+            ("labels", []) => LineCommand::Run(Command::Labels),
+            ("new", []) => LineCommand::Run(Command::NewGame),
+            ("pause", []) => LineCommand::Run(Command::Pause),
+            ("quit" | "q", []) => LineCommand::Run(Command::Quit),
+            ("rules", []) => LineCommand::Run(Command::Rules),
+            ("threats", []) => LineCommand::Run(Command::Threats),
+            ("zoom", []) => LineCommand::Run(Command::Zoom),
+            _ => match COMMANDS.iter().find(|(n, _)| *n == name) {
+                Some(("ai", _)) => {
+                    let names = engines.names().join("|");
+                    Err(Error::parse(format!("usage: ai <{names}|off>")))?
+                }
+                Some((_, usage)) => {
+                    Err(Error::parse(format!("usage: {usage}")))?
+                }
+                None => {
+                    Err(Error::parse(format!("unknown command '{name}'")))?
+                }
+            },
+        };
+        Ok(cmd)
+    }
+}
+
+/// Vim-like command line opened by `:` in the status bar
+#[derive(Debug, Clone)]
+pub struct CommandLine {
+    input: String,
+    /// Options of the last completion, when it wasn't unique
+    candidates: Vec<String>,
+    /// AI engines the `ai` command selects from
+    engines: Registry,
+}
+
+impl CommandLine {
+    /// Creates empty [`CommandLine`] selecting AI from the given engines
+    pub fn new(engines: Registry) -> Self {
+        Self {
+            input: String::new(),
+            candidates: vec![],
+            engines,
+        }
+    }
+
+    /// Parses the typed command
+    pub fn command(&self) -> Result<LineCommand, Error> {
+        LineCommand::parse(&self.input, &self.engines)
+    }
+
+    /// Gets the typed command
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Adds character to the typed command
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.candidates.clear();
+    }
+
+    /// Removes the last character, returns `false` when there was none
+    pub fn pop(&mut self) -> bool {
+        self.candidates.clear();
+        self.input.pop().is_some()
+    }
+
+    /// Completes the last word to the longest prefix shared by all its
+    /// options. Options are listed, when they can't be completed further.
+    pub fn complete(&mut self) {
+        let (head, word) = match self.input.rfind(' ') {
+            Some(id) => self.input.split_at(id + 1),
+            None => ("", self.input.as_str()),
+        };
+        let prev: Vec<_> = head.split_whitespace().collect();
+        let options: Vec<String> = match prev.as_slice() {
+            [] => COMMANDS.iter().map(|(n, _)| n.to_string()).collect(),
+            ["theme"] => {
+                Theme::PRESETS.iter().map(|t| t.to_string()).collect()
+            }
+            ["ai"] => self
+                .engines
+                .names()
+                .into_iter()
+                .chain(["off"])
+                .map(|d| d.to_string())
+                .collect(),
+            ["load" | "save"] => {
+                slots::list().into_iter().map(|s| s.name).collect()
+            }
+            _ => vec![],
+        };
+        let matches: Vec<_> = options
+            .into_iter()
+            .filter(|o| o.starts_with(word))
+            .collect();
+        let Some(first) = matches.first() else {
+            return;
+        };
+
+        let len = matches.iter().fold(first.len(), |len, m| {
+            let common =
+                first.bytes().zip(m.bytes()).take_while(|(a, b)| a == b);
+            common.count().min(len)
+        });
+        let mut input = format!("{head}{}", &first[..len]);
+        if matches.len() == 1 {
+            input.push(' ');
+        }
+        self.input = input;
+        self.candidates = match matches.len() {
+            1 => vec![],
+            _ => matches,
+        };
+    }
+
+    /// Gets text shown in the status bar
+    pub fn text(&self) -> String {
+        let mut text = format!(":{}_", self.input);
+        if !self.candidates.is_empty() {
+            text.push_str("  ");
+            text.push_str(&self.candidates.join(" "));
+        }
+        text
+    }
+}
+
+/// Parses number argument of the command
+fn num(arg: &str) -> Result<usize, Error> {
+    arg.parse()
+        .map_err(|_| Error::parse(format!("expected number, got '{arg}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_completes_commands() {
+        let engines = Registry::default();
+        let parse = |text| LineCommand::parse(text, &engines);
+        assert_eq!(
+            parse("resize 10  8").unwrap(),
+            LineCommand::Resize(Pos::new(10, 8))
+        );
+        assert_eq!(
+            parse("ai hard").unwrap(),
+            LineCommand::Ai(Some("hard".to_string()))
+        );
+        assert!(parse("win 2").is_err());
+        let err = parse("resize 10").unwrap_err();
+        assert_eq!(err.to_string(), "usage: resize <width> <height>");
+        let err = parse("ai").unwrap_err().to_string();
+        assert!(err.starts_with("usage: ai <easy|medium|hard|mcts"));
+
+        let mut line = CommandLine::new(engines.clone());
+        line.push('r');
+        line.complete();
+        assert_eq!(line.input(), "r");
+        assert_eq!(line.text(), ":r_  resize rules");
+        line.push('e');
+        line.complete();
+        line.push('o');
+        line.complete();
+        assert_eq!(line.input(), "resize o");
+        assert!(line.pop());
+
+        let mut line = CommandLine::new(engines.clone());
+        "theme oc".chars().for_each(|c| line.push(c));
+        line.complete();
+        assert_eq!(line.input(), "theme ocean ");
+    }
+}
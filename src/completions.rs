@@ -0,0 +1,201 @@
+use std::{fmt::Write, str::FromStr};
+
+use crate::{
+    args::{Flag, Values, FLAGS, SUBCOMMANDS},
+    engine::Registry,
+    error::Error,
+};
+
+/// Shell the completion script is generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// All the supported shells
+    pub const ALL: &'static [Shell] = &[Shell::Bash, Shell::Zsh, Shell::Fish];
+
+    /// Gets name of the shell
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    /// Generates completion script for the shell, AI names are completed
+    /// from the given engines
+    pub fn completions(&self, engines: &Registry) -> String {
+        match self {
+            Shell::Bash => bash(engines),
+            Shell::Zsh => zsh(engines),
+            Shell::Fish => fish(engines),
+        }
+    }
+}
+
+impl FromStr for Shell {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Shell::ALL
+            .iter()
+            .find(|sh| sh.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!("expected bash, zsh or fish, got '{s}'"))
+            })
+    }
+}
+
+/// Gets values completed after the given subcommand
+fn cmd_values(cmd: &str) -> Vec<&'static str> {
+    match cmd {
+        "completions" => Shell::ALL.iter().map(|s| s.name()).collect(),
+        _ => vec![],
+    }
+}
+
+/// Generates bash completion script
+fn bash(engines: &Registry) -> String {
+    let mut res = String::from(
+        "_tictactoe() {\n    \
+         local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n    \
+         local prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    \
+         local words\n\n    \
+         case \"$prev\" in\n",
+    );
+    let all = FLAGS.iter().chain(SUBCOMMANDS.iter().flat_map(|(_, f)| *f));
+    for flag in all.filter(|f| !f.params.is_empty()) {
+        let reply = match flag.values {
+            Values::List(_) | Values::Engines => {
+                let vals = flag.values.list(engines).join(" ");
+                format!("$(compgen -W \"{vals}\" -- \"$cur\")")
+            }
+            Values::File => "$(compgen -f -- \"$cur\")".to_string(),
+            _ => String::new(),
+        };
+        _ = writeln!(
+            res,
+            "        {}) COMPREPLY=({reply}); return ;;",
+            flag.names.join("|")
+        );
+    }
+    res.push_str("    esac\n\n    case \"${COMP_WORDS[1]}\" in\n");
+    for (cmd, flags) in SUBCOMMANDS {
+        let mut words = cmd_values(cmd);
+        words.extend(flags.iter().flat_map(|f| f.names));
+        _ = writeln!(res, "        {cmd}) words=\"{}\" ;;", words.join(" "));
+    }
+
+    let mut words: Vec<_> = SUBCOMMANDS.iter().map(|(c, _)| *c).collect();
+    words.extend(FLAGS.iter().flat_map(|f| f.names));
+    _ = write!(
+        res,
+        "        *) words=\"{}\" ;;\n    esac\n    \
+         COMPREPLY=($(compgen -W \"$words\" -- \"$cur\"))\n}}\n\n\
+         complete -F _tictactoe tictactoe\n",
+        words.join(" ")
+    );
+    res
+}
+
+/// Generates zsh completion script
+fn zsh(engines: &Registry) -> String {
+    let mut res = String::from(
+        "#compdef tictactoe\n\n_tictactoe() {\n    case $words[2] in\n",
+    );
+    for (cmd, flags) in SUBCOMMANDS {
+        _ = write!(res, "        {cmd}) _arguments '1: :({cmd})'");
+        let vals = cmd_values(cmd);
+        if !vals.is_empty() {
+            _ = write!(res, " '2:: :({})'", vals.join(" "));
+        }
+        zsh_flags(&mut res, flags, engines);
+        res.push_str(" ;;\n");
+    }
+
+    let cmds: Vec<_> = SUBCOMMANDS.iter().map(|(c, _)| *c).collect();
+    _ = write!(res, "        *) _arguments '1:: :({})'", cmds.join(" "));
+    zsh_flags(&mut res, FLAGS, engines);
+    res.push_str(" ;;\n    esac\n}\n\n_tictactoe \"$@\"\n");
+    res
+}
+
+/// Writes zsh `_arguments` specs of the given flags
+fn zsh_flags(res: &mut String, flags: &[Flag], engines: &Registry) {
+    for flag in flags {
+        let action = match flag.values {
+            Values::None => String::new(),
+            Values::Any => {
+                flag.params.iter().map(|p| format!(":{p}: ")).collect()
+            }
+            Values::File => format!(":{}:_files", flag.params.join(" ")),
+            Values::List(_) | Values::Engines => {
+                let vals = flag.values.list(engines).join(" ");
+                format!(":{}:({vals})", flag.params.join(" "))
+            }
+        };
+        let help = flag.help.replace('\'', "'\\''");
+        let spec = format!("[{help}]{action}'");
+        let names = match flag.names {
+            [name] => format!("'{name}"),
+            names => {
+                format!("'({})'{{{}}}'", names.join(" "), names.join(","))
+            }
+        };
+        _ = write!(res, " \\\n            {names}{spec}");
+    }
+}
+
+/// Generates fish completion script
+fn fish(engines: &Registry) -> String {
+    let mut res = String::new();
+    let cmds: Vec<_> = SUBCOMMANDS.iter().map(|(c, _)| *c).collect();
+    let no_cmd = format!("not __fish_seen_subcommand_from {}", cmds.join(" "));
+    _ = writeln!(res, "complete -c tictactoe -f");
+    for cmd in cmds.iter() {
+        _ = writeln!(res, "complete -c tictactoe -n '{no_cmd}' -a {cmd}");
+        let vals = cmd_values(cmd);
+        if !vals.is_empty() {
+            _ = writeln!(
+                res,
+                "complete -c tictactoe -n '__fish_seen_subcommand_from {cmd}' \
+                 -a '{}'",
+                vals.join(" ")
+            );
+        }
+    }
+
+    let groups = std::iter::once((no_cmd.clone(), FLAGS)).chain(
+        SUBCOMMANDS.iter().map(|(cmd, flags)| {
+            (format!("__fish_seen_subcommand_from {cmd}"), *flags)
+        }),
+    );
+    for (cond, flags) in groups {
+        for flag in flags {
+            _ = write!(res, "complete -c tictactoe -n '{cond}'");
+            for name in flag.names {
+                match name.strip_prefix("--") {
+                    Some(long) => _ = write!(res, " -l {long}"),
+                    None => _ = write!(res, " -s {}", &name[1..]),
+                }
+            }
+            match flag.values {
+                Values::None => {}
+                Values::Any => res.push_str(" -r"),
+                Values::File => res.push_str(" -r -F"),
+                Values::List(_) | Values::Engines => {
+                    let vals = flag.values.list(engines).join(" ");
+                    _ = write!(res, " -r -a '{vals}'")
+                }
+            }
+            _ = writeln!(res, " -d '{}'", flag.help.replace('\'', "\\'"));
+        }
+    }
+    res
+}
@@ -0,0 +1,285 @@
+use std::{fmt::Display, fs, path::PathBuf, time::Duration};
+
+use crate::{
+    color_mode::ColorMode,
+    controls::{Controls, PlayerKeys},
+    error::Error,
+    marks::Marks,
+    notation::Origin,
+    paths,
+    preset::Preset,
+    sound::SoundEvent,
+    start_rule::StartRule,
+    theme::{parse_hex, Theme},
+};
+
+/// Configuration loaded from the config file
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub sound: SoundConfig,
+    pub theme: Theme,
+    pub color_mode: ColorMode,
+    pub controls: Controls,
+    /// Own keys of each player in the game at one keyboard
+    pub players: Option<PlayerKeys>,
+    pub size: Option<(usize, usize)>,
+    pub relative: bool,
+    /// Whether row and column names are shown around the board
+    pub labels: bool,
+    /// Corner the rows are numbered from
+    pub origin: Origin,
+    /// Whether the labels are right-to-left, [`None`] follows the language
+    pub rtl: Option<bool>,
+    pub marks: Marks,
+    pub start_rule: StartRule,
+    pub stats: bool,
+    pub ai: AiConfig,
+    /// Presets of the user selected by `--preset`
+    pub presets: Vec<(String, Preset)>,
+    /// Longest wait for events while something animates, [`None`] uses
+    /// the default
+    pub tick: Option<Duration>,
+}
+
+/// AI section of the [`Config`]
+#[derive(Debug, Clone)]
+pub struct AiConfig {
+    /// Randomness of the opening moves
+    pub temperature: f64,
+}
+
+/// Sound section of the [`Config`]
+#[derive(Debug, Clone)]
+pub struct SoundConfig {
+    pub enabled: bool,
+    /// Rings terminal bell for events without sound file
+    pub bell: bool,
+    pub pack: Option<PathBuf>,
+    pub volume: u8,
+    pub events: Vec<SoundEvent>,
+}
+
+/// Problem found in the config file
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub line: usize,
+    pub msg: String,
+}
+
+impl Config {
+    /// Loads config from the config file, default config is used when the
+    /// file doesn't exist. Invalid values are replaced by defaults and
+    /// returned as errors.
+    pub fn load() -> (Self, Vec<ConfigError>) {
+        let Some(path) = Self::path() else {
+            return (Self::default(), vec![]);
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                (Self::default(), vec![])
+            }
+            Err(e) => {
+                let msg = format!("cannot read config: {e}");
+                (Self::default(), vec![ConfigError { line: 0, msg }])
+            }
+        }
+    }
+
+    /// Checks whether the config file exists
+    pub fn exists() -> bool {
+        Self::path().is_none_or(|p| p.exists())
+    }
+
+    /// Gets path to the config file
+    pub fn path() -> Option<PathBuf> {
+        Some(paths::config_dir()?.join("config.ini"))
+    }
+
+    /// Parses config from the given text. Config consists of `[section]`
+    /// headers and `key = value` pairs, lines starting with `#` or `;` are
+    /// comments.
+    pub fn parse(text: &str) -> (Self, Vec<ConfigError>) {
+        let mut config = Self::default();
+        let mut errors = vec![];
+        let mut section = String::new();
+
+        for (id, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if let Some(name) =
+                line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+            {
+                section = name.trim().to_string();
+                continue;
+            }
+
+            let res = match line.split_once('=') {
+                Some((key, val)) => config
+                    .set(&section, key.trim(), val.trim())
+                    .map_err(|e| format!("{}: {e}", key.trim())),
+                None => Err("expected 'key = value'".to_string()),
+            };
+            if let Err(msg) = res {
+                errors.push(ConfigError { line: id + 1, msg });
+            }
+        }
+        (config, errors)
+    }
+
+    /// Sets config value of given key in given section
+    fn set(
+        &mut self,
+        section: &str,
+        key: &str,
+        val: &str,
+    ) -> Result<(), Error> {
+        match (section, key) {
+            ("sound", "enabled") => self.sound.enabled = parse_bool(val)?,
+            ("sound", "bell") => self.sound.bell = parse_bool(val)?,
+            ("sound", "pack") => self.sound.pack = Some(PathBuf::from(val)),
+            ("sound", "volume") => {
+                let volume = val.parse::<u8>().ok().filter(|v| *v <= 100);
+                self.sound.volume = volume.ok_or_else(|| {
+                    Error::parse(format!(
+                        "expected volume between 0 and 100, got '{val}'"
+                    ))
+                })?;
+            }
+            ("controls", "preset") => self.controls = val.parse()?,
+            ("controls", "players") => self.players = Some(val.parse()?),
+            ("board", "size") => self.size = parse_size(val)?,
+            ("board", "relative") => self.relative = parse_bool(val)?,
+            ("board", "labels") => self.labels = parse_bool(val)?,
+            ("board", "numbering") => self.origin = val.parse()?,
+            ("board", "rtl") => self.rtl = Some(parse_bool(val)?),
+            ("board", "marks") => self.marks = val.parse()?,
+            ("ai", "temperature") => {
+                let temp = val.parse::<f64>().ok().filter(|t| *t >= 0.);
+                self.ai.temperature = temp.ok_or_else(|| {
+                    Error::parse(format!(
+                        "expected non-negative number, got '{val}'"
+                    ))
+                })?;
+            }
+            ("game", "start") => self.start_rule = val.parse()?,
+            ("game", "tick") => {
+                let tick = val
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|t| (10..=1000).contains(t))
+                    .ok_or_else(|| {
+                        Error::parse(format!(
+                            "expected milliseconds between 10 and 1000, got \
+                             '{val}'"
+                        ))
+                    })?;
+                self.tick = Some(Duration::from_millis(tick));
+            }
+            ("stats", "enabled") => self.stats = parse_bool(val)?,
+            ("presets", name) => {
+                let preset = val.parse()?;
+                self.presets.retain(|(n, _)| n != name);
+                self.presets.push((name.to_string(), preset));
+            }
+            ("theme", "mode") => self.color_mode = val.parse()?,
+            ("theme", "win-style") => self.theme.win_style = val.parse()?,
+            ("theme", "scheme") => self.theme = Theme::load(val)?,
+            ("theme", "preset") => {
+                self.theme = Theme::preset(val).ok_or_else(|| {
+                    Error::parse(format!("unknown theme preset '{val}'"))
+                })?;
+            }
+            ("theme", key) => {
+                let color = parse_hex(val).ok_or_else(|| {
+                    Error::parse(format!("expected hex color, got '{val}'"))
+                })?;
+                match key {
+                    "cross" => self.theme.cross = color,
+                    "circle" => self.theme.circle = color,
+                    "grid" => self.theme.grid = color,
+                    "selection" => self.theme.selection = color,
+                    "selection-bg" => self.theme.selection_bg = Some(color),
+                    "win-bg" => self.theme.win_bg = Some(color),
+                    _ => return Err(Error::parse("unknown key"))?,
+                }
+            }
+            ("sound", key) => {
+                let Some(event) =
+                    SoundEvent::ALL.iter().find(|e| e.name() == key)
+                else {
+                    return Err(Error::parse("unknown key"))?;
+                };
+                self.sound.events.retain(|e| e != event);
+                if parse_bool(val)? {
+                    self.sound.events.push(*event);
+                }
+            }
+            ("", _) => return Err(Error::parse("unknown key"))?,
+            _ => {
+                return Err(Error::parse(format!(
+                    "unknown section [{section}]"
+                )))?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            0 => write!(f, "{}", self.msg),
+            line => write!(f, "line {line}: {}", self.msg),
+        }
+    }
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self { temperature: 0.5 }
+    }
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bell: false,
+            pack: None,
+            volume: 100,
+            events: SoundEvent::ALL.to_vec(),
+        }
+    }
+}
+
+/// Parses bool config value
+fn parse_bool(val: &str) -> Result<bool, Error> {
+    match val {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => {
+            Err(Error::parse(format!("expected true or false, got '{val}'")))?
+        }
+    }
+}
+
+/// Parses board size config value (`auto` or `<width>x<height>`)
+fn parse_size(val: &str) -> Result<Option<(usize, usize)>, Error> {
+    if val == "auto" {
+        return Ok(None);
+    }
+    val.split_once('x')
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+        .filter(|(w, h)| *w >= 3 && *h >= 3)
+        .map(Some)
+        .ok_or_else(|| {
+            Error::parse(format!(
+                "expected auto or size like 7x7, got '{val}'"
+            ))
+        })
+}
@@ -0,0 +1,60 @@
+use std::cmp::max;
+
+use termint::{
+    buffer::Buffer,
+    geometry::{Constraint, Coords},
+    widgets::{Block, StrSpanExtension, Widget},
+};
+
+use crate::theme::Theme;
+
+/// Dialog asking to confirm an action, rendered over the game
+#[derive(Debug, Clone)]
+pub struct Confirm {
+    title: &'static str,
+    msg: &'static str,
+    keys: &'static str,
+    theme: Theme,
+}
+
+impl Confirm {
+    /// Creates new [`Confirm`] with given title, message and key hints
+    pub fn new(
+        title: &'static str,
+        msg: &'static str,
+        keys: &'static str,
+        theme: Theme,
+    ) -> Self {
+        Self {
+            title,
+            msg,
+            keys,
+            theme,
+        }
+    }
+}
+
+impl Widget for Confirm {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut block = Block::vertical()
+            .title(self.title.fg(self.theme.accent))
+            .border_color(self.theme.dim);
+        block.add_child(self.msg, Constraint::Length(1));
+        block.add_child(self.keys.fg(self.theme.dim), Constraint::Length(1));
+        block.render(buffer);
+    }
+
+    fn height(&self, _size: &Coords) -> usize {
+        4
+    }
+
+    fn width(&self, _size: &Coords) -> usize {
+        max(self.msg.len(), self.keys.len()) + 2
+    }
+}
+
+impl From<Confirm> for Box<dyn Widget> {
+    fn from(value: Confirm) -> Self {
+        Box::new(value)
+    }
+}
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    board::Board,
+    error::Error,
+    input::Action,
+    locale::{tr, trf},
+};
+
+/// Keys used for moving the selection, arrows work with every preset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Controls {
+    #[default]
+    Vim,
+    Wasd,
+    Arrows,
+}
+
+impl Controls {
+    /// All the control presets
+    pub const ALL: &'static [Controls] =
+        &[Controls::Vim, Controls::Wasd, Controls::Arrows];
+
+    /// Gets name of the preset
+    pub fn name(&self) -> &'static str {
+        match self {
+            Controls::Vim => "vim",
+            Controls::Wasd => "wasd",
+            Controls::Arrows => "arrows",
+        }
+    }
+
+    /// Gets movement action, which is bound to the given key
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        let moves = [
+            Action::MoveUp,
+            Action::MoveDown,
+            Action::MoveRight,
+            Action::MoveLeft,
+        ];
+        let keys = match self {
+            Controls::Vim => "kjlh",
+            Controls::Wasd => "wsda",
+            Controls::Arrows => "",
+        };
+        match code {
+            KeyCode::Up => Some(Action::MoveUp),
+            KeyCode::Down => Some(Action::MoveDown),
+            KeyCode::Right => Some(Action::MoveRight),
+            KeyCode::Left => Some(Action::MoveLeft),
+            KeyCode::Char(c) => keys.find(c).map(|id| moves[id]),
+            _ => None,
+        }
+    }
+
+    /// Gets function moving the selection, which is bound to the given key
+    pub fn movement(&self, code: KeyCode) -> Option<fn(&mut Board)> {
+        self.action(code)?.movement()
+    }
+
+    /// Gets key placing the mark, when players have their own keys
+    pub fn place_key(&self) -> KeyCode {
+        match self {
+            Controls::Wasd => KeyCode::Char(' '),
+            Controls::Vim | Controls::Arrows => KeyCode::Enter,
+        }
+    }
+
+    /// Gets action bound to the given key of the player's own keys, which
+    /// is movement or placing. Arrows belong only to the arrows preset.
+    pub fn own_action(&self, code: KeyCode) -> Option<Action> {
+        match (self, code) {
+            _ if code == self.place_key() => Some(Action::Place),
+            (Controls::Arrows, _) | (_, KeyCode::Char(_)) => self.action(code),
+            _ => None,
+        }
+    }
+
+    /// Gets help text of the movement keys
+    pub fn help(&self) -> String {
+        let keys = match self {
+            Controls::Vim => "Arrows/hjkl",
+            Controls::Wasd => "Arrows/wasd",
+            Controls::Arrows => "Arrows",
+        };
+        format!("[{keys}]{}", tr("help.move"))
+    }
+}
+
+/// Own keys of the first and the second player sharing one keyboard, so
+/// they don't have to pass the same keys to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerKeys(pub [Controls; 2]);
+
+impl PlayerKeys {
+    /// Gets index of the player, whose keys contain the given key
+    pub fn owner(&self, code: KeyCode) -> Option<usize> {
+        self.0.iter().position(|c| c.own_action(code).is_some())
+    }
+
+    /// Gets help text of the keys of both players
+    pub fn help(&self) -> [String; 2] {
+        let mut id = 0;
+        self.0.map(|c| {
+            id += 1;
+            let keys = match c {
+                Controls::Vim => "hjkl/Enter",
+                Controls::Wasd => "wasd/Space",
+                Controls::Arrows => "Arrows/Enter",
+            };
+            format!("[{keys}]{}", trf("help.player", &[&id]))
+        })
+    }
+}
+
+impl FromStr for PlayerKeys {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((first, second)) = s.split_once(',') else {
+            return Err(Error::parse(format!(
+                "expected two presets, got '{s}'"
+            )))?;
+        };
+        let keys: [Controls; 2] =
+            [first.trim().parse()?, second.trim().parse()?];
+        if keys[0].place_key() == keys[1].place_key() {
+            Err(Error::parse(
+                "players need different keys, combine wasd with vim or arrows",
+            ))?;
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl FromStr for Controls {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Controls::ALL
+            .iter()
+            .find(|c| c.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "expected vim, wasd or arrows, got '{s}'"
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_keys_are_split() {
+        let keys: PlayerKeys = "wasd, arrows".parse().unwrap();
+        assert_eq!(keys.owner(KeyCode::Char('w')), Some(0));
+        assert_eq!(keys.owner(KeyCode::Char(' ')), Some(0));
+        assert_eq!(keys.owner(KeyCode::Up), Some(1));
+        assert_eq!(keys.owner(KeyCode::Enter), Some(1));
+        assert_eq!(keys.owner(KeyCode::Char('r')), None);
+        assert!("vim, arrows".parse::<PlayerKeys>().is_err());
+        assert!("wasd".parse::<PlayerKeys>().is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{board::Board, cell::Cell, pos::Pos, rng::Rng};
+
+/// Number of seconds in a day
+pub const DAY: u64 = 24 * 60 * 60;
+
+/// Daily challenge, board generated from the date, so it's the same for
+/// everyone on the given day
+#[derive(Debug, Clone)]
+pub struct Daily {
+    /// Days since the Unix epoch
+    pub day: u64,
+    pub size: Pos,
+    pub win_len: usize,
+    pub blocks: usize,
+    /// Number of marks of each player placed before the start
+    pub marks: usize,
+    /// Generator placing the blocks and the marks
+    rng: Rng,
+}
+
+impl Daily {
+    /// Creates [`Daily`] challenge of the current day (UTC)
+    pub fn today() -> Self {
+        Self::new(current_day())
+    }
+
+    /// Creates [`Daily`] challenge of the given day since the Unix epoch
+    pub fn new(day: u64) -> Self {
+        let mut rng = Rng::new(day);
+        let size = Pos::new(5 + rng.range(3), 5 + rng.range(3));
+        let blocks = 2 + rng.range(4);
+        let marks = 1 + rng.range(3);
+        Self {
+            day,
+            size,
+            win_len: 4,
+            blocks,
+            marks,
+            rng,
+        }
+    }
+
+    /// Checks whether it's the challenge of the current day
+    pub fn is_today(&self) -> bool {
+        self.day == current_day()
+    }
+
+    /// Places the blocks and the marks of both players on the restarted
+    /// board of the challenge size
+    pub fn setup(&self, board: &mut Board) {
+        let mut rng = self.rng.clone();
+        board.add_blocks(self.blocks, &mut rng);
+        // Handicap count includes the marks placed before
+        board.add_handicap(Cell::Cross, self.marks, &mut rng);
+        board.add_handicap(Cell::Circle, self.marks * 2, &mut rng);
+    }
+
+    /// Gets date of the challenge in the YYYY-MM-DD format
+    pub fn date(&self) -> String {
+        date(self.day)
+    }
+}
+
+/// Gets number of days since the Unix epoch (UTC)
+pub fn current_day() -> u64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    secs / DAY
+}
+
+/// Gets date of the given day since the Unix epoch in the YYYY-MM-DD format
+pub fn date(day: u64) -> String {
+    // Converts days to the civil date, March is the first month of the
+    // shifted year, so the leap day is at its end
+    let z = day + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + u64::from(m <= 2);
+    format!("{y}-{m:02}-{d:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_of_day() {
+        assert_eq!(Daily::new(0).date(), "1970-01-01");
+        assert_eq!(Daily::new(11_016).date(), "2000-02-29");
+        assert_eq!(Daily::new(20_741).date(), "2026-10-15");
+    }
+
+    #[test]
+    fn same_day_same_board() {
+        let boards = [Daily::new(20_741), Daily::new(20_741)].map(|daily| {
+            let mut board =
+                Board::new(daily.size.x, daily.size.y, daily.win_len);
+            daily.setup(&mut board);
+            board
+        });
+        assert_eq!(boards[0].cells, boards[1].cells);
+        assert_eq!(boards[0].handicap.len(), Daily::new(20_741).marks * 2);
+    }
+}
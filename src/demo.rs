@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    board::Board, cell::Cell, engine::Engine, error::Error, pos::Pos,
+    thinking::Thinking,
+};
+
+/// Default delay between the demo moves
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+
+/// Demo mode, where two AI players play against each other, also used by
+/// the computer opponent
+#[derive(Debug)]
+pub struct Demo {
+    /// Engine playing the moves, [`None`] while it's thinking
+    engine: Option<Box<dyn Engine>>,
+    thinking: Option<Thinking>,
+    pub delay: Duration,
+    last: Instant,
+}
+
+impl Demo {
+    /// Creates new [`Demo`] with given engine and delay between moves
+    pub fn new(engine: Box<dyn Engine>, delay: Option<Duration>) -> Self {
+        Self {
+            engine: Some(engine),
+            thinking: None,
+            delay: delay.unwrap_or(DEFAULT_DELAY),
+            last: Instant::now(),
+        }
+    }
+
+    /// Checks whether the AI should start thinking about the next move
+    pub fn ready(&self) -> bool {
+        self.engine.is_some() && self.last.elapsed() >= self.delay
+    }
+
+    /// Starts thinking about the move of the given player on a background
+    /// thread
+    pub fn think(&mut self, board: &Board, player: Cell) {
+        if let Some(engine) = self.engine.take() {
+            self.thinking =
+                Some(Thinking::start(engine, board.clone(), player));
+        }
+    }
+
+    /// Gets the move when the engine finished thinking and restarts the
+    /// timer
+    pub fn result(&mut self) -> Option<Result<Option<Pos>, Error>> {
+        let (engine, pos) = self.thinking.as_ref()?.result()?;
+        self.engine = Some(engine);
+        self.thinking = None;
+        self.last = Instant::now();
+        Some(pos)
+    }
+
+    /// Gets name of the engine, [`None`] while it's thinking
+    pub fn name(&self) -> Option<&str> {
+        self.engine.as_ref().map(|e| e.name())
+    }
+
+    /// Gets the spinner frame, when the AI is thinking
+    pub fn spinner(&self) -> Option<char> {
+        self.thinking.as_ref().map(|t| t.spinner())
+    }
+
+    /// Gets time remaining until the next move or the next spinner frame
+    pub fn remaining(&self) -> Duration {
+        match &self.thinking {
+            Some(thinking) => thinking.remaining(),
+            None => self.delay.saturating_sub(self.last.elapsed()),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{fmt::Debug, time::Duration};
+
+use crate::{
+    ai::{Ai, Difficulty},
+    board::Board,
+    bot::Bot,
+    cell::Cell,
+    error::Error,
+    pos::Pos,
+    table,
+};
+
+/// Name of the engine used when none is selected
+pub const DEFAULT: &str = "medium";
+
+/// Computer player choosing the moves, like the built-in [`Ai`] or an
+/// external [`Bot`]
+pub trait Engine: Debug + Send {
+    /// Gets name of the player shown in the game records
+    fn name(&self) -> &str;
+
+    /// Chooses move of the given player on the board, [`None`] when the
+    /// game ended
+    fn choose_move(
+        &mut self,
+        board: &Board,
+        me: Cell,
+    ) -> Result<Option<Pos>, Error>;
+}
+
+impl Engine for Ai {
+    fn name(&self) -> &str {
+        "AI"
+    }
+
+    fn choose_move(
+        &mut self,
+        board: &Board,
+        me: Cell,
+    ) -> Result<Option<Pos>, Error> {
+        Ok(self.best_move(board, me))
+    }
+}
+
+impl Engine for Bot {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose_move(
+        &mut self,
+        board: &Board,
+        me: Cell,
+    ) -> Result<Option<Pos>, Error> {
+        match board.state() {
+            Some(_) => Ok(None),
+            None => self.best_move(board, me).map(Some),
+        }
+    }
+}
+
+/// Settings the engines are created with
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub seed: u64,
+    /// Randomness of the opening moves, 0 means always the best move
+    pub temperature: f64,
+    /// Time per move, engine chooses its default when not set
+    pub time: Option<Duration>,
+    /// Size of the transposition table in MB
+    pub table_size: usize,
+}
+
+impl Options {
+    /// Creates [`Ai`] of the given difficulty with these options
+    pub fn ai(&self, difficulty: Difficulty) -> Ai {
+        Ai::new(difficulty)
+            .seed(self.seed)
+            .temperature(self.temperature)
+            .time(self.time)
+            .table_size(self.table_size)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            temperature: 0.,
+            time: None,
+            table_size: table::DEFAULT_SIZE,
+        }
+    }
+}
+
+/// Creates the engine with the given options
+pub type Factory = fn(&Options) -> Box<dyn Engine>;
+
+/// Engines selectable by their name
+#[derive(Debug, Clone)]
+pub struct Registry {
+    engines: Vec<(&'static str, Factory)>,
+}
+
+impl Registry {
+    /// Creates empty [`Registry`]
+    pub fn new() -> Self {
+        Self { engines: vec![] }
+    }
+
+    /// Adds engine with the given name, engine of the same name is
+    /// replaced
+    pub fn register(mut self, name: &'static str, factory: Factory) -> Self {
+        self.engines.retain(|(n, _)| *n != name);
+        self.engines.push((name, factory));
+        self
+    }
+
+    /// Gets names of the engines in the order they were added
+    pub fn names(&self) -> Vec<&'static str> {
+        self.engines.iter().map(|(n, _)| *n).collect()
+    }
+
+    /// Gets factory of the engine with given name
+    pub fn find(&self, name: &str) -> Result<Factory, Error> {
+        self.engines
+            .iter()
+            .find_map(|(n, f)| (*n == name).then_some(*f))
+            .ok_or_else(|| {
+                let names = self.names().join(", ");
+                Error::parse(format!(
+                    "unknown AI '{name}', expected one of: {names}"
+                ))
+            })
+    }
+
+    /// Creates engine with given name and options
+    pub fn create(
+        &self,
+        name: &str,
+        options: &Options,
+    ) -> Result<Box<dyn Engine>, Error> {
+        Ok(self.find(name)?(options))
+    }
+}
+
+impl Default for Registry {
+    /// Creates [`Registry`] with the built-in AI difficulties
+    fn default() -> Self {
+        Self::new()
+            .register("easy", |o| Box::new(o.ai(Difficulty::Easy)))
+            .register("medium", |o| Box::new(o.ai(Difficulty::Medium)))
+            .register("hard", |o| Box::new(o.ai(Difficulty::Hard)))
+            .register("mcts", |o| Box::new(o.ai(Difficulty::Mcts)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Engine always playing the first empty cell
+    #[derive(Debug)]
+    struct First;
+
+    impl Engine for First {
+        fn name(&self) -> &str {
+            "first"
+        }
+
+        fn choose_move(
+            &mut self,
+            board: &Board,
+            _: Cell,
+        ) -> Result<Option<Pos>, Error> {
+            let id = board.cells.iter().position(|c| *c == Cell::Empty);
+            Ok(id.map(|id| Pos::from_index(id, board.size.x)))
+        }
+    }
+
+    #[test]
+    fn registered_engine_is_created_by_name() {
+        let registry = Registry::default()
+            .register("first", |_| Box::new(First))
+            .register("easy", |_| Box::new(First));
+        assert_eq!(
+            registry.names(),
+            ["medium", "hard", "mcts", "first", "easy"]
+        );
+
+        let options = Options::default();
+        let mut engine = registry.create("easy", &options).unwrap();
+        assert_eq!(engine.name(), "first");
+        let mut board = Board::new(3, 3, 3);
+        board.cells.set(0, Cell::Cross);
+        let pos = engine.choose_move(&board, Cell::Circle).unwrap();
+        assert_eq!(pos, Some(Pos::new(1, 0)));
+
+        let err = registry.find("random").unwrap_err();
+        assert!(err.to_string().starts_with("unknown AI 'random'"));
+    }
+}
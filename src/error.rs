@@ -1,27 +1,111 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug)]
 pub enum Error {
-    IO(std::io::Error),
-    Msg(String),
+    IO(io::Error),
+    /// Move breaking the rules, with the reason
+    InvalidMove(String),
+    /// Move played after the game ended
+    GameEnded,
+    /// Invalid command line argument, setting or typed command. Argument
+    /// is [`None`] when it isn't known yet or when the arguments can't be
+    /// combined.
+    ArgParse {
+        arg: Option<String>,
+        msg: String,
+    },
+    /// Failed network operation of the engine server
+    Network {
+        source: io::Error,
+    },
+    /// Message of the engine protocol or the bot, which can't be
+    /// understood
+    Protocol(String),
+    /// File couldn't be saved
+    Save {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// File couldn't be read or its content is invalid
+    Load {
+        path: PathBuf,
+        msg: String,
+    },
+    /// Action isn't available in the current game
+    Unavailable(String),
+    /// Given number of benchmarks is slower than the baseline
+    Regressed(usize),
     Exit,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(value: std::io::Error) -> Self {
-        Self::IO(value)
+impl Error {
+    /// Creates [`Error::Save`] of the given path
+    pub fn save<P: AsRef<Path>>(path: P, source: io::Error) -> Self {
+        Self::Save {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
+
+    /// Creates [`Error::Load`] of the given path
+    pub fn load<P: AsRef<Path>>(path: P, msg: impl Display) -> Self {
+        Self::Load {
+            path: path.as_ref().to_path_buf(),
+            msg: msg.to_string(),
+        }
     }
-}
 
-impl From<&str> for Error {
-    fn from(value: &str) -> Self {
-        Self::Msg(value.to_string())
+    /// Creates [`Error::ArgParse`] of the value, which isn't known to
+    /// belong to an argument yet
+    pub fn parse(msg: impl Into<String>) -> Self {
+        Self::ArgParse {
+            arg: None,
+            msg: msg.into(),
+        }
+    }
+
+    /// Creates [`Error::IO`] of the app directory, which can't be found
+    pub fn no_dir(name: &str) -> Self {
+        let msg = format!("cannot find {name} directory");
+        Self::IO(io::Error::new(io::ErrorKind::NotFound, msg))
+    }
+
+    /// Sets argument of [`Error::ArgParse`], which doesn't know its
+    /// argument yet, other errors are kept
+    pub fn arg(self, arg: Option<&str>) -> Self {
+        match self {
+            Error::ArgParse { arg: None, msg } => Error::ArgParse {
+                arg: arg.map(String::from),
+                msg,
+            },
+            e => e,
+        }
+    }
+
+    /// Gets exit code of the process ending with this error, so scripts
+    /// can tell the categories apart
+    pub fn code(&self) -> i32 {
+        match self {
+            Error::Exit => 0,
+            Error::IO(_) => 1,
+            Error::ArgParse { .. } => 2,
+            Error::InvalidMove(_) | Error::GameEnded => 3,
+            Error::Network { .. } | Error::Protocol(_) => 4,
+            Error::Save { .. } => 5,
+            Error::Load { .. } => 6,
+            Error::Unavailable(_) => 7,
+            Error::Regressed(_) => 8,
+        }
     }
 }
 
-impl From<String> for Error {
-    fn from(value: String) -> Self {
-        Self::Msg(value)
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::IO(value)
     }
 }
 
@@ -29,8 +113,36 @@ impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::IO(e) => write!(f, "{e}"),
-            Error::Msg(msg) => write!(f, "{msg}"),
+            Error::InvalidMove(msg)
+            | Error::Protocol(msg)
+            | Error::Unavailable(msg) => write!(f, "{msg}"),
+            Error::GameEnded => write!(f, "game ended"),
+            Error::ArgParse { arg, msg } => match arg {
+                Some(arg) => write!(f, "{arg}: {msg}"),
+                None => write!(f, "{msg}"),
+            },
+            Error::Network { source } => write!(f, "network error: {source}"),
+            Error::Save { path, source } => {
+                write!(f, "cannot save '{}': {source}", path.display())
+            }
+            Error::Load { path, msg } => {
+                write!(f, "cannot load '{}': {msg}", path.display())
+            }
+            Error::Regressed(count) => {
+                write!(f, "{count} benchmark(s) regressed")
+            }
             Error::Exit => write!(f, "exit"),
         }
     }
 }
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(source)
+            | Error::Network { source }
+            | Error::Save { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
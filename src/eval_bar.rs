@@ -0,0 +1,197 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use termint::{
+    buffer::Buffer,
+    geometry::{Coords, Rect},
+    style::Style,
+    widgets::Widget,
+};
+
+use crate::{
+    ai::{Ai, Difficulty, Eval},
+    board::Board,
+    cell::Cell,
+    theme::Theme,
+};
+
+/// Width of the eval bar including the gap before the boards
+pub const WIDTH: usize = 2;
+/// Score, at which the bar is filled by three quarters
+const SCALE: f64 = 256.;
+
+/// Engine's evaluation of the current position, which is computed on a
+/// background thread after each move
+#[derive(Debug, Default)]
+pub struct EvalBar {
+    /// Evaluation from the cross' point of view, [`None`] when the
+    /// position can't be evaluated
+    eval: Option<Eval>,
+    /// Cells and the player on turn of the last evaluated position
+    position: Option<(Vec<Cell>, Cell)>,
+    /// Engine, which is moved to the search thread while evaluating
+    ai: Option<Ai>,
+    rx: Option<Receiver<(Ai, Eval)>>,
+}
+
+impl EvalBar {
+    /// Starts evaluating the position, when it changed since the last
+    /// evaluation, and stores the finished evaluation. Returns whether the
+    /// bar changed.
+    pub fn update(&mut self, board: &Board, player: Cell) -> bool {
+        let mut changed = false;
+        if let Some((ai, eval)) =
+            self.rx.as_ref().and_then(|r| r.try_recv().ok())
+        {
+            self.ai = Some(ai);
+            self.eval = Some(eval);
+            self.rx = None;
+            changed = true;
+        }
+
+        let position = (board.cells.to_vec(), player);
+        if self.rx.is_some() || self.position.as_ref() == Some(&position) {
+            return changed;
+        }
+        self.position = Some(position);
+        if !EvalBar::supported(board) {
+            return self.eval.take().is_some() || changed;
+        }
+
+        let mut ai =
+            self.ai.take().unwrap_or_else(|| Ai::new(Difficulty::Hard));
+        let board = board.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let eval = ai.evaluate(&board, player);
+            let eval = match player {
+                Cell::Circle => eval.flip(),
+                _ => eval,
+            };
+            _ = tx.send((ai, eval));
+        });
+        self.rx = Some(rx);
+        changed
+    }
+
+    /// Checks whether the position is being evaluated
+    pub fn evaluating(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    /// Checks whether the position on the board can be evaluated. Engine
+    /// can't search the variants and would reveal cells hidden by the fog.
+    pub fn supported(board: &Board) -> bool {
+        board.standard() && board.fog.is_none()
+    }
+
+    /// Gets the gauge showing the evaluation next to the given boards
+    pub fn gauge(&self, theme: Theme, boards: Box<dyn Widget>) -> Gauge {
+        let share = self.eval.map(|eval| match eval {
+            Eval::Win => 1.,
+            Eval::Loss => 0.,
+            Eval::Score(score) => {
+                let score = score as f64;
+                0.5 + 0.5 * score / (score.abs() + SCALE)
+            }
+        });
+        Gauge {
+            share,
+            theme,
+            boards,
+        }
+    }
+}
+
+/// Vertical bar on the left of the boards filled by cross from the top and
+/// by circle from the bottom based on which of them is favored
+#[derive(Debug)]
+pub struct Gauge {
+    /// Part of the bar belonging to cross, [`None`] without evaluation
+    share: Option<f64>,
+    theme: Theme,
+    boards: Box<dyn Widget>,
+}
+
+impl Widget for Gauge {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut boards = Buffer::empty(Rect::new(
+            buffer.x() + WIDTH,
+            buffer.y(),
+            buffer.width().saturating_sub(WIDTH),
+            buffer.height(),
+        ));
+        self.boards.render(&mut boards);
+        buffer.union(boards);
+
+        let size = Coords::new(buffer.width(), buffer.height());
+        let height = self.boards.height(&size).min(buffer.height());
+        let Some(share) = self.share else {
+            let style = Style::new().fg(self.theme.dim);
+            for y in 0..height {
+                let pos = Coords::new(buffer.x(), buffer.y() + y);
+                buffer.set_str_styled("│", &pos, style);
+            }
+            return;
+        };
+
+        let cross = (share * height as f64).round() as usize;
+        for y in 0..height {
+            // Parts differ in symbol too, so they are told apart without
+            // colors
+            let (cell, symbol) = match y < cross {
+                true => (Cell::Cross, "█"),
+                false => (Cell::Circle, "░"),
+            };
+            let style = Style::new().fg(self.theme.cell(cell));
+            let pos = Coords::new(buffer.x(), buffer.y() + y);
+            buffer.set_str_styled(symbol, &pos, style);
+        }
+    }
+
+    fn height(&self, size: &Coords) -> usize {
+        self.boards.height(size)
+    }
+
+    fn width(&self, size: &Coords) -> usize {
+        self.boards.width(size) + WIDTH
+    }
+}
+
+impl From<Gauge> for Box<dyn Widget> {
+    fn from(value: Gauge) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_for_cross() {
+        let mut board = Board::new(3, 3, 3);
+        for (cell, x, y) in [
+            (Cell::Cross, 0, 0),
+            (Cell::Circle, 0, 1),
+            (Cell::Cross, 1, 0),
+            (Cell::Circle, 1, 1),
+        ] {
+            board.set(cell, x, y).unwrap();
+        }
+
+        // Both players win on their turn, so the bar depends on the turn
+        for (player, eval) in
+            [(Cell::Cross, Eval::Win), (Cell::Circle, Eval::Loss)]
+        {
+            let mut bar = EvalBar::default();
+            bar.update(&board, player);
+            while bar.evaluating() {
+                bar.update(&board, player);
+            }
+            assert_eq!(bar.eval, Some(eval));
+        }
+    }
+}
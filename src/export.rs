@@ -0,0 +1,381 @@
+use std::{fmt::Write, fs, path::Path};
+
+use termint::enums::Color;
+
+use crate::{board::Board, cell::Cell, error::Error, rules::Rules};
+
+/// Size of a single cell in pixels
+const CELL: f64 = 40.;
+/// Size of the margin around the board in pixels
+const MARGIN: f64 = 8.;
+/// Background color of the image
+const BACKGROUND: Rgb = Rgb(0x1d, 0x1f, 0x21);
+
+/// Color in RGB format
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rgb(u8, u8, u8);
+
+/// Shape the exported image consists of
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    Line {
+        from: (f64, f64),
+        to: (f64, f64),
+        width: f64,
+        color: Rgb,
+    },
+    Ring {
+        center: (f64, f64),
+        radius: f64,
+        width: f64,
+        color: Rgb,
+    },
+    Rect {
+        pos: (f64, f64),
+        size: f64,
+        color: Rgb,
+    },
+}
+
+/// Exports the board as an image to the given path. PNG is used when path
+/// has `png` extension, SVG otherwise. Summary of the rules is embedded in
+/// the image.
+pub fn export<P: AsRef<Path>>(
+    board: &Board,
+    rules: &Rules,
+    path: P,
+) -> Result<(), Error> {
+    let path = path.as_ref();
+    let summary = rules.summary();
+    let res = match path.extension().is_some_and(|e| e == "png") {
+        true => fs::write(path, png(board, &summary)),
+        false => fs::write(path, svg(board, &summary)),
+    };
+    res.map_err(|e| Error::save(path, e))?;
+    Ok(())
+}
+
+/// Renders the board as an SVG image
+fn svg(board: &Board, rules: &str) -> String {
+    let (w, h) = image_size(board);
+    let mut res = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" \
+         height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+         <desc>tictactoe {rules}</desc>\n\
+         <rect width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+        hex(BACKGROUND)
+    );
+    for shape in shapes(board) {
+        _ = match shape {
+            Shape::Line {
+                from,
+                to,
+                width,
+                color,
+            } => writeln!(
+                res,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" \
+                 stroke=\"{}\" stroke-width=\"{width}\" \
+                 stroke-linecap=\"round\"/>",
+                from.0,
+                from.1,
+                to.0,
+                to.1,
+                hex(color)
+            ),
+            Shape::Ring {
+                center,
+                radius,
+                width,
+                color,
+            } => writeln!(
+                res,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" fill=\"none\" \
+                 stroke=\"{}\" stroke-width=\"{width}\"/>",
+                center.0,
+                center.1,
+                hex(color)
+            ),
+            Shape::Rect { pos, size, color } => writeln!(
+                res,
+                "<rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\" \
+                 fill=\"{}\"/>",
+                pos.0,
+                pos.1,
+                hex(color)
+            ),
+        };
+    }
+    res.push_str("</svg>\n");
+    res
+}
+
+/// Renders the board as a PNG image
+fn png(board: &Board, rules: &str) -> Vec<u8> {
+    let (w, h) = image_size(board);
+    let (w, h) = (w as usize, h as usize);
+    let mut pixels = [BACKGROUND.0, BACKGROUND.1, BACKGROUND.2].repeat(w * h);
+    for shape in shapes(board) {
+        raster(&mut pixels, w, h, shape);
+    }
+
+    // Each row starts with filter type byte (0 - no filter)
+    let mut raw = Vec::with_capacity((w * 3 + 1) * h);
+    for row in pixels.chunks(w * 3) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut ihdr = vec![];
+    ihdr.extend_from_slice(&(w as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(h as u32).to_be_bytes());
+    // 8 bit depth, RGB, default compression, filter and no interlace
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut res = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut res, b"IHDR", &ihdr);
+    // Text chunk consists of keyword, null separator and the text
+    png_chunk(
+        &mut res,
+        b"tEXt",
+        format!("Rules\0tictactoe {rules}").as_bytes(),
+    );
+    png_chunk(&mut res, b"IDAT", &zlib_stored(&raw));
+    png_chunk(&mut res, b"IEND", &[]);
+    res
+}
+
+/// Gets size of the image in pixels
+fn image_size(board: &Board) -> (f64, f64) {
+    (
+        board.size.x as f64 * CELL + MARGIN * 2.,
+        board.size.y as f64 * CELL + MARGIN * 2.,
+    )
+}
+
+/// Gets shapes representing the board
+fn shapes(board: &Board) -> Vec<Shape> {
+    let grid = rgb(board.theme.grid, Rgb(0x80, 0x80, 0x80));
+    let cross = rgb(board.theme.cross, Rgb(0x4c, 0xaf, 0x50));
+    let circle = rgb(board.theme.circle, Rgb(0xf4, 0x43, 0x36));
+    let (w, h) = (board.size.x as f64, board.size.y as f64);
+
+    let mut res = vec![];
+    for x in 0..=board.size.x {
+        let x = MARGIN + x as f64 * CELL;
+        res.push(line((x, MARGIN), (x, MARGIN + h * CELL), 2., grid));
+    }
+    for y in 0..=board.size.y {
+        let y = MARGIN + y as f64 * CELL;
+        res.push(line((MARGIN, y), (MARGIN + w * CELL, y), 2., grid));
+    }
+
+    let pad = CELL * 0.25;
+    for (id, cell) in board.cells.iter().enumerate() {
+        let x = MARGIN + (id % board.size.x) as f64 * CELL;
+        let y = MARGIN + (id / board.size.x) as f64 * CELL;
+        match cell {
+            Cell::Cross => {
+                let (l, r) = (x + pad, x + CELL - pad);
+                let (t, b) = (y + pad, y + CELL - pad);
+                res.push(line((l, t), (r, b), 4., cross));
+                res.push(line((r, t), (l, b), 4., cross));
+            }
+            Cell::Circle => res.push(Shape::Ring {
+                center: (x + CELL / 2., y + CELL / 2.),
+                radius: CELL / 2. - pad,
+                width: 4.,
+                color: circle,
+            }),
+            Cell::Blocked => res.push(Shape::Rect {
+                pos: (x + 4., y + 4.),
+                size: CELL - 8.,
+                color: grid,
+            }),
+            Cell::Empty => {}
+        }
+    }
+
+    for (pos, (dx, dy)) in board.win.iter().copied() {
+        let len = board.win_len.get((dx, dy)) as isize - 1;
+        let end = (pos.x as isize + dx * len, pos.y as isize + dy * len);
+        let center = |x: f64, y: f64| {
+            (MARGIN + (x + 0.5) * CELL, MARGIN + (y + 0.5) * CELL)
+        };
+        let color = match board.cells[pos.index(board.size.x)] {
+            Cell::Circle => circle,
+            _ => cross,
+        };
+        res.push(line(
+            center(pos.x as f64, pos.y as f64),
+            center(end.0 as f64, end.1 as f64),
+            6.,
+            color,
+        ));
+    }
+    res
+}
+
+/// Creates line shape
+fn line(from: (f64, f64), to: (f64, f64), width: f64, color: Rgb) -> Shape {
+    Shape::Line {
+        from,
+        to,
+        width,
+        color,
+    }
+}
+
+/// Draws given shape to the RGB pixels
+fn raster(pixels: &mut [u8], w: usize, h: usize, shape: Shape) {
+    let (min, max, color): ((f64, f64), (f64, f64), Rgb) = match shape {
+        Shape::Line {
+            from,
+            to,
+            width,
+            color,
+        } => {
+            let r = width / 2.;
+            (
+                (from.0.min(to.0) - r, from.1.min(to.1) - r),
+                (from.0.max(to.0) + r, from.1.max(to.1) + r),
+                color,
+            )
+        }
+        Shape::Ring {
+            center,
+            radius,
+            width,
+            color,
+        } => {
+            let r = radius + width / 2.;
+            (
+                (center.0 - r, center.1 - r),
+                (center.0 + r, center.1 + r),
+                color,
+            )
+        }
+        Shape::Rect { pos, size, color } => {
+            (pos, (pos.0 + size, pos.1 + size), color)
+        }
+    };
+
+    let x_end = (max.0.ceil() as usize).min(w);
+    let y_end = (max.1.ceil() as usize).min(h);
+    for y in min.1.max(0.) as usize..y_end {
+        for x in min.0.max(0.) as usize..x_end {
+            let p = (x as f64 + 0.5, y as f64 + 0.5);
+            if covers(&shape, p) {
+                let id = (x + y * w) * 3;
+                pixels[id..id + 3]
+                    .copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+}
+
+/// Checks whether the shape covers given point
+fn covers(shape: &Shape, (px, py): (f64, f64)) -> bool {
+    match *shape {
+        Shape::Line {
+            from, to, width, ..
+        } => {
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            let len = dx * dx + dy * dy;
+            let t = match len {
+                0. => 0.,
+                _ => (((px - from.0) * dx + (py - from.1) * dy) / len)
+                    .clamp(0., 1.),
+            };
+            let (cx, cy) = (from.0 + t * dx - px, from.1 + t * dy - py);
+            (cx * cx + cy * cy).sqrt() <= width / 2.
+        }
+        Shape::Ring {
+            center,
+            radius,
+            width,
+            ..
+        } => {
+            let dist = (px - center.0).hypot(py - center.1);
+            (dist - radius).abs() <= width / 2.
+        }
+        Shape::Rect { pos, size, .. } => {
+            px >= pos.0
+                && px < pos.0 + size
+                && py >= pos.1
+                && py < pos.1 + size
+        }
+    }
+}
+
+/// Appends PNG chunk with given type and data
+fn png_chunk(res: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    res.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = res.len();
+    res.extend_from_slice(kind);
+    res.extend_from_slice(data);
+    let crc = crc32(&res[start..]);
+    res.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data into zlib stream using uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut res = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        res.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        res.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        res.extend_from_slice(&len.to_le_bytes());
+        res.extend_from_slice(&(!len).to_le_bytes());
+        res.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    res.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    res
+}
+
+/// Computes CRC-32 checksum of the given data
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+/// Converts terminal color to RGB, uses default for colors, which depend
+/// on the terminal
+fn rgb(color: Color, default: Rgb) -> Rgb {
+    match color {
+        Color::Hex(hex) => Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8),
+        Color::Rgb(r, g, b) => Rgb(r, g, b),
+        Color::Red | Color::DarkRed => Rgb(0xf4, 0x43, 0x36),
+        Color::Green | Color::DarkGreen => Rgb(0x4c, 0xaf, 0x50),
+        Color::Yellow | Color::DarkYellow => Rgb(0xff, 0xc1, 0x07),
+        Color::Blue | Color::DarkBlue => Rgb(0x21, 0x96, 0xf3),
+        Color::Cyan | Color::DarkCyan => Rgb(0x00, 0xbc, 0xd4),
+        Color::Magenta | Color::DarkMagenta => Rgb(0x9c, 0x27, 0xb0),
+        Color::White | Color::LightGray => Rgb(0xe0, 0xe0, 0xe0),
+        Color::Gray => Rgb(0x80, 0x80, 0x80),
+        _ => default,
+    }
+}
+
+/// Formats color as hex string
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
@@ -0,0 +1,413 @@
+use std::cmp::{max, min};
+
+use crate::{
+    board::{Board, BoardEvent, Illegal},
+    cell::Cell,
+    daily::Daily,
+    error::Error,
+    mode::Mode,
+    pos::Pos,
+    rng::Rng,
+    rules::Rules,
+    series::Series,
+    start_rule::StartRule,
+    win_len::WinLen,
+};
+
+/// Default number of the notakto boards
+pub const BOARDS: usize = 3;
+
+/// Game session owning the board and the turn order. Frontends only pass
+/// the moves in and present the result, so they all follow the same rules.
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub board: Board,
+    /// Boards of the multi-board variant, the played one is copied to
+    /// `board` and written back when switching
+    pub boards: Vec<Board>,
+    /// Index of the played board in `boards`
+    pub active: usize,
+    pub mode: Mode,
+    /// Player on turn, players are identified by the mark they start with
+    pub player: Cell,
+    /// Player, who started the current game
+    pub starter: Cell,
+    /// Mark placed by the player on turn, when players choose it
+    pub mark: Cell,
+    /// Whether the first player plays circle after swapping sides
+    pub swapped: bool,
+    /// Games won by the first and the second player, draw counts to both
+    pub score: (usize, usize),
+    /// Board size given by the arguments
+    pub size: Option<Pos>,
+    /// Win length of the new boards, it's based on their size when not
+    /// given
+    pub win_len: Option<usize>,
+    /// Win lengths of rows, columns and diagonals overriding `win_len`
+    pub win_dirs: (Option<usize>, Option<usize>, Option<usize>),
+    /// Whether completed lines score points instead of ending the game
+    pub points: bool,
+    /// Whether the board grows, when a mark is placed near its edge
+    pub infinite: bool,
+    /// Number of pieces of each player, which are moved once placed
+    pub pieces: Option<usize>,
+    /// Number of further moves after which a placed mark disappears
+    pub decay: Option<usize>,
+    /// Distance from own marks within which the player sees the board
+    pub fog: Option<usize>,
+    pub blocks: usize,
+    /// Number of marks placed for the second player before the start
+    pub handicap: usize,
+    /// Seed of the random blocks and marks, day of the daily challenge
+    pub seed: u64,
+    rng: Rng,
+    pub swap_rule: bool,
+    pub start_rule: StartRule,
+    /// Number of the notakto boards
+    pub board_count: usize,
+    pub series: Option<Series>,
+    /// Daily challenge, which places its own blocks and marks
+    pub daily: Option<Daily>,
+}
+
+impl Game {
+    /// Creates new [`Game`] played on the given board, new boards get
+    /// its rules
+    pub fn new(board: Board, mode: Mode) -> Self {
+        Self {
+            points: board.points,
+            infinite: board.infinite,
+            pieces: board.pieces,
+            decay: board.decay,
+            fog: board.fog,
+            board,
+            boards: vec![],
+            active: 0,
+            mode,
+            player: Cell::Cross,
+            starter: Cell::Cross,
+            mark: Cell::Cross,
+            swapped: false,
+            score: (0, 0),
+            size: None,
+            win_len: None,
+            win_dirs: (None, None, None),
+            blocks: 0,
+            handicap: 0,
+            seed: 0,
+            rng: Rng::new(0),
+            swap_rule: false,
+            start_rule: StartRule::default(),
+            board_count: BOARDS,
+            series: None,
+            daily: None,
+        }
+    }
+
+    /// Sets seed of the random blocks and handicap marks
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Gets rules of the current game
+    pub fn rules(&self) -> Rules {
+        Rules {
+            size: self.board.size,
+            win_len: self.board.win_len,
+            blocks: self.blocks,
+            handicap: self.handicap,
+            seed: (self.blocks > 0
+                || self.handicap > 0
+                || self.daily.is_some())
+            .then_some(self.seed),
+            swap_rule: self.swap_rule,
+            points: self.points,
+            infinite: self.infinite,
+            mode: self.mode,
+            boards: self.boards.len(),
+            pieces: self.pieces,
+            decay: self.decay,
+            fog: self.fog,
+            best_of: self.series.as_ref().map(|s| s.best_of),
+            start_rule: self.start_rule,
+        }
+    }
+
+    /// Gets player on turn
+    pub fn current_player(&self) -> Cell {
+        self.player
+    }
+
+    /// Gets symbol played by the first player
+    pub fn first_player(&self) -> Cell {
+        match self.swapped {
+            true => Cell::Circle,
+            false => Cell::Cross,
+        }
+    }
+
+    /// Gets moves of the played board
+    pub fn history(&self) -> &[(Cell, Pos)] {
+        &self.board.history
+    }
+
+    /// Gets player who won the finished game, [`Cell::Empty`] on a draw.
+    /// Player on turn stays the same after the last move.
+    pub fn result(&self) -> Option<Cell> {
+        // Multi-board game ends when all the boards are dead
+        let dead = self
+            .boards
+            .iter()
+            .enumerate()
+            .all(|(id, b)| id == self.active || b.state().is_some());
+        if !dead {
+            return None;
+        }
+        self.board.state().map(|s| self.mode.winner(s, self.player))
+    }
+
+    /// Plays the player on turn on the given position. When the pieces are
+    /// moved, the first call picks up the piece and the second one moves
+    /// it, playing the lifted piece puts it back. Returns events of the
+    /// played board.
+    pub fn play(&mut self, pos: Pos) -> Result<Vec<BoardEvent>, Error> {
+        self.board.select(pos);
+        let mark = self.mode.mark(self.player, self.mark);
+        self.legality(pos)?;
+        if self.board.must_move(mark) && self.board.lifted.is_none() {
+            self.board.lift_selected(mark)?;
+            return Ok(vec![]);
+        }
+        if self.board.lifted == Some(pos) {
+            // Piece is put back, so another one can be picked up
+            self.board.lifted = None;
+            return Ok(vec![]);
+        }
+        match self.board.lifted {
+            Some(_) => self.board.move_selected()?,
+            None => self.board.set_selected(mark)?,
+        };
+        // Dead board is switched below, so its events are taken first
+        let events = self.board.take_events();
+
+        match self.result() {
+            Some(winner) => self.end(winner),
+            None => {
+                self.player = self.player.next();
+                // Dead board can't be played anymore
+                if self.board.state().is_some() {
+                    self.switch_board(true);
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Counts the result of the ended game to the score and the match
+    fn end(&mut self, winner: Cell) {
+        let first = self.first_player();
+        let winner = match winner {
+            Cell::Empty => None,
+            cell if cell == first => Some(0),
+            _ => Some(1),
+        };
+        match winner {
+            None => self.score = (self.score.0 + 1, self.score.1 + 1),
+            Some(0) => self.score.0 += 1,
+            Some(_) => self.score.1 += 1,
+        }
+        if let Some(series) = self.series.as_mut() {
+            series.end_round(winner);
+        }
+    }
+
+    /// Checks whether the player on turn can play the cell on given
+    /// position
+    pub fn legality(&self, pos: Pos) -> Result<(), Illegal> {
+        let mark = self.mode.mark(self.player, self.mark);
+        self.board.legality_for(pos, mark)
+    }
+
+    /// Creates board of given size with the rules of the game
+    pub fn new_board(&self, w: usize, h: usize) -> Board {
+        let win = self.win_len.unwrap_or(min(max(w, h), 5));
+        let mut board = Board::new(w, h, win);
+        let (rows, cols, diag) = self.win_dirs;
+        board.win_len = board.win_len.with(rows, cols, diag);
+        board.points = self.points;
+        board.pieces = self.pieces;
+        board.decay = self.decay;
+        board.fog = self.fog;
+        board.infinite = self.infinite;
+        // Marks don't alternate in the variants
+        board.free_marks = self.mode != Mode::Normal;
+        board
+    }
+
+    /// Starts new game on the given board
+    pub fn set_board(&mut self, board: Board) {
+        self.board = board;
+        self.restart(Cell::Cross);
+    }
+
+    /// Replaces the board by the one with given size and win length, the
+    /// look of the board is kept and the game is restarted
+    pub fn resize(&mut self, size: Pos, win: usize) -> Result<(), Error> {
+        let (rows, cols, diag) = self.win_dirs;
+        WinLen::new(win)
+            .with(rows, cols, diag)
+            .check(size.x, size.y)?;
+
+        self.size = Some(size);
+        self.win_len = Some(win);
+        let old = &self.board;
+        let mut board = self.new_board(size.x, size.y);
+        board.theme = old.theme;
+        board.relative = old.relative;
+        board.labels = old.labels;
+        board.numbering = old.numbering;
+        board.threats = old.threats;
+        board.marks = old.marks;
+        board.zoom = old.zoom;
+        self.set_board(board);
+        Ok(())
+    }
+
+    /// Continues the game on the given board, where the given player
+    /// started. Next games are played with the given rules.
+    pub fn load(&mut self, board: Board, first: Cell, rules: &Rules) {
+        self.size = Some(rules.size);
+        let win = rules.win_len;
+        self.win_len = Some(win.rows);
+        self.win_dirs = (Some(win.rows), Some(win.cols), Some(win.diag));
+        self.points = rules.points;
+        self.infinite = rules.infinite;
+        self.pieces = rules.pieces;
+        self.decay = rules.decay;
+        self.fog = rules.fog;
+        self.blocks = rules.blocks;
+        self.handicap = rules.handicap;
+        self.swap_rule = rules.swap_rule;
+        self.start_rule = rules.start_rule;
+        self.series = rules.best_of.map(Series::new);
+        if let Some(seed) = rules.seed {
+            self.seed = seed;
+            self.rng = Rng::new(seed);
+        }
+
+        self.board = board;
+        self.player = first;
+        self.starter = first;
+    }
+
+    /// Starts new game, the starting player is chosen by the start rule
+    pub fn new_game(&mut self) {
+        let starter = self.start_rule.next(self.starter, self.result());
+        self.restart(starter);
+    }
+
+    /// Restarts the game, the given player starts. Blocks and handicap
+    /// marks are placed and the boards of the multi-board variant created.
+    pub fn restart(&mut self, starter: Cell) {
+        self.board.restart();
+        self.player = starter;
+        self.starter = starter;
+        match &self.daily {
+            Some(daily) => daily.setup(&mut self.board),
+            None => {
+                self.board.add_blocks(self.blocks, &mut self.rng);
+                let weaker = starter.next();
+                self.board
+                    .add_handicap(weaker, self.handicap, &mut self.rng);
+            }
+        }
+        let count = match self.mode {
+            Mode::Notakto => self.board_count,
+            _ => 0,
+        };
+        self.setup_boards(count);
+        self.swapped = self.series.as_ref().is_some_and(|s| s.swapped());
+    }
+
+    /// Creates the given number of boards of the multi-board variant from
+    /// the played board
+    pub fn setup_boards(&mut self, count: usize) {
+        self.board.multi = count > 0;
+        self.boards = vec![self.board.clone(); count];
+        self.active = 0;
+    }
+
+    /// Switches to the next board, which isn't dead, in the given direction
+    pub fn switch_board(&mut self, forward: bool) {
+        let len = self.boards.len();
+        if len == 0 {
+            return;
+        }
+        self.boards[self.active] = self.board.clone();
+        let step = if forward { 1 } else { len - 1 };
+        let next = (1..=len)
+            .map(|i| (self.active + i * step) % len)
+            .find(|id| self.boards[*id].state().is_none());
+        if let Some(id) = next {
+            self.active = id;
+            self.board = self.boards[id].clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_alternates_players_and_scores() {
+        let mut game = Game::new(Board::new(3, 3, 3), Mode::Normal);
+        for (x, y) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            game.play(Pos::new(x, y)).unwrap();
+        }
+        assert_eq!(game.current_player(), Cell::Cross);
+        assert!(game.play(Pos::new(0, 0)).is_err());
+
+        game.play(Pos::new(2, 0)).unwrap();
+        assert_eq!(game.result(), Some(Cell::Cross));
+        assert_eq!(game.score, (1, 0));
+        assert!(matches!(game.play(Pos::new(2, 2)), Err(Error::GameEnded)));
+    }
+
+    #[test]
+    fn restart_follows_rules() {
+        let mut game = Game::new(Board::new(5, 5, 4), Mode::Normal).seed(7);
+        game.blocks = 2;
+        game.handicap = 1;
+        game.restart(Cell::Cross);
+        let blocked = game.board.cells.iter();
+        assert_eq!(blocked.filter(|c| **c == Cell::Blocked).count(), 2);
+        assert_eq!(game.board.handicap.len(), 1);
+
+        let mut game = Game::new(Board::new(3, 3, 3), Mode::Normal);
+        game.series = Some(Series::new(3));
+        for (x, y) in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)] {
+            game.play(Pos::new(x, y)).unwrap();
+        }
+        assert!(game.series.as_ref().is_some_and(|s| s.wins == (1, 0)));
+        game.series.as_mut().unwrap().next_round();
+        game.new_game();
+        assert!(game.swapped);
+        assert_eq!(game.first_player(), Cell::Circle);
+    }
+
+    #[test]
+    fn dead_board_is_switched() {
+        let mut game = Game::new(Board::new(3, 3, 3), Mode::Notakto);
+        game.board.free_marks = true;
+        game.setup_boards(2);
+        for x in 0..3 {
+            game.play(Pos::new(x, 0)).unwrap();
+        }
+        assert_eq!(game.active, 1);
+        assert_eq!(game.result(), None);
+        assert_eq!(game.history().len(), 0);
+    }
+}
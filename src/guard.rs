@@ -0,0 +1,63 @@
+use std::{
+    io::{stdout, Write},
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+    thread,
+};
+
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
+use crate::error::Error;
+
+/// Whether the terminal is currently in the full screen raw mode
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Makes sure the panic hook is installed only once
+static HOOK: Once = Once::new();
+
+/// Switches the terminal to the alternate screen in raw mode and restores
+/// it when dropped. Panic on the main thread restores the terminal before
+/// the panic message is printed, so it isn't lost in the alternate screen.
+#[derive(Debug)]
+pub struct TermGuard;
+
+impl TermGuard {
+    /// Saves screen, clears it, hides cursor and enables raw mode
+    pub fn enter() -> Result<Self, Error> {
+        HOOK.call_once(|| {
+            let prev = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                // AI threads panicking don't end the app
+                if thread::current().name() == Some("main") {
+                    restore();
+                }
+                prev(info);
+            }));
+        });
+
+        print!("\x1b[?1049h\x1b[2J\x1b[?25l");
+        _ = stdout().flush();
+        ACTIVE.store(true, Ordering::SeqCst);
+        enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TermGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Disables raw mode, restores screen and shows cursor, when the terminal
+/// is in the full screen raw mode
+pub fn restore() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    _ = disable_raw_mode();
+    print!("\x1b[?1049l\x1b[?25h");
+    _ = stdout().flush();
+}
@@ -0,0 +1,395 @@
+use std::{
+    fmt::Display,
+    io,
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{board::Board, error::Error, palette::Command};
+
+/// Action of the player in the game, which the keys are translated to, so
+/// the game doesn't depend on the actual keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    /// Places the mark on the selected cell
+    Place,
+    /// Places the mark on the cell of the 3x3 board with given numpad digit
+    Numpad(usize),
+    /// Adds digit to the count of the next movement
+    Count(usize),
+    /// Answers whether the second player swaps sides after the first move
+    Swap(bool),
+    /// Switches to the next mark or board
+    SwitchNext,
+    /// Switches to the previous board
+    SwitchPrev,
+    Export,
+    Analysis,
+    Hint,
+    /// Opens prompt for the cell name to place at
+    PlaceAt,
+    CommandLine,
+    Palette,
+    Log,
+    ScrollUp,
+    ScrollDown,
+    /// Executes the palette command
+    Run(Command),
+    /// Exits immediately without confirmation
+    Exit,
+    /// Key without an action, handled by the dialogs and the prompts
+    Key(KeyEvent),
+}
+
+impl Action {
+    /// Gets action bound to the given key, which doesn't depend on the
+    /// controls preset
+    pub fn from_key(event: KeyEvent) -> Option<Self> {
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        let action = match event.code {
+            KeyCode::Char('c') if ctrl => Action::Exit,
+            KeyCode::Char('p') if ctrl => Action::Palette,
+            KeyCode::Enter => Action::Place,
+            KeyCode::Tab => Action::SwitchNext,
+            KeyCode::BackTab => Action::SwitchPrev,
+            KeyCode::PageUp => Action::ScrollUp,
+            KeyCode::PageDown => Action::ScrollDown,
+            KeyCode::Esc => Action::Run(Command::Quit),
+            KeyCode::Char('e') => Action::Export,
+            KeyCode::Char('A') => Action::Analysis,
+            KeyCode::Char('H') => Action::Hint,
+            KeyCode::Char('g') => Action::PlaceAt,
+            KeyCode::Char(':') => Action::CommandLine,
+            KeyCode::Char('L') => Action::Log,
+            KeyCode::Char(c) => {
+                let key = c.to_string();
+                let cmd = Command::ALL.iter().find(|cmd| cmd.key() == key)?;
+                Action::Run(*cmd)
+            }
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    /// Names of the actions without parameters used in the recordings
+    const NAMES: &'static [(&'static str, Action)] = &[
+        ("move-up", Action::MoveUp),
+        ("move-down", Action::MoveDown),
+        ("move-left", Action::MoveLeft),
+        ("move-right", Action::MoveRight),
+        ("place", Action::Place),
+        ("switch-next", Action::SwitchNext),
+        ("switch-prev", Action::SwitchPrev),
+        ("export", Action::Export),
+        ("analysis", Action::Analysis),
+        ("hint", Action::Hint),
+        ("place-at", Action::PlaceAt),
+        ("command-line", Action::CommandLine),
+        ("palette", Action::Palette),
+        ("log", Action::Log),
+        ("scroll-up", Action::ScrollUp),
+        ("scroll-down", Action::ScrollDown),
+        ("exit", Action::Exit),
+    ];
+
+    /// Gets function moving the selection, when the action is a movement
+    pub fn movement(&self) -> Option<fn(&mut Board)> {
+        match self {
+            Action::MoveUp => Some(Board::up),
+            Action::MoveDown => Some(Board::down),
+            Action::MoveLeft => Some(Board::left),
+            Action::MoveRight => Some(Board::right),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Numpad(digit) => write!(f, "numpad {digit}"),
+            Action::Count(digit) => write!(f, "count {digit}"),
+            Action::Swap(true) => write!(f, "swap yes"),
+            Action::Swap(false) => write!(f, "swap no"),
+            Action::Run(cmd) => write!(f, "run {}", cmd.key()),
+            Action::Key(event) => write!(f, "key {}", key_name(event)),
+            action => {
+                let name = Action::NAMES
+                    .iter()
+                    .find_map(|(n, a)| (a == action).then_some(*n))
+                    .unwrap_or_default();
+                write!(f, "{name}")
+            }
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, arg) = match s.split_once(' ') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s, None),
+        };
+        let digit = |max| {
+            arg.and_then(|a| a.parse().ok())
+                .filter(|d| *d <= max)
+                .ok_or_else(|| {
+                    Error::parse(format!("invalid {name} digit in '{s}'"))
+                })
+        };
+        let action = match (name, arg) {
+            ("numpad", _) => match digit(9)? {
+                0 => Err(Error::parse(format!(
+                    "invalid {name} digit in '{s}'"
+                )))?,
+                d => Action::Numpad(d),
+            },
+            ("count", _) => Action::Count(digit(9)?),
+            ("swap", Some("yes")) => Action::Swap(true),
+            ("swap", Some("no")) => Action::Swap(false),
+            ("run", Some(key)) => Command::ALL
+                .iter()
+                .find(|c| c.key() == key)
+                .map(|c| Action::Run(*c))
+                .ok_or_else(|| {
+                    Error::parse(format!("unknown command key '{key}'"))
+                })?,
+            ("key", Some(key)) => Action::Key(parse_key(key)?),
+            (name, None) => Action::NAMES
+                .iter()
+                .find_map(|(n, a)| (*n == name).then_some(*a))
+                .ok_or_else(|| {
+                    Error::parse(format!("unknown action '{s}'"))
+                })?,
+            _ => Err(Error::parse(format!("unknown action '{s}'")))?,
+        };
+        Ok(action)
+    }
+}
+
+/// Names of the keys without a character
+const KEYS: &[(&str, KeyCode)] = &[
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("space", KeyCode::Char(' ')),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("null", KeyCode::Null),
+];
+
+/// Modifiers of the keys, which prefix the key name joined by `+`
+const MODIFIERS: &[(&str, KeyModifiers)] = &[
+    ("ctrl", KeyModifiers::CONTROL),
+    ("alt", KeyModifiers::ALT),
+    ("shift", KeyModifiers::SHIFT),
+];
+
+/// Gets name of the key, like `ctrl+c`. Keys, which can't be named, are
+/// named `null` and do nothing when parsed back.
+fn key_name(event: &KeyEvent) -> String {
+    let mut name = String::new();
+    for (modifier, flag) in MODIFIERS {
+        if event.modifiers.contains(*flag) {
+            name.push_str(modifier);
+            name.push('+');
+        }
+    }
+    match KEYS.iter().find(|(_, code)| *code == event.code) {
+        Some((key, _)) => name.push_str(key),
+        None => match event.code {
+            KeyCode::Char(c) => name.push(c),
+            KeyCode::F(n) => name.push_str(&format!("f{n}")),
+            _ => name.push_str("null"),
+        },
+    }
+    name
+}
+
+/// Parses key with the given name, see [`key_name`]
+fn parse_key(name: &str) -> Result<KeyEvent, Error> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut key = name;
+    while let Some((modifier, flag)) = MODIFIERS
+        .iter()
+        .find(|(m, _)| key.starts_with(&format!("{m}+")))
+    {
+        modifiers |= *flag;
+        key = &key[modifier.len() + 1..];
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match KEYS.iter().find(|(k, _)| *k == key) {
+            Some((_, code)) => *code,
+            None => key
+                .strip_prefix('f')
+                .and_then(|n| n.parse().ok())
+                .map(KeyCode::F)
+                .ok_or_else(|| {
+                    Error::parse(format!("unknown key '{name}'"))
+                })?,
+        },
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Queue of the input events read in the background. Buttons of the game
+/// controller are merged with the terminal events as keys, when the
+/// `gamepad` feature is enabled.
+#[derive(Debug)]
+pub struct Input {
+    rx: Receiver<io::Result<Event>>,
+}
+
+impl Input {
+    /// Starts reading the terminal and the game controller
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel();
+        gamepad(tx.clone());
+        thread::spawn(move || loop {
+            let event = read();
+            let failed = event.is_err();
+            if tx.send(event).is_err() || failed {
+                break;
+            }
+        });
+        Self { rx }
+    }
+
+    /// Waits for the next event at most the given time, [`None`] when no
+    /// event came
+    pub fn next(&self, timeout: Duration) -> Result<Option<Event>, Error> {
+        match self.rx.recv_timeout(timeout) {
+            Ok(event) => Ok(Some(event?)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input closed",
+            ))?,
+        }
+    }
+}
+
+/// Reads the game controller using the Linux joystick API and sends its
+/// buttons as the keys, nothing is read when there's no controller
+#[cfg(feature = "gamepad")]
+fn gamepad(tx: Sender<io::Result<Event>>) {
+    use std::{fs::File, io::Read};
+
+    /// Device of the first connected controller
+    const DEVICE: &str = "/dev/input/js0";
+
+    thread::spawn(move || {
+        let Ok(mut device) = File::open(DEVICE) else {
+            return;
+        };
+        // Event consists of time, value, type and number of the control
+        let mut buf = [0; 8];
+        while device.read_exact(&mut buf).is_ok() {
+            let value = i16::from_le_bytes([buf[4], buf[5]]);
+            let Some(code) = gamepad_key(buf[6], buf[7], value) else {
+                continue;
+            };
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            if tx.send(Ok(Event::Key(key))).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Game controller support is disabled
+#[cfg(not(feature = "gamepad"))]
+fn gamepad(_tx: Sender<io::Result<Event>>) {}
+
+/// Gets key of the controller event with given type, number and value.
+/// D-pad moves the selection, A places the mark and Start restarts.
+#[cfg(feature = "gamepad")]
+fn gamepad_key(kind: u8, number: u8, value: i16) -> Option<KeyCode> {
+    const BUTTON: u8 = 0x01;
+    const AXIS: u8 = 0x02;
+    match (kind, number) {
+        (BUTTON, 0) if value == 1 => Some(KeyCode::Enter),
+        (BUTTON, 7) if value == 1 => Some(KeyCode::Char('r')),
+        (AXIS, 6) if value < 0 => Some(KeyCode::Left),
+        (AXIS, 6) if value > 0 => Some(KeyCode::Right),
+        (AXIS, 7) if value < 0 => Some(KeyCode::Up),
+        (AXIS, 7) if value > 0 => Some(KeyCode::Down),
+        // Initial state is reported with its own type and releases are
+        // ignored
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_actions() {
+        let key = |code| Action::from_key(KeyEvent::from(code));
+        assert_eq!(key(KeyCode::Enter), Some(Action::Place));
+        assert_eq!(
+            key(KeyCode::Char('r')),
+            Some(Action::Run(Command::NewGame))
+        );
+        assert_eq!(key(KeyCode::Esc), Some(Action::Run(Command::Quit)));
+        assert_eq!(key(KeyCode::Char('x')), None);
+        let ctrl = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(Action::from_key(ctrl), Some(Action::Exit));
+    }
+
+    #[test]
+    fn actions_are_named() {
+        let mods = KeyModifiers::SHIFT | KeyModifiers::CONTROL;
+        for action in [
+            Action::MoveLeft,
+            Action::Numpad(7),
+            Action::Swap(false),
+            Action::Run(Command::EvalBar),
+            Action::Key(KeyEvent::new(KeyCode::Char('+'), mods)),
+            Action::Key(KeyEvent::from(KeyCode::F(5))),
+        ] {
+            assert_eq!(action.to_string().parse::<Action>().unwrap(), action);
+        }
+        assert_eq!(
+            Action::Key(KeyEvent::new(KeyCode::Char('c'), mods)).to_string(),
+            "key ctrl+shift+c"
+        );
+        assert!("numpad 0".parse::<Action>().is_err());
+        assert!("run x".parse::<Action>().is_err());
+    }
+
+    #[cfg(feature = "gamepad")]
+    #[test]
+    fn gamepad_buttons_are_keys() {
+        assert_eq!(gamepad_key(1, 0, 1), Some(KeyCode::Enter));
+        assert_eq!(gamepad_key(1, 0, 0), None);
+        assert_eq!(gamepad_key(2, 7, -32767), Some(KeyCode::Up));
+        assert_eq!(gamepad_key(2, 6, 0), None);
+        // Initial state of the button isn't pressing it
+        assert_eq!(gamepad_key(0x81, 0, 1), None);
+    }
+}
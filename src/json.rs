@@ -0,0 +1,273 @@
+use std::{fmt::Display, iter::Peekable, str::Chars};
+
+use crate::error::Error;
+
+/// Represents JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Parses JSON value from the given text
+    pub fn parse(text: &str) -> Result<Json, Error> {
+        let mut chars = text.chars().peekable();
+        let val = parse_val(&mut chars)?;
+        skip_ws(&mut chars);
+        match chars.next() {
+            None => Ok(val),
+            Some(c) => Err(Error::Protocol(format!(
+                "json: unexpected '{c}' after value"
+            )))?,
+        }
+    }
+
+    /// Creates JSON object from the given key value pairs
+    pub fn obj<const N: usize>(pairs: [(&str, Json); N]) -> Json {
+        Json::Obj(pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    /// Gets value of the given key, when value is an object
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Obj(pairs) => {
+                pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets value as number
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Gets value as string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Gets value as array
+    pub fn as_arr(&self) -> Option<&[Json]> {
+        match self {
+            Json::Arr(arr) => Some(arr),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Num(n) if n.is_finite() => write!(f, "{n}"),
+            Json::Num(_) => write!(f, "null"),
+            Json::Str(s) => write_str(f, s),
+            Json::Arr(arr) => {
+                write!(f, "[")?;
+                for (i, val) in arr.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{val}")?;
+                }
+                write!(f, "]")
+            }
+            Json::Obj(pairs) => {
+                write!(f, "{{")?;
+                for (i, (key, val)) in pairs.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{val}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::Str(value.to_string())
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Num(value)
+    }
+}
+
+/// Writes escaped JSON string
+fn write_str(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Skips whitespace characters
+fn skip_ws(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Parses JSON value
+fn parse_val(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    skip_ws(chars);
+    match chars.peek() {
+        Some('{') => parse_obj(chars),
+        Some('[') => parse_arr(chars),
+        Some('"') => parse_str(chars).map(Json::Str),
+        Some('t') => parse_word(chars, "true", Json::Bool(true)),
+        Some('f') => parse_word(chars, "false", Json::Bool(false)),
+        Some('n') => parse_word(chars, "null", Json::Null),
+        Some(c) if *c == '-' || c.is_ascii_digit() => parse_num(chars),
+        Some(c) => Err(Error::Protocol(format!("json: unexpected '{c}'")))?,
+        None => Err(Error::Protocol("json: unexpected end".into()))?,
+    }
+}
+
+/// Parses JSON object
+fn parse_obj(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    chars.next();
+    let mut pairs = vec![];
+    skip_ws(chars);
+    if chars.next_if_eq(&'}').is_some() {
+        return Ok(Json::Obj(pairs));
+    }
+
+    loop {
+        skip_ws(chars);
+        let key = parse_str(chars)?;
+        skip_ws(chars);
+        if chars.next() != Some(':') {
+            return Err(Error::Protocol("json: expected ':'".into()));
+        }
+        pairs.push((key, parse_val(chars)?));
+        skip_ws(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(Json::Obj(pairs)),
+            _ => {
+                return Err(Error::Protocol(
+                    "json: expected ',' or '}'".into(),
+                ))?
+            }
+        }
+    }
+}
+
+/// Parses JSON array
+fn parse_arr(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    chars.next();
+    let mut arr = vec![];
+    skip_ws(chars);
+    if chars.next_if_eq(&']').is_some() {
+        return Ok(Json::Arr(arr));
+    }
+
+    loop {
+        arr.push(parse_val(chars)?);
+        skip_ws(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(Json::Arr(arr)),
+            _ => {
+                return Err(Error::Protocol(
+                    "json: expected ',' or ']'".into(),
+                ))?
+            }
+        }
+    }
+}
+
+/// Parses JSON string
+fn parse_str(chars: &mut Peekable<Chars>) -> Result<String, Error> {
+    if chars.next() != Some('"') {
+        return Err(Error::Protocol("json: expected string".into()));
+    }
+
+    let mut res = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(res),
+            Some('\\') => match chars.next() {
+                Some('n') => res.push('\n'),
+                Some('r') => res.push('\r'),
+                Some('t') => res.push('\t'),
+                Some('b') => res.push('\u{8}'),
+                Some('f') => res.push('\u{c}'),
+                Some('u') => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&code, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .unwrap_or(char::REPLACEMENT_CHARACTER);
+                    res.push(c);
+                }
+                Some(c) => res.push(c),
+                None => {
+                    return Err(Error::Protocol(
+                        "json: unexpected end".into(),
+                    ))?
+                }
+            },
+            Some(c) => res.push(c),
+            None => {
+                return Err(Error::Protocol(
+                    "json: unterminated string".into(),
+                ))?
+            }
+        }
+    }
+}
+
+/// Parses JSON number
+fn parse_num(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    let mut num = String::new();
+    while let Some(c) = chars.next_if(|c| {
+        c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+    }) {
+        num.push(c);
+    }
+    num.parse::<f64>()
+        .map(Json::Num)
+        .map_err(|_| Error::Protocol(format!("json: invalid number '{num}'")))
+}
+
+/// Parses JSON keyword
+fn parse_word(
+    chars: &mut Peekable<Chars>,
+    word: &str,
+    val: Json,
+) -> Result<Json, Error> {
+    for c in word.chars() {
+        if chars.next() != Some(c) {
+            return Err(Error::Protocol(format!("json: expected '{word}'")));
+        }
+    }
+    Ok(val)
+}
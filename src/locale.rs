@@ -0,0 +1,395 @@
+use std::{collections::HashMap, env, fmt::Display, fs, sync::OnceLock};
+
+use crate::{error::Error, paths};
+
+/// Texts of the user interface in English, which is built in and used for
+/// the texts missing in the language file. `{}` is replaced by the values
+/// in the given order.
+const ENGLISH: &[(&str, &str)] = &[
+    // Writing direction, `rtl` letters the board columns from the right
+    ("direction", "ltr"),
+    ("state.turn", "turn."),
+    ("state.wins", "wins!"),
+    ("state.draw", "Draw!"),
+    ("state.swap", "swap? y/n"),
+    ("state.thinking", "{} thinking..."),
+    ("vs", "vs"),
+    ("daily.streak", "streak {}"),
+    ("place-at", "Place at: {}_"),
+    ("continue", "Press any key to continue."),
+    ("game-saved", "Game saved, continue it using {}"),
+    ("help.move", "Move"),
+    ("help.player", "Player {}"),
+    ("help.place", "Place"),
+    ("help.mark", "Mark"),
+    ("help.board", "Board"),
+    ("help.restart", "Restart"),
+    ("help.reset-score", "Resets score"),
+    ("help.log", "Log"),
+    ("help.export", "Export"),
+    ("help.copy", "Copy"),
+    ("help.labels", "Labels"),
+    ("help.threats", "Threats"),
+    ("help.eval-bar", "Eval bar"),
+    ("help.place-at", "Place at"),
+    ("help.hint", "Hint"),
+    ("help.rules", "Rules"),
+    ("help.pause", "Pause"),
+    ("help.commands", "Commands"),
+    ("help.command-line", "Command line"),
+    ("help.quit", "Quit"),
+    ("help.mark-cell", "Mark"),
+    ("help.line", "Line"),
+    ("help.clear", "Clear"),
+    ("help.stop", "Stop"),
+    ("hint.next-game", "Game over, press Enter to continue"),
+    ("hint.rematch", "Game over, press r for rematch"),
+    ("hint.swap", "Press y to swap sides or n to keep playing"),
+    ("hint.move-piece", "Select empty cell to move the piece to"),
+    ("hint.pick-up", "Pick up your piece to move it"),
+    ("status.no-cell", "no cell named '{}'"),
+    ("status.config-not-saved", "cannot save config: {}"),
+    ("status.tournament-not-saved", "cannot save tournament: {}"),
+    ("status.wait-replay", "Wait for the replay to finish"),
+    ("status.wait-ai", "Wait for the AI move"),
+    ("status.no-assist", "Hints are given in the assisted mode"),
+    ("status.no-hints", "No hints left in this game"),
+    ("status.hint", "Hint: {} ({} left)"),
+    ("status.daily-solved", "Daily challenge solved, streak {}"),
+    ("status.analysing", "Game is still being analysed"),
+    (
+        "status.no-analysis",
+        "Analysis is available after the game ends",
+    ),
+    (
+        "status.analysis-ready",
+        "Analysis ready, press A to show it",
+    ),
+    ("status.exported", "Exported to {}"),
+    ("status.copied", "Board copied to clipboard"),
+    ("status.saved", "Saved to {}"),
+    ("status.loaded", "Loaded {}"),
+    ("status.replay-finished", "Replay finished"),
+    (
+        "status.no-rules",
+        "Record has no rule summary, its rules may be incomplete",
+    ),
+    ("status.playback-finished", "Playback finished"),
+    ("status.zoom", "Zoom: {}"),
+    ("status.resized", "Board resized to {}x{}"),
+    ("status.win-len", "Win length set to {}"),
+    ("status.ai-on", "AI opponent plays circle"),
+    ("status.ai-off", "AI opponent turned off"),
+    ("illegal.ended", "game ended"),
+    ("illegal.occupied", "cell is occupied"),
+    ("illegal.blocked", "cell is blocked and can't be played"),
+    (
+        "illegal.must-move",
+        "all pieces are placed, pick one up to move",
+    ),
+    ("illegal.not-own-piece", "pick up one of your pieces"),
+    ("illegal.dead-board", "board is dead, play on another one"),
+    ("illegal.hidden", "cell is hidden in the fog"),
+    (
+        "error.board-locked",
+        "board can't be changed in the daily challenge, tournament or \
+        notakto",
+    ),
+    (
+        "error.ai-locked",
+        "AI opponent can't be changed in the daily challenge or tournament",
+    ),
+    (
+        "error.load-normal",
+        "Saved games can be loaded only in normal game",
+    ),
+    (
+        "error.export-normal",
+        "game records support only the normal mode on a fixed board",
+    ),
+    ("quit.title", "Quit?"),
+    ("quit.text", "Game in progress will be lost."),
+    ("quit.keys", "[y]Quit  [n|Esc]Keep playing"),
+    ("risky.title", "Risky move"),
+    ("risky.text", "Opponent wins next turn here."),
+    ("risky.keys", "[y]Place anyway  [n|Esc]Cancel"),
+    ("unwinnable.title", "Unwinnable game"),
+    ("unwinnable.text", "Win length doesn't fit the board."),
+    ("unwinnable.keys", "[y]Play anyway  [n|Esc]Quit"),
+    ("small.title", "Terminal too small!"),
+    ("small.text", "You have to increase terminal size"),
+    ("config-errors.title", "Config errors"),
+    (
+        "config-errors.defaults",
+        "Invalid values were replaced by defaults.",
+    ),
+    ("match.title", "Match over"),
+    ("match.won", "Player {} wins the match!"),
+    ("match.keys", "[r]New match  [Esc|q]Quit"),
+    ("paused.title", "Paused"),
+    ("paused.text", "Press p to continue."),
+    ("command.title", "Commands"),
+    ("command.new-game", "New game"),
+    ("command.reset-score", "Reset score"),
+    ("command.rules", "Show rules"),
+    ("command.copy-board", "Copy board"),
+    ("command.zoom", "Zoom"),
+    ("command.labels", "Toggle labels"),
+    ("command.threats", "Toggle threats"),
+    ("command.eval-bar", "Toggle eval bar"),
+    ("command.pause", "Pause"),
+    ("command.load", "Load game"),
+    ("command.quit", "Quit"),
+    ("analysis.title", "Analysis"),
+    ("analysis.accuracy", "Accuracy: X {}, O {}"),
+    ("analysis.blunder", "First blunder: {}"),
+    ("analysis.missed", "Missed wins: {}"),
+    ("analysis.none", "none"),
+    ("analysis.keys", "[Esc]Close"),
+    ("log.title", "Moves"),
+    ("tournament.title", "Tournament"),
+    ("tournament.won", "{} wins the tournament!"),
+    ("tournament.next", "Next: {} (X) vs {} (O)"),
+    ("tournament.keys", "[Enter]Play  [Esc|q]Quit"),
+    ("tournament.over-keys", "[Esc|q]Quit"),
+    ("tournament.bye", "bye"),
+    ("tournament.final", "Final"),
+    ("tournament.semifinals", "Semifinals"),
+    ("tournament.quarterfinals", "Quarterfinals"),
+    ("tournament.round", "Round {}"),
+    ("wizard.title", "Setup"),
+    ("wizard.text", "No config found, choose your defaults:"),
+    ("wizard.controls", "Controls"),
+    ("wizard.theme", "Theme"),
+    ("wizard.size", "Board size"),
+    ("wizard.stats", "Save stats"),
+    ("wizard.auto", "auto"),
+    ("wizard.on", "on"),
+    ("wizard.off", "off"),
+    ("wizard.keys", "[↑↓]Select [←→]Change [Enter]Save [Esc]Skip"),
+    ("rules.title", "Rules"),
+    ("rules.board", "Board: {}x{}"),
+    ("rules.win", "Win: {}"),
+    ("rules.draw", "Draw: board is full and nobody won"),
+    ("rules.free-marks", "Marks: both players place X or O"),
+    ("rules.order-wins", "Order wins: {}, any mark"),
+    ("rules.chaos-wins", "Chaos wins: no line can be completed"),
+    ("rules.boards", "Boards: {}, both players place X"),
+    ("rules.dead-board", "Dead board: {}"),
+    (
+        "rules.notakto-lose",
+        "Lose: completing line on the last board",
+    ),
+    ("rules.wild-win", "Win: {}, any mark"),
+    ("rules.wild-draw", "Draw: no line can be completed"),
+    ("rules.infinite", "Board: grows near the edges, no draw"),
+    ("rules.line", "Line: {}"),
+    ("rules.points-win", "Win: most lines when board is full"),
+    ("rules.points-draw", "Draw: same number of lines"),
+    ("rules.decay-draw", "Draw: nobody won in {} moves"),
+    ("rules.blocks", "Blocks: {} cells can't be played"),
+    (
+        "rules.handicap",
+        "Handicap: 2nd player starts with {} marks",
+    ),
+    ("rules.pieces", "Pieces: {} each, then they are moved"),
+    ("rules.decay", "Decay: marks disappear after {} moves"),
+    ("rules.fog", "Fog: only cells within {} of own marks"),
+    ("rules.seed", "Seed: {}, reproduce with --seed"),
+    ("rules.swap", "Swap: 2nd player can swap after 1st move"),
+    ("rules.match", "Match: best of {}, first to {} wins"),
+    ("rules.start", "Start: {}"),
+    ("win-len.uniform", "{} in a row in any direction"),
+    (
+        "win-len.directions",
+        "{} in a row, {} in a column, {} diagonally",
+    ),
+    ("start.alternate", "players take turns starting"),
+    ("start.loser", "loser of the last game starts"),
+    ("start.winner", "winner of the last game starts"),
+    ("start.always-x", "X always starts"),
+    ("accessible.config-error", "Config error, {}"),
+    ("accessible.unwinnable", "Warning, {}, nobody can win."),
+    (
+        "accessible.seed",
+        "Seed {}, reproduce the boards with --seed.",
+    ),
+    (
+        "accessible.new-game",
+        "New game on {}x{} board, {} {}. {} starts.",
+    ),
+    ("accessible.goal", "wins"),
+    ("accessible.points-goal", "scores a line, most lines win"),
+    ("accessible.blocks", "Cells marked # are blocked."),
+    ("accessible.handicap", "{} starts with {}."),
+    (
+        "accessible.intro",
+        "Type cell name like b2 to play, help for commands.",
+    ),
+    ("accessible.turn", "{} to move:"),
+    ("accessible.game-over", "Type restart or quit:"),
+    ("accessible.unknown", "Unknown cell or command: {}"),
+    ("accessible.invalid", "Can't play {}, {}."),
+    ("accessible.move", "{} plays {}."),
+    ("accessible.lines", "Lines: X {}, O {}."),
+    ("accessible.draw", "Draw."),
+    ("accessible.wins", "{} wins!"),
+    (
+        "accessible.help",
+        "Commands:\n  cell name (e.g. b2) plays the cell\n  board prints \
+        the board (or empty line)\n  restart starts new game\n  quit exits \
+        the game",
+    ),
+];
+
+/// Locale used by [`tr`], English is used until it's set
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Texts of the user interface translated to one language
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    texts: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Loads locale of the given language, or of the language of the
+    /// system when [`None`]. Language file of the system language is
+    /// optional, English is used without it.
+    pub fn load(lang: Option<&str>) -> Result<Self, Error> {
+        let (code, required) = match lang {
+            Some(lang) => (lang.to_string(), true),
+            None => (Locale::system(), false),
+        };
+        if code == "en" {
+            return Ok(Locale::default());
+        }
+
+        let path = paths::config_dir()
+            .map(|dir| dir.join("lang").join(format!("{code}.ini")));
+        let Some(path) = path else {
+            return match required {
+                true => Err(Error::no_dir("config")),
+                false => Ok(Locale::default()),
+            };
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => Locale::parse(&text).map_err(|e| Error::load(path, e)),
+            Err(_) if !required => Ok(Locale::default()),
+            Err(e) => Err(Error::load(path, e)),
+        }
+    }
+
+    /// Parses locale from the given text. Each line contains `key = text`,
+    /// `\n` in the text is a line break and lines starting with `#` are
+    /// comments.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut texts = HashMap::new();
+        for (i, line) in text.lines().map(str::trim).enumerate() {
+            let err = |msg: String| {
+                Error::parse(format!("language line {}: {msg}", i + 1))
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, val)) = line.split_once('=') else {
+                Err(err("expected 'key = text'".into()))?
+            };
+            let key = key.trim();
+            let Some((_, english)) = ENGLISH.iter().find(|(k, _)| *k == key)
+            else {
+                Err(err(format!("unknown key '{key}'")))?
+            };
+            let val = val.trim().replace("\\n", "\n");
+            if key == "direction" && val != "ltr" && val != "rtl" {
+                Err(err(format!(
+                    "expected direction ltr or rtl, got '{val}'"
+                )))?;
+            }
+            if val.matches("{}").count() != english.matches("{}").count() {
+                Err(err(format!("'{key}' needs same values as '{english}'")))?;
+            }
+            texts.insert(key.to_string(), val);
+        }
+        Ok(Self { texts })
+    }
+
+    /// Gets code of the system language from the locale variables, like
+    /// `cs` from `cs_CZ.UTF-8`. The C locale is English.
+    fn system() -> String {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|v| env::var(v).ok())
+            .find(|v| !v.is_empty())
+            .unwrap_or_default();
+        let code = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        match code {
+            "" | "C" | "POSIX" => "en".into(),
+            code => code.to_lowercase(),
+        }
+    }
+}
+
+/// Sets the locale used by the whole app, it can be set only once
+pub fn set(locale: Locale) {
+    _ = LOCALE.set(locale);
+}
+
+/// Gets text of the user interface with the given key translated to the
+/// language of the locale
+pub fn tr(key: &str) -> &'static str {
+    if let Some(text) = LOCALE.get().and_then(|l| l.texts.get(key)) {
+        return text;
+    }
+    ENGLISH
+        .iter()
+        .find_map(|(k, text)| (*k == key).then_some(*text))
+        .unwrap_or("?")
+}
+
+/// Checks whether the language is written right-to-left
+pub fn rtl() -> bool {
+    tr("direction") == "rtl"
+}
+
+/// Gets translated text like [`tr`] with `{}` replaced by the given values
+pub fn trf(key: &str, values: &[&dyn Display]) -> String {
+    fill(tr(key), values)
+}
+
+/// Replaces `{}` in the text by the given values in order
+fn fill(text: &str, values: &[&dyn Display]) -> String {
+    let mut res = String::new();
+    for (i, part) in text.split("{}").enumerate() {
+        if let Some(value) = i.checked_sub(1).and_then(|i| values.get(i)) {
+            res.push_str(&value.to_string());
+        }
+        res.push_str(part);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_language_file() {
+        let locale = Locale::parse(
+            "# Czech\nstate.turn = na tahu.\nhelp.player = Hráč {}\n\
+            accessible.help = Příkazy:\\n  quit",
+        )
+        .unwrap();
+        assert_eq!(locale.texts["state.turn"], "na tahu.");
+        assert_eq!(locale.texts["accessible.help"], "Příkazy:\n  quit");
+        assert_eq!(fill(&locale.texts["help.player"], &[&2]), "Hráč 2");
+
+        let err = Locale::parse("\nstate.tutn = na tahu").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "language line 2: unknown key 'state.tutn'"
+        );
+        assert!(Locale::parse("status.zoom = Přiblížení").is_err());
+
+        assert_eq!(trf("status.resized", &[&5, &7]), "Board resized to 5x7");
+    }
+}
@@ -1,29 +1,179 @@
+use std::time::Duration;
+
+use accessible::Accessible;
 use app::App;
-use args::Args;
+use args::{Args, Subcommand};
+use bot::Bot;
+use config::Config;
+use engine::Registry;
 use error::Error;
+use locale::Locale;
+use record::Record;
+use recording::Recording;
 use termint::{enums::Color, widgets::StrSpanExtension};
+use theme::Theme;
+use tournament::Tournament;
+use weights::Weights;
 
+mod accessible;
+mod ai;
+mod analysis;
+mod annotations;
 mod app;
+mod arena;
 mod args;
+mod assist;
+mod bench;
+mod bitboard;
 mod board;
+mod board_row;
 mod board_tui;
+mod book;
+mod bot;
 mod cell;
+mod cells;
+mod clipboard;
+mod color_mode;
+mod command_line;
+mod completions;
+mod config;
+mod confirm;
+mod controls;
+mod daily;
+mod demo;
+mod engine;
 mod error;
+mod eval_bar;
+mod export;
+mod game;
+mod guard;
+mod input;
+mod json;
+mod locale;
+mod marks;
+mod mcts;
+mod mode;
+mod move_log;
+mod notation;
+mod overlay;
+mod palette;
+mod paths;
+mod pos;
+mod preset;
+mod record;
+mod recording;
+mod replay;
+mod rng;
+mod rules;
+#[cfg(test)]
+mod scenario;
+mod screen;
+mod series;
+mod server;
+mod signals;
+mod slots;
+mod sound;
+mod start_rule;
+mod stats;
+mod status;
+mod symmetry;
+mod table;
+mod theme;
+mod thinking;
+mod tournament;
+mod tune;
+mod weights;
+mod win_len;
+mod wizard;
+mod zoom;
 
 fn main() {
     if let Err(e) = run() {
         println!("{} {e}", "Error:".fg(Color::Red));
-        std::process::exit(1);
+        std::process::exit(e.code());
     }
 }
 
 fn run() -> Result<(), Error> {
-    let args = Args::parse(std::env::args())?;
+    // Engines selectable by `--ai`, custom engines are registered here
+    let mut args = Args::parse(std::env::args(), Registry::default())?;
+    locale::set(Locale::load(args.lang.as_deref())?);
+    weights::set(Weights::load_tuned()?);
     if args.help {
-        Args::help();
+        args.help();
         return Ok(());
     }
 
-    let mut app = App::new(args.size, args.win_len);
+    let mut tournament = None;
+    match &args.cmd {
+        Some(Subcommand::Arena {
+            players,
+            games,
+            sizes,
+            time,
+        }) => {
+            return arena::run(players, *games, sizes, *time, &args.engines);
+        }
+        Some(Subcommand::Bench { compare, save }) => {
+            return bench::run(compare.as_deref(), save.as_deref());
+        }
+        Some(Subcommand::Serve(port)) => {
+            return server::run(*port, &args.engines);
+        }
+        Some(Subcommand::Completions(shell)) => {
+            print!("{}", shell.completions(&args.engines));
+            return Ok(());
+        }
+        Some(Subcommand::Tune {
+            rounds,
+            games,
+            sizes,
+            save,
+        }) => return tune::run(*rounds, *games, sizes, save.as_deref()),
+        Some(Subcommand::Tournament(players)) => {
+            tournament = Some(Tournament::start(players)?);
+        }
+        None => {}
+    }
+
+    let (mut config, errors) = Config::load();
+    args.apply_preset(&config.presets)
+        .map_err(|e| e.arg(Some("--preset")))?;
+    if args.accessible {
+        return Accessible::new(&args, &config).run(&errors);
+    }
+    if let Some(theme) = &args.theme {
+        config.theme = Theme::load(theme)?;
+    }
+    let bot = match &args.bot {
+        Some(cmd) => {
+            Some(Bot::spawn(cmd, args.ai_time.unwrap_or(bot::DEFAULT_TIME))?)
+        }
+        None => None,
+    };
+    let record = match (&args.import, &args.load) {
+        (Some(path), _) => Some(Record::load(path)?),
+        (_, Some(name)) => Some(Record::load(slots::path(name)?)?),
+        _ => None,
+    };
+    let playback = args.playback.as_ref().map(Recording::load).transpose()?;
+    // Recorded seed makes the random boards and the AI the same
+    if let Some(seed) = playback.as_ref().and_then(|p| p.seed) {
+        args.seed.get_or_insert(seed);
+    }
+    let mut app = App::new(&args, config, errors);
+    if let Some(record) = record {
+        let delay = args.delay.map(|d| Duration::from_millis(d as u64));
+        app.import(&record, delay)?;
+    }
+    if let Some(playback) = playback {
+        app.play_back(playback, args.speed.unwrap_or(1.));
+    }
+    if let Some(bot) = bot {
+        app.play_bot(bot);
+    }
+    if let Some(tournament) = tournament {
+        app.start_tournament(tournament);
+    }
     app.run()
 }
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+use crate::{cell::Cell, error::Error};
+
+/// Glyphs the marks are drawn with on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Marks {
+    #[default]
+    Ascii,
+    /// Fullwidth letters, occupying two columns
+    Wide,
+    /// Emoji, occupying two columns
+    Emoji,
+}
+
+impl Marks {
+    /// All the mark styles
+    pub const ALL: &'static [Marks] =
+        &[Marks::Ascii, Marks::Wide, Marks::Emoji];
+
+    /// Gets name of the mark style
+    pub fn name(&self) -> &'static str {
+        match self {
+            Marks::Ascii => "ascii",
+            Marks::Wide => "wide",
+            Marks::Emoji => "emoji",
+        }
+    }
+
+    /// Gets glyph representing the cell
+    pub fn symbol(&self, cell: Cell) -> char {
+        match (self, cell) {
+            (_, Cell::Empty) => ' ',
+            (Marks::Ascii, cell) => cell.symbol(),
+            (Marks::Wide, Cell::Cross) => 'Ｘ',
+            (Marks::Wide, Cell::Circle) => 'Ｏ',
+            (Marks::Wide, Cell::Blocked) => '＃',
+            (Marks::Emoji, Cell::Cross) => '❌',
+            (Marks::Emoji, Cell::Circle) => '⭕',
+            (Marks::Emoji, Cell::Blocked) => '⬛',
+        }
+    }
+
+    /// Gets number of columns the glyphs occupy in the terminal
+    pub fn width(&self) -> usize {
+        match self {
+            Marks::Ascii => 1,
+            Marks::Wide | Marks::Emoji => 2,
+        }
+    }
+}
+
+impl FromStr for Marks {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Marks::ALL
+            .iter()
+            .find(|m| m.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "expected ascii, wide or emoji marks, got '{s}'"
+                ))
+            })
+    }
+}
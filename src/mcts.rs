@@ -0,0 +1,389 @@
+use std::time::{Duration, Instant};
+
+use crate::{board::Board, cell::Cell, rng::Rng, win_len::WinLen};
+
+/// Time of the search, when the AI isn't given time per move
+pub const TIME: Duration = Duration::from_millis(500);
+/// Most simulations of a single search, so small boards don't use all the
+/// time
+const MAX_SIMULATIONS: usize = 100_000;
+/// Most moves of a single playout, longer playouts are draws
+const MAX_PLAYOUT: usize = 200;
+/// Most moves tried in each node of the tree
+const WIDTH: usize = 10;
+/// Exploration constant of the UCT formula
+const EXPLORATION: f64 = 1.4;
+/// Directions of the lines: right, down, down-right and down-left
+const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
+
+/// Node of the search tree
+#[derive(Debug, Clone)]
+struct Node {
+    /// Index of the move leading to the node
+    id: usize,
+    /// Player who played the move
+    player: Cell,
+    children: Vec<usize>,
+    /// Moves which don't have their node yet
+    untried: Vec<usize>,
+    visits: u32,
+    /// Sum of the playout results of the player who played the move, win
+    /// counts as 1 and draw as 0.5
+    score: f64,
+    /// Result of the game ended by the move, [`Cell::Empty`] is a draw
+    end: Option<Cell>,
+}
+
+/// Monte Carlo tree search, which scores the moves by random playouts
+/// instead of evaluating the positions, so it plays on any board size
+pub struct Mcts<'a> {
+    cells: Vec<Cell>,
+    width: usize,
+    height: usize,
+    win_len: WinLen,
+    rng: &'a mut Rng,
+    nodes: Vec<Node>,
+    /// Moves played in the current simulation, so they can be taken back
+    played: Vec<usize>,
+}
+
+impl<'a> Mcts<'a> {
+    /// Creates new [`Mcts`] searching the given board
+    pub fn new(board: &Board, rng: &'a mut Rng) -> Self {
+        Self {
+            cells: board.cells.to_vec(),
+            width: board.size.x,
+            height: board.size.y,
+            win_len: board.win_len,
+            rng,
+            nodes: vec![],
+            played: vec![],
+        }
+    }
+
+    /// Runs simulations until the time runs out or there were `max` of
+    /// them. Returns index of the most visited move and the number of
+    /// simulations.
+    pub fn search(
+        &mut self,
+        player: Cell,
+        time: Duration,
+        max: usize,
+    ) -> (Option<usize>, usize) {
+        let start = Instant::now();
+        let untried = self.untried(player);
+        self.nodes = vec![Node::new(usize::MAX, player.next(), untried)];
+
+        let mut count = 0;
+        while count < max.min(MAX_SIMULATIONS) && start.elapsed() < time {
+            self.simulate();
+            count += 1;
+        }
+        let best = self.nodes[0]
+            .children
+            .iter()
+            .max_by_key(|c| self.nodes[**c].visits)
+            .map(|c| self.nodes[*c].id);
+        (
+            best.or_else(|| self.nodes[0].untried.first().copied()),
+            count,
+        )
+    }
+
+    /// Selects a node by the UCT formula, expands it, plays it out and
+    /// propagates the result back to the root
+    fn simulate(&mut self) {
+        let mut path = vec![0];
+        let mut node = 0;
+        while self.nodes[node].untried.is_empty()
+            && self.nodes[node].end.is_none()
+            && !self.nodes[node].children.is_empty()
+        {
+            node = self.select(node);
+            self.place(self.nodes[node].id, self.nodes[node].player);
+            path.push(node);
+        }
+
+        if self.nodes[node].end.is_none()
+            && !self.nodes[node].untried.is_empty()
+        {
+            let id = self.nodes[node].untried.pop().expect("node has moves");
+            let player = self.nodes[node].player.next();
+            self.place(id, player);
+            let child = self.expand(id, player);
+            self.nodes[node].children.push(child);
+            node = child;
+            path.push(node);
+        }
+
+        let result = match self.nodes[node].end {
+            Some(end) => end,
+            None => self.playout(self.nodes[node].player.next()),
+        };
+        for node in path {
+            let node = &mut self.nodes[node];
+            node.visits += 1;
+            node.score += match result {
+                Cell::Empty => 0.5,
+                r if r == node.player => 1.,
+                _ => 0.,
+            };
+        }
+        for id in self.played.drain(..) {
+            self.cells[id] = Cell::Empty;
+        }
+    }
+
+    /// Gets child of the node with the best UCT score
+    fn select(&self, node: usize) -> usize {
+        let ln = (self.nodes[node].visits.max(1) as f64).ln();
+        let uct = |c: &usize| {
+            let child = &self.nodes[*c];
+            let visits = child.visits.max(1) as f64;
+            child.score / visits + EXPLORATION * (ln / visits).sqrt()
+        };
+        *self.nodes[node]
+            .children
+            .iter()
+            .max_by(|a, b| uct(a).total_cmp(&uct(b)))
+            .expect("selected node has children")
+    }
+
+    /// Creates node of the move, which was just played
+    fn expand(&mut self, id: usize, player: Cell) -> usize {
+        let untried = self.untried(player.next());
+        let end = if self.wins_at(id, player) {
+            Some(player)
+        } else if untried.is_empty() {
+            Some(Cell::Empty)
+        } else {
+            None
+        };
+        let mut node = Node::new(id, player, untried);
+        node.end = end;
+        if end.is_some() {
+            node.untried.clear();
+        }
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Plays the game out starting with the given player. Player wins when
+    /// it can, blocks the opponent's win or plays randomly next to the
+    /// marks otherwise. Returns the winner, [`Cell::Empty`] on draw.
+    fn playout(&mut self, mut player: Cell) -> Cell {
+        let mut moves = self.moves();
+        for _ in 0..MAX_PLAYOUT {
+            moves.retain(|id| self.cells[*id] == Cell::Empty);
+            if moves.is_empty() {
+                return Cell::Empty;
+            }
+
+            let recent = self.played.iter().rev().take(2).copied();
+            let lines: Vec<_> = recent.flat_map(|id| self.lines(id)).collect();
+            let id = lines
+                .iter()
+                .find(|id| self.wins_at(**id, player))
+                .or_else(|| {
+                    lines.iter().find(|id| self.wins_at(**id, player.next()))
+                })
+                .copied()
+                .unwrap_or_else(|| moves[self.rng.range(moves.len())]);
+
+            self.place(id, player);
+            if self.wins_at(id, player) {
+                return player;
+            }
+            moves.extend(self.neighbours(id));
+            player = player.next();
+        }
+        Cell::Empty
+    }
+
+    /// Places the player's mark on the given index
+    fn place(&mut self, id: usize, player: Cell) {
+        self.cells[id] = player;
+        self.played.push(id);
+    }
+
+    /// Gets moves of the given player to be tried in the tree. Only the
+    /// winning move or the block of the opponent's win is tried, when
+    /// there's one.
+    fn untried(&self, player: Cell) -> Vec<usize> {
+        let moves = self.moves();
+        let forced = [player, player.next()].into_iter().find_map(|p| {
+            moves.iter().copied().find(|id| self.wins_at(*id, p))
+        });
+        if let Some(id) = forced {
+            return vec![id];
+        }
+
+        // Most promising moves are last, so they are tried first
+        let mut moves: Vec<_> = moves
+            .into_iter()
+            .map(|id| {
+                let score = self.strength(id, player)
+                    + self.strength(id, player.next()) * 9 / 10;
+                (score, id)
+            })
+            .collect();
+        moves.sort();
+        moves
+            .into_iter()
+            .rev()
+            .take(WIDTH)
+            .rev()
+            .map(|(_, id)| id)
+            .collect()
+    }
+
+    /// Gets strength of the lines the player's mark on given index would
+    /// be part of, longer lines with more open ends are stronger
+    fn strength(&self, id: usize, player: Cell) -> u64 {
+        DIRS.iter()
+            .map(|&(dx, dy)| {
+                let fwd = self.run(id, dx, dy, player);
+                let bwd = self.run(id, -dx, -dy, player);
+                let open = [(fwd, dx, dy), (bwd, -dx, -dy)]
+                    .into_iter()
+                    .filter(|(run, dx, dy)| {
+                        let i = *run as isize + 1;
+                        self.offset(id, dx * i, dy * i)
+                            .is_some_and(|c| self.cells[c] == Cell::Empty)
+                    })
+                    .count();
+                10_u64.pow((fwd + bwd + 1).min(9) as u32) * (open as u64 + 1)
+            })
+            .sum()
+    }
+
+    /// Gets empty cells next to any mark. When there are none, gets the
+    /// center or the first empty cell.
+    fn moves(&self) -> Vec<usize> {
+        let mut moves: Vec<_> = (0..self.cells.len())
+            .filter(|id| self.cells[*id] == Cell::Empty)
+            .filter(|id| {
+                self.around(*id, 1).any(|n| {
+                    matches!(self.cells[n], Cell::Cross | Cell::Circle)
+                })
+            })
+            .collect();
+        if moves.is_empty() {
+            let center = self.width / 2 + self.height / 2 * self.width;
+            let first = match self.cells.get(center) {
+                Some(Cell::Empty) => Some(center),
+                _ => self.cells.iter().position(|c| *c == Cell::Empty),
+            };
+            moves.extend(first);
+        }
+        moves
+    }
+
+    /// Gets empty cells next to the given index
+    fn neighbours(&self, id: usize) -> Vec<usize> {
+        self.around(id, 1)
+            .filter(|n| self.cells[*n] == Cell::Empty)
+            .collect()
+    }
+
+    /// Gets empty cells on the lines going through the given index, which
+    /// are close enough to complete a line with it
+    fn lines(&self, id: usize) -> Vec<usize> {
+        let mut cells = vec![];
+        for dir in DIRS {
+            let len = self.win_len.get(dir) as isize;
+            for i in (1 - len..len).filter(|i| *i != 0) {
+                if let Some(c) = self.offset(id, dir.0 * i, dir.1 * i) {
+                    if self.cells[c] == Cell::Empty {
+                        cells.push(c);
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Iterates over indexes of the cells in the given distance
+    fn around(&self, id: usize, dist: isize) -> impl Iterator<Item = usize> {
+        let cells: Vec<_> = (-dist..=dist)
+            .flat_map(|dy| (-dist..=dist).map(move |dx| (dx, dy)))
+            .filter(|d| *d != (0, 0))
+            .filter_map(|(dx, dy)| self.offset(id, dx, dy))
+            .collect();
+        cells.into_iter()
+    }
+
+    /// Checks whether the player's mark on given index completes a line
+    fn wins_at(&self, id: usize, player: Cell) -> bool {
+        DIRS.iter().any(|&(dx, dy)| {
+            self.run(id, dx, dy, player) + self.run(id, -dx, -dy, player) + 1
+                >= self.win_len.get((dx, dy))
+        })
+    }
+
+    /// Gets number of consecutive player's marks from the given index in
+    /// the given direction
+    fn run(&self, id: usize, dx: isize, dy: isize, player: Cell) -> usize {
+        (1..)
+            .map_while(|i| self.offset(id, dx * i, dy * i))
+            .take_while(|c| self.cells[*c] == player)
+            .count()
+    }
+
+    /// Gets index moved by the given offset, [`None`] when out of the board
+    fn offset(&self, id: usize, dx: isize, dy: isize) -> Option<usize> {
+        let x = (id % self.width) as isize + dx;
+        let y = (id / self.width) as isize + dy;
+        ((0..self.width as isize).contains(&x)
+            && (0..self.height as isize).contains(&y))
+        .then(|| x as usize + y as usize * self.width)
+    }
+}
+
+impl Node {
+    /// Creates new unvisited [`Node`]
+    fn new(id: usize, player: Cell, untried: Vec<usize>) -> Self {
+        Self {
+            id,
+            player,
+            children: vec![],
+            untried,
+            visits: 0,
+            score: 0.,
+            end: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Searches the board with fixed number of simulations
+    fn best(board: &Board, player: Cell) -> Option<usize> {
+        let mut rng = Rng::new(1);
+        Mcts::new(board, &mut rng)
+            .search(player, Duration::MAX, 400)
+            .0
+    }
+
+    #[test]
+    fn completes_and_blocks_lines() {
+        let mut board = Board::new(15, 15, 5);
+        for x in 3..7 {
+            board.cells.set(x + 7 * 15, Cell::Cross);
+        }
+        board.cells.set(2 + 7 * 15, Cell::Circle);
+        for y in 9..12 {
+            board.cells.set(10 + y * 15, Cell::Circle);
+        }
+        assert_eq!(best(&board, Cell::Cross), Some(7 + 7 * 15));
+        assert_eq!(best(&board, Cell::Circle), Some(7 + 7 * 15));
+    }
+
+    #[test]
+    fn empty_board_starts_in_center() {
+        let board = Board::new(9, 9, 4);
+        assert_eq!(best(&board, Cell::Cross), Some(4 + 4 * 9));
+    }
+}
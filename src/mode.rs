@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use crate::{cell::Cell, error::Error};
+
+/// Variant of the game deciding which marks players place and who wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Normal,
+    /// Both players place either mark, Order wins by completing a line of
+    /// any mark and Chaos by preventing it
+    OrderChaos,
+    /// Both players place either mark, player completing a line wins
+    Wild,
+    /// Both players place X on multiple boards, board with a line is dead
+    /// and player completing the last line loses
+    Notakto,
+}
+
+impl Mode {
+    /// All the game modes
+    pub const ALL: &'static [Mode] =
+        &[Mode::Normal, Mode::OrderChaos, Mode::Wild, Mode::Notakto];
+
+    /// Gets name of the mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::OrderChaos => "order-chaos",
+            Mode::Wild => "wild",
+            Mode::Notakto => "notakto",
+        }
+    }
+
+    /// Checks whether players choose the mark they place each turn
+    pub fn free_marks(&self) -> bool {
+        matches!(self, Mode::OrderChaos | Mode::Wild)
+    }
+
+    /// Gets mark placed by the player on turn, given the mark chosen by
+    /// the player, when players choose it
+    pub fn mark(&self, player: Cell, chosen: Cell) -> Cell {
+        match self {
+            Mode::Normal => player,
+            Mode::OrderChaos | Mode::Wild => chosen,
+            Mode::Notakto => Cell::Cross,
+        }
+    }
+
+    /// Gets name of the player identified by its starting mark, players
+    /// are called by their mark when it's [`None`]
+    pub fn player_name(&self, player: Cell) -> Option<&'static str> {
+        match (self, player) {
+            (Mode::OrderChaos, Cell::Cross) => Some("Order"),
+            (Mode::OrderChaos, Cell::Circle) => Some("Chaos"),
+            (Mode::Wild | Mode::Notakto, Cell::Cross) => Some("P1"),
+            (Mode::Wild | Mode::Notakto, Cell::Circle) => Some("P2"),
+            _ => None,
+        }
+    }
+
+    /// Gets player who won the game with the given board state after the
+    /// given player's move. Players are identified by their starting mark,
+    /// [`Cell::Empty`] is a draw.
+    pub fn winner(&self, state: Cell, last: Cell) -> Cell {
+        match self {
+            Mode::Normal => state,
+            Mode::Wild => match state {
+                Cell::Empty => Cell::Empty,
+                _ => last,
+            },
+            Mode::OrderChaos => match state {
+                Cell::Empty => Cell::Circle,
+                _ => Cell::Cross,
+            },
+            Mode::Notakto => last.next(),
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Mode::ALL
+            .iter()
+            .find(|m| m.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                let modes = "normal, order-chaos, wild or notakto";
+                Error::parse(format!("expected {modes} mode, got '{s}'"))
+            })
+    }
+}
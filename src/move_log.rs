@@ -0,0 +1,56 @@
+use termint::{
+    geometry::Constraint,
+    widgets::{Block, Span, StrSpanExtension},
+};
+
+use crate::{board::Board, locale::tr};
+
+/// Width of the move log panel including borders
+pub const LOG_WIDTH: usize = 16;
+
+/// Panel listing all the moves played in the game
+#[derive(Debug, Clone, Default)]
+pub struct MoveLog {
+    scroll: usize,
+}
+
+impl MoveLog {
+    /// Creates new [`MoveLog`] scrolled to the latest move
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scrolls back to the older moves
+    pub fn scroll_up(&mut self, len: usize) {
+        self.scroll = (self.scroll + 1).min(len.saturating_sub(1));
+    }
+
+    /// Scrolls to the newer moves
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    /// Renders the log of the given board's moves with given height
+    pub fn render(&self, board: &Board, height: usize) -> Block {
+        let lines = height.saturating_sub(2);
+        let end = board.history.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(lines);
+
+        let mut block = Block::vertical()
+            .title(tr("log.title").fg(board.theme.dim))
+            .border_color(board.theme.dim);
+        let visible = board.visible(board.viewer);
+        for (i, (cell, pos)) in board.history[start..end].iter().enumerate() {
+            // Moves hidden in the fog of war are listed without the cell
+            let name = match visible[pos.index(board.size.x)] {
+                true => board.cell_name(pos),
+                false => "?".into(),
+            };
+            let text =
+                format!("{:>3}. {} {name}", start + i + 1, cell.symbol());
+            let fg = board.theme.cell(*cell);
+            block.add_child(Span::new(text).fg(fg), Constraint::Length(1));
+        }
+        block
+    }
+}
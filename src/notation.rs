@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use crate::{board::Board, cell::Cell, error::Error, pos::Pos};
+
+/// Corner of the board the rows are numbered from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Origin {
+    #[default]
+    TopLeft,
+    BottomLeft,
+}
+
+impl Origin {
+    /// Gets name of the origin
+    pub fn name(&self) -> &'static str {
+        match self {
+            Origin::TopLeft => "top-left",
+            Origin::BottomLeft => "bottom-left",
+        }
+    }
+}
+
+impl FromStr for Origin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Origin::TopLeft, Origin::BottomLeft]
+            .into_iter()
+            .find(|o| o.name() == s)
+            .ok_or(Error::parse(format!("unknown numbering origin '{s}'")))
+    }
+}
+
+/// How the rows and columns are named on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Numbering {
+    pub origin: Origin,
+    /// Whether columns are lettered from the right and the row numbers
+    /// are on the right, for the right-to-left languages
+    pub rtl: bool,
+}
+
+impl Numbering {
+    /// Gets number of the row on given position of the board with given
+    /// height, counted from 1
+    pub fn row(&self, y: usize, height: usize) -> usize {
+        match self.origin {
+            Origin::TopLeft => y + 1,
+            Origin::BottomLeft => height - y,
+        }
+    }
+
+    /// Gets name of the column on given position of the board with given
+    /// width
+    pub fn col(&self, x: usize, width: usize) -> String {
+        match self.rtl {
+            true => col_name(width - 1 - x),
+            false => col_name(x),
+        }
+    }
+
+    /// Gets name of the cell on given position of the board with given
+    /// size
+    pub fn pos_name(&self, pos: &Pos, size: Pos) -> String {
+        format!("{}{}", self.col(pos.x, size.x), self.row(pos.y, size.y))
+    }
+
+    /// Parses name of the cell of the board with given size to its
+    /// position, cells outside of the board are [`None`]
+    pub fn parse_pos(&self, name: &str, size: Pos) -> Option<Pos> {
+        let pos = parse_pos(name).filter(|p| p.x < size.x && p.y < size.y)?;
+        let x = match self.rtl {
+            true => size.x - 1 - pos.x,
+            false => pos.x,
+        };
+        Some(Pos::new(x, self.row(pos.y, size.y) - 1))
+    }
+}
+
+/// Gets name of the column (a, b, ..., z, aa, ab, ...)
+pub fn col_name(mut x: usize) -> String {
+    let mut name = vec![];
+    loop {
+        name.push((b'a' + (x % 26) as u8) as char);
+        if x < 26 {
+            break;
+        }
+        x = x / 26 - 1;
+    }
+    name.iter().rev().collect()
+}
+
+/// Gets name of the cell on given position (e.g. `e3`), rows are numbered
+/// from 1 starting at the top. Records, the server and the bots always use
+/// these names, the screen uses the [`Numbering`] of the board.
+pub fn pos_name(pos: &Pos) -> String {
+    format!("{}{}", col_name(pos.x), pos.y + 1)
+}
+
+/// Parses name of the cell (e.g. `e3`) to its position
+pub fn parse_pos(name: &str) -> Option<Pos> {
+    let split = name.find(|c: char| !c.is_ascii_lowercase())?;
+    let (col, row) = name.split_at(split);
+    if col.is_empty() {
+        return None;
+    }
+
+    let x = col.bytes().try_fold(0usize, |x, c| {
+        x.checked_mul(26)?.checked_add((c - b'a' + 1) as usize)
+    })?;
+    let y = row.parse::<usize>().ok().filter(|y| *y > 0)?;
+    Some(Pos::new(x - 1, y - 1))
+}
+
+/// Gets the board as rows of characters with row and column names in its
+/// numbering, cells hidden from the viewer by the fog of war are `?`
+pub fn board_text(board: &Board) -> String {
+    let numbering = board.numbering;
+    let label = board.size.y.to_string().len();
+    let cols: Vec<_> = (0..board.size.x)
+        .map(|x| numbering.col(x, board.size.x))
+        .collect();
+    let visible = board.visible(board.viewer);
+    let mut text = match numbering.rtl {
+        true => format!("{}\n", cols.join(" ")),
+        false => format!("{:label$} {}\n", "", cols.join(" ")),
+    };
+    for (y, row) in board.rows().enumerate() {
+        let row: Vec<_> = row
+            .iter()
+            .zip(&cols)
+            .enumerate()
+            .map(|(x, (c, col))| {
+                let sym = match c {
+                    _ if !visible[x + y * board.size.x] => '?',
+                    Cell::Empty => '.',
+                    c => c.symbol(),
+                };
+                format!("{sym:<w$}", w = col.len())
+            })
+            .collect();
+        let num = numbering.row(y, board.size.y);
+        text += &match numbering.rtl {
+            true => format!("{} {num}\n", row.join(" ")),
+            false => format!("{num:>label$} {}\n", row.join(" ")),
+        };
+    }
+    text
+}
+
+impl Board {
+    /// Gets name of the cell on given position in the board's numbering
+    pub fn cell_name(&self, pos: &Pos) -> String {
+        self.numbering.pos_name(pos, self.size)
+    }
+
+    /// Parses name of the cell in the board's numbering to its position
+    pub fn parse_cell(&self, name: &str) -> Option<Pos> {
+        self.numbering.parse_pos(name, self.size)
+    }
+}
@@ -0,0 +1,63 @@
+use std::cmp::{max, min};
+
+use termint::{
+    buffer::Buffer,
+    geometry::{Coords, Rect},
+    widgets::Widget,
+};
+
+/// Renders widget centered on top of the base widget
+#[derive(Debug)]
+pub struct Overlay {
+    base: Box<dyn Widget>,
+    top: Box<dyn Widget>,
+}
+
+impl Overlay {
+    /// Creates new [`Overlay`] rendering `top` over the `base`
+    pub fn new<B, T>(base: B, top: T) -> Self
+    where
+        B: Into<Box<dyn Widget>>,
+        T: Into<Box<dyn Widget>>,
+    {
+        Self {
+            base: base.into(),
+            top: top.into(),
+        }
+    }
+}
+
+impl Widget for Overlay {
+    fn render(&self, buffer: &mut Buffer) {
+        self.base.render(buffer);
+
+        let size = buffer.size();
+        let width = min(self.top.width(&size), buffer.width());
+        let height = min(
+            self.top.height(&Coords::new(width, size.y)),
+            buffer.height(),
+        );
+        let mut tbuffer = Buffer::empty(Rect::new(
+            buffer.x() + (buffer.width() - width) / 2,
+            buffer.y() + (buffer.height() - height) / 2,
+            width,
+            height,
+        ));
+        self.top.render(&mut tbuffer);
+        buffer.union(tbuffer);
+    }
+
+    fn height(&self, size: &Coords) -> usize {
+        max(self.base.height(size), self.top.height(size))
+    }
+
+    fn width(&self, size: &Coords) -> usize {
+        max(self.base.width(size), self.top.width(size))
+    }
+}
+
+impl From<Overlay> for Box<dyn Widget> {
+    fn from(value: Overlay) -> Self {
+        Box::new(value)
+    }
+}
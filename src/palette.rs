@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+
+use termint::{
+    buffer::Buffer,
+    enums::Color,
+    geometry::{Constraint, Coords},
+    widgets::{Block, Layout, Spacer, StrSpanExtension, Widget},
+};
+
+use crate::{locale::tr, theme::Theme};
+
+/// Width of the palette content (without borders)
+const WIDTH: usize = 32;
+
+/// Represents command, which can be executed from the [`Palette`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    NewGame,
+    ResetScore,
+    Rules,
+    CopyBoard,
+    Zoom,
+    Labels,
+    Threats,
+    EvalBar,
+    Pause,
+    Load,
+    Quit,
+}
+
+impl Command {
+    /// All the commands in the order they are listed in
+    pub const ALL: &'static [Command] = &[
+        Command::NewGame,
+        Command::ResetScore,
+        Command::Rules,
+        Command::CopyBoard,
+        Command::Zoom,
+        Command::Labels,
+        Command::Threats,
+        Command::EvalBar,
+        Command::Pause,
+        Command::Load,
+        Command::Quit,
+    ];
+
+    /// Gets name of the command
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::NewGame => tr("command.new-game"),
+            Command::ResetScore => tr("command.reset-score"),
+            Command::Rules => tr("command.rules"),
+            Command::CopyBoard => tr("command.copy-board"),
+            Command::Zoom => tr("command.zoom"),
+            Command::Labels => tr("command.labels"),
+            Command::Threats => tr("command.threats"),
+            Command::EvalBar => tr("command.eval-bar"),
+            Command::Pause => tr("command.pause"),
+            Command::Load => tr("command.load"),
+            Command::Quit => tr("command.quit"),
+        }
+    }
+
+    /// Gets keybind, which executes the command directly
+    pub fn key(&self) -> &'static str {
+        match self {
+            Command::NewGame => "r",
+            Command::ResetScore => "R",
+            Command::Rules => "?",
+            Command::CopyBoard => "y",
+            Command::Zoom => "z",
+            Command::Labels => "n",
+            Command::Threats => "t",
+            Command::EvalBar => "b",
+            Command::Pause => "p",
+            Command::Load => "o",
+            Command::Quit => "q",
+        }
+    }
+}
+
+/// Command palette with fuzzy search over all the [`Command`]s
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    query: String,
+    selected: usize,
+    theme: Theme,
+}
+
+impl Palette {
+    /// Creates new [`Palette`] with empty query rendered using given theme
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            theme,
+            ..Self::default()
+        }
+    }
+
+    /// Adds character to the search query
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    /// Removes last character from the search query
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    /// Moves selection up
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down
+    pub fn down(&mut self) {
+        let len = self.matches().len();
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    /// Gets selected command
+    pub fn selected(&self) -> Option<Command> {
+        self.matches().get(self.selected).copied()
+    }
+
+    /// Gets commands matching the query, sorted from the best match
+    pub fn matches(&self) -> Vec<Command> {
+        let mut matches: Vec<_> = Command::ALL
+            .iter()
+            .filter_map(|c| {
+                fuzzy_score(&self.query, c.name()).map(|s| (s, *c))
+            })
+            .collect();
+        matches.sort_by_key(|(s, _)| Reverse(*s));
+        matches.into_iter().map(|(_, c)| c).collect()
+    }
+}
+
+impl Widget for Palette {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut block = Block::vertical()
+            .title(tr("command.title").fg(self.theme.accent))
+            .border_color(self.theme.dim);
+        block.add_child(format!("> {}_", self.query), Constraint::Length(1));
+
+        for (i, cmd) in self.matches().iter().enumerate() {
+            let fg = match i == self.selected {
+                true => self.theme.accent,
+                false => Color::Default,
+            };
+            let mut item = Layout::horizontal();
+            item.add_child(cmd.name().fg(fg), Constraint::Min(0));
+            item.add_child(Spacer::new(), Constraint::Fill);
+            item.add_child(cmd.key().fg(self.theme.dim), Constraint::Min(0));
+            block.add_child(item, Constraint::Length(1));
+        }
+        block.render(buffer);
+    }
+
+    fn height(&self, _size: &Coords) -> usize {
+        Command::ALL.len() + 3
+    }
+
+    fn width(&self, _size: &Coords) -> usize {
+        WIDTH + 2
+    }
+}
+
+impl From<Palette> for Box<dyn Widget> {
+    fn from(value: Palette) -> Self {
+        Box::new(value)
+    }
+}
+
+/// Gets fuzzy match score of the query in the given text. Query matches when
+/// all its characters are contained in the text in the same order. Higher
+/// score means better match.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut prev: Option<usize> = None;
+    let mut chars = text.chars().enumerate();
+    let text: Vec<char> = text.chars().collect();
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let q = q.to_ascii_lowercase();
+        let (id, _) = chars.find(|(_, c)| c.to_ascii_lowercase() == q)?;
+
+        score += 1;
+        if prev.is_some_and(|p| p + 1 == id) {
+            score += 3;
+        }
+        if id == 0 || text[id - 1] == ' ' {
+            score += 2;
+        }
+        prev = Some(id);
+    }
+    Some(score)
+}
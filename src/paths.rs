@@ -0,0 +1,61 @@
+use std::{env, path::PathBuf};
+
+/// Name of the app directory in the platform directories
+const APP: &str = "tictactoe";
+
+/// Environment variable overriding the config directory
+pub const CONFIG_ENV: &str = "TICTACTOE_CONFIG";
+/// Environment variable overriding the data directory
+pub const DATA_ENV: &str = "TICTACTOE_DATA";
+
+/// Gets directory of the config file. It's `$TICTACTOE_CONFIG` when set,
+/// otherwise `$XDG_CONFIG_HOME/tictactoe` or the platform default:
+/// `~/.config` on Linux, `~/Library/Application Support` on macOS and
+/// `%APPDATA%` on Windows.
+pub fn config_dir() -> Option<PathBuf> {
+    resolve(CONFIG_ENV, "XDG_CONFIG_HOME", || {
+        platform_dir(".config", "APPDATA")
+    })
+}
+
+/// Gets directory of the saved games, stats and tournament. It's
+/// `$TICTACTOE_DATA` when set, otherwise `$XDG_DATA_HOME/tictactoe` or the
+/// platform default: `~/.local/share` on Linux,
+/// `~/Library/Application Support` on macOS and `%APPDATA%` on Windows.
+pub fn data_dir() -> Option<PathBuf> {
+    resolve(DATA_ENV, "XDG_DATA_HOME", || {
+        platform_dir(".local/share", "APPDATA")
+    })
+}
+
+/// Gets the app directory from the override variable, the XDG variable or
+/// the platform default, in this order. Empty variables are ignored.
+fn resolve<F>(over: &str, xdg: &str, default: F) -> Option<PathBuf>
+where
+    F: FnOnce() -> Option<PathBuf>,
+{
+    if let Some(dir) = var(over) {
+        return Some(dir);
+    }
+    var(xdg).or_else(default).map(|dir| dir.join(APP))
+}
+
+/// Gets base directory of the platform, `unix` is relative to the home
+/// directory and `windows` is the variable containing the directory
+fn platform_dir(unix: &str, windows: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        return var(windows);
+    }
+    let home = var("HOME")?;
+    match cfg!(target_os = "macos") {
+        true => Some(home.join("Library").join("Application Support")),
+        false => Some(home.join(unix)),
+    }
+}
+
+/// Gets path from the environment variable, when it's set and not empty
+fn var(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
@@ -0,0 +1,141 @@
+use termint::geometry::Coords;
+
+/// Position of a cell on the board or size of the board, so the game logic
+/// doesn't depend on the TUI types
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    /// Creates new [`Pos`]
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Gets position of the cell with given index on the board of given
+    /// width, cells are indexed by rows
+    pub fn from_index(id: usize, width: usize) -> Self {
+        Self::new(id % width, id / width)
+    }
+
+    /// Gets index of the cell on the board of given width
+    pub fn index(&self, width: usize) -> usize {
+        self.x + self.y * width
+    }
+
+    /// Gets the neighbouring position in the given direction, [`None`]
+    /// when it's outside of the board of given size
+    pub fn step(&self, dir: (isize, isize), size: Pos) -> Option<Pos> {
+        self.offset(dir, 1, size)
+    }
+
+    /// Gets the position given number of steps away in the given direction,
+    /// [`None`] when it's outside of the board of given size
+    pub fn offset(
+        &self,
+        (dx, dy): (isize, isize),
+        steps: usize,
+        size: Pos,
+    ) -> Option<Pos> {
+        let steps = steps as isize;
+        let x = self
+            .x
+            .checked_add_signed(dx * steps)
+            .filter(|x| *x < size.x)?;
+        let y = self
+            .y
+            .checked_add_signed(dy * steps)
+            .filter(|y| *y < size.y)?;
+        Some(Pos::new(x, y))
+    }
+
+    /// Iterates over the positions from this one in the given direction
+    /// until the edge of the board of given size
+    pub fn ray(
+        self,
+        dir: (isize, isize),
+        size: Pos,
+    ) -> impl Iterator<Item = Pos> {
+        std::iter::successors(
+            Some(self).filter(|p| p.inside(size)),
+            move |p| p.step(dir, size),
+        )
+    }
+
+    /// Checks whether the position is on the board of given size
+    pub fn inside(&self, size: Pos) -> bool {
+        self.x < size.x && self.y < size.y
+    }
+
+    /// Iterates over all the positions on the board of given size by rows
+    pub fn all(size: Pos) -> impl Iterator<Item = Pos> {
+        (0..size.x * size.y).map(move |id| Pos::from_index(id, size.x))
+    }
+
+    /// Iterates over the positions in the row with given index
+    pub fn row(y: usize, size: Pos) -> impl Iterator<Item = Pos> {
+        Pos::new(0, y).ray((1, 0), size)
+    }
+
+    /// Iterates over the positions in the column with given index
+    pub fn col(x: usize, size: Pos) -> impl Iterator<Item = Pos> {
+        Pos::new(x, 0).ray((0, 1), size)
+    }
+
+    /// Iterates over the diagonals going down, to the left when `anti`.
+    /// Each diagonal is iterated from its top cell.
+    pub fn diagonals(
+        anti: bool,
+        size: Pos,
+    ) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        let (edge, dir) = match anti {
+            true => (size.x.saturating_sub(1), (-1, 1)),
+            false => (0, (1, 1)),
+        };
+        let top = (0..size.x).map(|x| Pos::new(x, 0));
+        let side = (1..size.y).map(move |y| Pos::new(edge, y));
+        top.chain(side).map(move |start| start.ray(dir, size))
+    }
+}
+
+impl From<Coords> for Pos {
+    fn from(value: Coords) -> Self {
+        Self::new(value.x, value.y)
+    }
+}
+
+impl From<Pos> for Coords {
+    fn from(value: Pos) -> Self {
+        Coords::new(value.x, value.y)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_lines_of_the_board() {
+        let size = Pos::new(3, 2);
+        let row: Vec<_> = Pos::row(1, size).collect();
+        assert_eq!(row, [Pos::new(0, 1), Pos::new(1, 1), Pos::new(2, 1)]);
+        assert_eq!(Pos::col(2, size).count(), 2);
+        assert_eq!(Pos::all(size).nth(4), Some(Pos::new(1, 1)));
+
+        let anti: Vec<_> = Pos::new(2, 0).ray((-1, 1), size).collect();
+        assert_eq!(anti, [Pos::new(2, 0), Pos::new(1, 1)]);
+        let lens: Vec<_> =
+            Pos::diagonals(true, size).map(|d| d.count()).collect();
+        assert_eq!(lens, [1, 2, 2, 1]);
+        assert_eq!(Pos::new(0, 0).step((-1, 0), size), None);
+        assert_eq!(Pos::from_index(5, 3).index(3), 5);
+    }
+}
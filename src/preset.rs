@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use crate::{arena::parse_size, error::Error, mode::Mode, pos::Pos};
+
+/// Named board size, win length and mode selected by `--preset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    pub size: Pos,
+    pub win_len: usize,
+    pub mode: Mode,
+}
+
+impl Preset {
+    /// Names of the built-in presets
+    pub const NAMES: &'static [&'static str] =
+        &["classic", "gomoku", "connect6", "big"];
+
+    /// Creates new [`Preset`] of the normal mode
+    const fn new(w: usize, h: usize, win_len: usize) -> Self {
+        Self {
+            size: Pos::new(w, h),
+            win_len,
+            mode: Mode::Normal,
+        }
+    }
+
+    /// Gets built-in preset with the given name
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "classic" => Some(Self::new(3, 3, 3)),
+            "gomoku" => Some(Self::new(15, 15, 5)),
+            "connect6" => Some(Self::new(19, 19, 6)),
+            "big" => Some(Self::new(25, 25, 5)),
+            _ => None,
+        }
+    }
+
+    /// Finds preset with the given name, presets of the user override the
+    /// built-in ones
+    pub fn find(name: &str, user: &[(String, Preset)]) -> Option<Self> {
+        user.iter()
+            .find_map(|(n, p)| (n == name).then_some(*p))
+            .or_else(|| Self::built_in(name))
+    }
+}
+
+impl FromStr for Preset {
+    type Err = Error;
+
+    /// Parses preset from size with optional win length and mode, like
+    /// `9x9:4 wild`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (size, mode) = s.split_once(' ').unwrap_or((s, "normal"));
+        let (w, h, win_len) = parse_size(size)?;
+        Ok(Self {
+            size: Pos::new(w, h),
+            win_len,
+            mode: mode.trim().parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_presets_override_built_in() {
+        let user = vec![
+            ("gomoku".to_string(), "19x19:5".parse().unwrap()),
+            ("wild".to_string(), "9x9:4 wild".parse().unwrap()),
+        ];
+        assert_eq!(Preset::find("gomoku", &user).unwrap().size.x, 19);
+        assert_eq!(Preset::find("wild", &user).unwrap().mode, Mode::Wild);
+        assert_eq!(
+            Preset::find("classic", &user),
+            Preset::built_in("classic")
+        );
+        assert_eq!(Preset::find("tiny", &user), None);
+        assert!("9x9:4 chess".parse::<Preset>().is_err());
+    }
+}
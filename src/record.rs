@@ -0,0 +1,445 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    board::Board,
+    cell::Cell,
+    daily,
+    error::Error,
+    mode::Mode,
+    notation::{parse_pos, pos_name},
+    paths,
+    pos::Pos,
+    rules::Rules,
+    start_rule::StartRule,
+    win_len::WinLen,
+};
+
+/// Extension of the game record files
+pub const EXTENSION: &str = "ttt";
+
+/// Gets path to the file the game in progress is saved to, when the app
+/// is ended by a signal
+pub fn autosave_path() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join(format!("autosave.{EXTENSION}")))
+}
+
+/// Human-readable record of the game inspired by PGN. Metadata tags like
+/// `[Size "7x7"]` are followed by the numbered moves and the result:
+///
+/// ```text
+/// [Date "2026-10-15"]
+/// [X "Alice"]
+/// [O "Bob"]
+/// [Size "3x3"]
+/// [Win "3"]
+/// [Rules "size=3x3 win=3 blocks=0 swap-rule=false start=alternate"]
+/// [Result "1-0"]
+///
+/// 1. b2 a1 2. c1 a3 3. a2 c2 4. c3 1-0
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+    /// Metadata as name and value pairs in the written order
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<Pos>,
+}
+
+impl Record {
+    /// Creates [`Record`] of the game on the board with given rules played
+    /// by the players with given names (cross first) with given winner,
+    /// [`Cell::Empty`] is a draw and [`None`] unfinished game
+    pub fn new(
+        board: &Board,
+        rules: &Rules,
+        players: [&str; 2],
+        winner: Option<Cell>,
+    ) -> Self {
+        let mut rec = Record::default();
+        rec.add("Date", daily::date(daily::current_day()));
+        rec.add("X", players[0]);
+        rec.add("O", players[1]);
+        rec.add("Size", format!("{}x{}", board.size.x, board.size.y));
+        let win = &board.win_len;
+        rec.add(
+            "Win",
+            match win.uniform() {
+                Some(len) => len.to_string(),
+                None => format!("{} {} {}", win.rows, win.cols, win.diag),
+            },
+        );
+
+        let blocked: Vec<_> = (0..board.cells.len())
+            .filter(|id| board.cells[*id] == Cell::Blocked)
+            .map(|id| pos_name(&Pos::from_index(id, board.size.x)))
+            .collect();
+        if !blocked.is_empty() {
+            rec.add("Blocked", blocked.join(" "));
+        }
+        if let Some(pos) = board.handicap.first() {
+            let player = board.cells[pos.index(board.size.x)];
+            let cells: Vec<_> = board.handicap.iter().map(pos_name).collect();
+            rec.add(
+                "Handicap",
+                format!("{} {}", player.symbol(), cells.join(" ")),
+            );
+        }
+        if let Some(decay) = rules.decay {
+            rec.add("Decay", decay.to_string());
+        }
+        if rules.points {
+            rec.add("Points", "true");
+        }
+        if let Some(fog) = rules.fog {
+            rec.add("Fog", fog.to_string());
+        }
+        if rules.swap_rule {
+            rec.add("Swap", "true");
+        }
+        if rules.start_rule != StartRule::default() {
+            rec.add("Start", rules.start_rule.name());
+        }
+        if let Some(best_of) = rules.best_of {
+            rec.add("BestOf", best_of.to_string());
+        }
+        if let Some(seed) = rules.seed {
+            rec.add("Seed", seed.to_string());
+        }
+        // Checked on import, so the game is continued with the same rules
+        rec.add("Rules", rules.summary());
+        if let Some((Cell::Circle, _)) = board.history.first() {
+            rec.add("First", "O");
+        }
+        rec.add("Result", Record::result(winner));
+        rec.moves = board.history.iter().map(|(_, pos)| *pos).collect();
+        rec
+    }
+
+    /// Loads record from the given file, its board and moves are checked
+    /// so errors of the record tell the file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).map_err(|e| Error::load(path, e))?;
+        let rec = Record::parse(&text).map_err(|e| Error::load(path, e))?;
+        rec.rules().map_err(|e| Error::load(path, e))?;
+        Ok(rec)
+    }
+
+    /// Saves record to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|e| Error::save(path, e))?;
+        Ok(())
+    }
+
+    /// Parses record from the given text
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut rec = Record::default();
+        for line in text.lines().map(str::trim) {
+            if let Some(tag) = line.strip_prefix('[') {
+                let (name, val) = tag
+                    .strip_suffix(']')
+                    .and_then(|t| t.split_once(' '))
+                    .ok_or_else(|| {
+                        Error::parse(format!("record: invalid tag '{line}'"))
+                    })?;
+                let val = val.trim().trim_matches('"');
+                rec.add(name, val);
+                continue;
+            }
+
+            for token in line.split_whitespace() {
+                if token.ends_with('.') || RESULTS.contains(&token) {
+                    continue;
+                }
+                let pos = parse_pos(token).ok_or_else(|| {
+                    Error::parse(format!("record: invalid move '{token}'"))
+                })?;
+                rec.moves.push(pos);
+            }
+        }
+        Ok(rec)
+    }
+
+    /// Gets value of the tag with given name
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Creates board set up as before the first move and gets the player
+    /// who played the first move
+    pub fn board(&self) -> Result<(Board, Cell), Error> {
+        let size = self
+            .tag("Size")
+            .ok_or_else(|| Error::parse("record: missing size"))?;
+        let (w, h) = size
+            .split_once('x')
+            .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+            .filter(|(w, h): &(usize, usize)| *w >= 3 && *h >= 3)
+            .ok_or_else(|| {
+                Error::parse(format!("record: invalid size '{size}'"))
+            })?;
+
+        let win = self
+            .tag("Win")
+            .ok_or_else(|| Error::parse("record: missing win length"))?;
+        let lens = win
+            .split_whitespace()
+            .map(|l| l.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>();
+        let win_len = match lens.as_deref() {
+            Some([len]) => WinLen::new(*len),
+            Some([rows, cols, diag]) => {
+                WinLen::new(*rows).with(Some(*rows), Some(*cols), Some(*diag))
+            }
+            _ => Err(Error::parse(format!(
+                "record: invalid win length '{win}'"
+            )))?,
+        };
+        win_len
+            .check(w, h)
+            .map_err(|e| Error::parse(format!("record: {e}")))?;
+        let mut board = Board::new(w, h, win_len.rows);
+        board.win_len = win_len;
+        board.points = self.tag("Points") == Some("true");
+        board.decay = self.num_tag("Decay")?;
+        board.fog = self.num_tag("Fog")?;
+
+        let cell =
+            |name: &str| {
+                parse_pos(name).filter(|p| p.x < w && p.y < h).ok_or_else(
+                    || Error::parse(format!("record: invalid cell '{name}'")),
+                )
+            };
+        for name in self.tag("Blocked").unwrap_or_default().split_whitespace()
+        {
+            let pos = cell(name)?;
+            board.cells.set(pos.x + pos.y * w, Cell::Blocked);
+        }
+        let mut handicap =
+            self.tag("Handicap").unwrap_or_default().split_whitespace();
+        let weaker = match handicap.next() {
+            Some("O") => Cell::Circle,
+            _ => Cell::Cross,
+        };
+        for name in handicap {
+            let pos = cell(name)?;
+            board.cells.set(pos.x + pos.y * w, weaker);
+            board.handicap.push(pos);
+        }
+
+        let first = match self.tag("First") {
+            Some("O") => Cell::Circle,
+            _ => Cell::Cross,
+        };
+        self.check(&board, first)?;
+        Ok((board, first))
+    }
+
+    /// Gets rules the recorded game was played with, they have to match
+    /// the rule summary, when it's recorded
+    pub fn rules(&self) -> Result<Rules, Error> {
+        let (board, _) = self.board()?;
+        let start_rule = match self.tag("Start") {
+            Some(rule) => rule
+                .parse()
+                .map_err(|e| Error::parse(format!("record: {e}")))?,
+            None => StartRule::default(),
+        };
+        let rules = Rules {
+            size: board.size,
+            win_len: board.win_len,
+            blocks: board
+                .cells
+                .iter()
+                .filter(|c| **c == Cell::Blocked)
+                .count(),
+            handicap: board.handicap.len(),
+            seed: self
+                .tag("Seed")
+                .map(str::parse)
+                .transpose()
+                .map_err(|_| Error::parse("record: invalid seed"))?,
+            swap_rule: self.tag("Swap") == Some("true"),
+            points: board.points,
+            infinite: false,
+            mode: Mode::Normal,
+            boards: 0,
+            pieces: None,
+            decay: board.decay,
+            fog: board.fog,
+            best_of: self.num_tag("BestOf")?,
+            start_rule,
+        };
+        match self.tag("Rules") {
+            Some(summary) if summary != rules.summary() => Err(Error::parse(
+                format!("record: tags don't match the rules '{summary}'"),
+            )),
+            _ => Ok(rules),
+        }
+    }
+
+    /// Gets value of the numeric tag with given name
+    fn num_tag(&self, name: &str) -> Result<Option<usize>, Error> {
+        self.tag(name)
+            .map(|val| {
+                val.parse().map_err(|_| {
+                    let name = name.to_lowercase();
+                    Error::parse(format!("record: invalid {name} '{val}'"))
+                })
+            })
+            .transpose()
+    }
+
+    /// Checks whether all the moves can be played on the given board
+    fn check(&self, board: &Board, mut player: Cell) -> Result<(), Error> {
+        let mut board = board.clone();
+        for (i, pos) in self.moves.iter().enumerate() {
+            let name = pos_name(pos);
+            if pos.x >= board.size.x || pos.y >= board.size.y {
+                Err(Error::parse(format!(
+                    "record: move {} ({name}) is off board",
+                    i + 1
+                )))?;
+            }
+            if let Err(e) = board.legality(*pos) {
+                Err(Error::parse(format!(
+                    "record: move {} ({name}) can't be played, {}",
+                    i + 1,
+                    e.reason()
+                )))?;
+            }
+            _ = board.set(player, pos.x, pos.y);
+            player = player.next();
+        }
+        Ok(())
+    }
+
+    /// Adds tag with given name and value
+    fn add<T: Into<String>>(&mut self, name: &str, val: T) {
+        self.tags.push((name.to_string(), val.into()));
+    }
+
+    /// Gets result of the game with given winner
+    fn result(winner: Option<Cell>) -> &'static str {
+        match winner {
+            Some(Cell::Cross) => RESULTS[0],
+            Some(Cell::Circle) => RESULTS[1],
+            Some(_) => RESULTS[2],
+            None => RESULTS[3],
+        }
+    }
+}
+
+/// Results of the game: cross wins, circle wins, draw and unfinished
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (name, val) in &self.tags {
+            writeln!(f, "[{name} \"{val}\"]")?;
+        }
+        writeln!(f)?;
+
+        let mut line = String::new();
+        for (i, pos) in self.moves.iter().enumerate() {
+            let num = match i % 2 {
+                0 => format!("{}. ", i / 2 + 1),
+                _ => String::new(),
+            };
+            let token = format!("{num}{}", pos_name(pos));
+            // Lines are wrapped like in PGN
+            if !line.is_empty() && line.len() + token.len() >= 80 {
+                writeln!(f, "{line}")?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        let result = self.tag("Result").unwrap_or(RESULTS[3]);
+        match line.is_empty() {
+            true => writeln!(f, "{result}"),
+            false => writeln!(f, "{line} {result}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::Game, series::Series};
+
+    #[test]
+    fn record_round_trip() {
+        let mut board = Board::new(4, 4, 3);
+        board.cells.set(15, Cell::Blocked);
+        let moves = [(1, 1), (0, 0), (2, 1), (0, 1), (3, 1)];
+        let mut player = Cell::Cross;
+        for (x, y) in moves {
+            board.set(player, x, y).unwrap();
+            player = player.next();
+        }
+
+        let rules = Game::new(board.clone(), Mode::Normal).rules();
+        let rec = Record::new(&board, &rules, ["Alice", "Bob"], board.state());
+        let text = rec.to_string();
+        assert!(text.contains("[Blocked \"d4\"]"));
+        assert!(text.contains("1. b2 a1 2. c2 a2 3. d2 1-0"));
+
+        let parsed = Record::parse(&text).unwrap();
+        assert_eq!(parsed, rec);
+        let (mut replayed, first) = parsed.board().unwrap();
+        assert_eq!(first, Cell::Cross);
+        assert_eq!(replayed.cells[15], Cell::Blocked);
+        for (i, pos) in parsed.moves.iter().enumerate() {
+            let player = if i % 2 == 0 {
+                Cell::Cross
+            } else {
+                Cell::Circle
+            };
+            replayed.set(player, pos.x, pos.y).unwrap();
+        }
+        assert_eq!(replayed.state(), Some(Cell::Cross));
+    }
+
+    #[test]
+    fn rejects_illegal_moves() {
+        let rec = Record::parse("[Size \"3x3\"]\n[Win \"3\"]\n\n1. b2 b2 *")
+            .unwrap();
+        assert!(rec.board().is_err());
+
+        let rec = Record::parse("[Size \"3x3\"]\n[Win \"2\"]\n\n*").unwrap();
+        assert!(rec.board().is_err());
+        let rec = Record::parse("[Size \"3x3\"]\n[Win \"4\"]\n\n*").unwrap();
+        assert!(rec.board().is_err());
+    }
+
+    #[test]
+    fn rules_round_trip() {
+        let mut game = Game::new(Board::new(5, 5, 4), Mode::Normal);
+        game.fog = Some(2);
+        game.swap_rule = true;
+        game.start_rule = StartRule::LoserStarts;
+        game.series = Some(Series::new(3));
+        game.set_board(game.new_board(5, 5));
+
+        let rules = game.rules();
+        let rec = Record::new(&game.board, &rules, ["A", "B"], None);
+        let text = rec.to_string();
+        assert!(text.contains(&format!("[Rules \"{}\"]", rules.summary())));
+        let parsed = Record::parse(&text).unwrap();
+        assert_eq!(parsed.rules().unwrap().summary(), rules.summary());
+
+        let changed = text.replace("[Fog \"2\"]", "[Fog \"3\"]");
+        assert!(Record::parse(&changed).unwrap().rules().is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{error::Error, input::Action};
+
+/// Actions of the session with the delays between them, which can be
+/// saved to a file and played back
+#[derive(Debug, Clone)]
+pub struct Recording {
+    /// Seed of the random generators, so the random boards and the AI are
+    /// the same when played back
+    pub seed: Option<u64>,
+    pub actions: Vec<(Duration, Action)>,
+    /// Time of the last recorded action
+    last: Instant,
+}
+
+impl Recording {
+    /// Creates new empty [`Recording`] of the session with given seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            actions: vec![],
+            last: Instant::now(),
+        }
+    }
+
+    /// Records the action with the delay since the previous one
+    pub fn push(&mut self, action: Action) {
+        self.actions.push((self.last.elapsed(), action));
+        self.last = Instant::now();
+    }
+
+    /// Loads recording from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).map_err(|e| Error::load(path, e))?;
+        Recording::parse(&text).map_err(|e| Error::load(path, e))
+    }
+
+    /// Saves recording to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|e| Error::save(path, e))?;
+        Ok(())
+    }
+
+    /// Parses recording from the given text. Each line contains the delay
+    /// in milliseconds and the action, except the optional seed line.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut rec = Self {
+            seed: None,
+            actions: vec![],
+            last: Instant::now(),
+        };
+        for (i, line) in text.lines().map(str::trim).enumerate() {
+            let err = |msg: String| {
+                Error::parse(format!("recording line {}: {msg}", i + 1))
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (first, rest) = line.split_once(' ').unwrap_or((line, ""));
+            if first == "seed" {
+                let seed = rest
+                    .parse()
+                    .map_err(|_| err(format!("invalid seed '{rest}'")))?;
+                rec.seed = Some(seed);
+                continue;
+            }
+
+            let delay = first
+                .parse()
+                .map_err(|_| err(format!("invalid delay '{first}'")))?;
+            let action =
+                rest.trim().parse().map_err(|e: Error| err(e.to_string()))?;
+            rec.actions.push((Duration::from_millis(delay), action));
+        }
+        Ok(rec)
+    }
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(seed) = self.seed {
+            writeln!(f, "seed {seed}")?;
+        }
+        for (delay, action) in self.actions.iter() {
+            writeln!(f, "{} {action}", delay.as_millis())?;
+        }
+        Ok(())
+    }
+}
+
+/// [`Recording`] played back with its delays divided by the speed
+#[derive(Debug, Clone)]
+pub struct Playback {
+    actions: VecDeque<(Duration, Action)>,
+    speed: f64,
+    last: Instant,
+}
+
+impl Playback {
+    /// Creates new [`Playback`] of the recording with given speed
+    pub fn new(recording: Recording, speed: f64) -> Self {
+        Self {
+            actions: recording.actions.into(),
+            speed,
+            last: Instant::now(),
+        }
+    }
+
+    /// Gets the next action, when its delay since the previous one passed
+    pub fn next_action(&mut self) -> Option<Action> {
+        if !self.remaining().is_zero() {
+            return None;
+        }
+        self.last = Instant::now();
+        self.actions.pop_front().map(|(_, action)| action)
+    }
+
+    /// Checks whether all the actions were played back
+    pub fn finished(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Gets time remaining until the next action
+    pub fn remaining(&self) -> Duration {
+        let Some((delay, _)) = self.actions.front() else {
+            return Duration::ZERO;
+        };
+        delay
+            .div_f64(self.speed)
+            .saturating_sub(self.last.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+
+    #[test]
+    fn parses_recording() {
+        let text = "seed 42\n0 move-up\n120 key enter\n# end\n1500 exit\n";
+        let rec = Recording::parse(text).unwrap();
+        assert_eq!(rec.seed, Some(42));
+        assert_eq!(
+            rec.actions,
+            [
+                (Duration::ZERO, Action::MoveUp),
+                (
+                    Duration::from_millis(120),
+                    Action::Key(KeyEvent::from(KeyCode::Enter))
+                ),
+                (Duration::from_millis(1500), Action::Exit),
+            ]
+        );
+        assert_eq!(rec.to_string(), text.replace("# end\n", ""));
+
+        let err = Recording::parse("seed 1\n\n10 jump").unwrap_err();
+        assert_eq!(err.to_string(), "recording line 3: unknown action 'jump'");
+
+        let mut playback = Playback::new(rec, 2.);
+        assert_eq!(playback.next_action(), Some(Action::MoveUp));
+        assert!(playback.remaining() <= Duration::from_millis(60));
+        assert!(!playback.finished());
+    }
+}
@@ -0,0 +1,47 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::pos::Pos;
+
+/// Default delay between the replayed moves
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+
+/// Moves of the imported game played one by one with a delay
+#[derive(Debug, Clone)]
+pub struct Replay {
+    moves: VecDeque<Pos>,
+    delay: Duration,
+    last: Instant,
+}
+
+impl Replay {
+    /// Creates new [`Replay`] of the given moves
+    pub fn new(moves: Vec<Pos>, delay: Option<Duration>) -> Self {
+        Self {
+            moves: moves.into(),
+            delay: delay.unwrap_or(DEFAULT_DELAY),
+            last: Instant::now(),
+        }
+    }
+
+    /// Gets the next move, when the delay since the previous one passed
+    pub fn next_move(&mut self) -> Option<Pos> {
+        if self.last.elapsed() < self.delay {
+            return None;
+        }
+        self.last = Instant::now();
+        self.moves.pop_front()
+    }
+
+    /// Checks whether all the moves were replayed
+    pub fn finished(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Gets time remaining until the next move
+    pub fn remaining(&self) -> Duration {
+        self.delay.saturating_sub(self.last.elapsed())
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Simple pseudo random number generator (xorshift64*)
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates new [`Rng`] with given seed
+    pub fn new(seed: u64) -> Self {
+        // Mixes the seed so similar seeds give different sequences
+        let mut state = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        state ^= state >> 31;
+        Self {
+            state: state.max(1),
+        }
+    }
+
+    /// Creates new [`Rng`] seeded by the current time
+    pub fn from_time() -> Self {
+        Self::new(Self::time_seed())
+    }
+
+    /// Gets seed based on the current time
+    pub fn time_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    }
+
+    /// Gets next random number
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Gets random number in range from 0 to 1 (exclusive)
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Gets random number in range from 0 to `max` (exclusive)
+    pub fn range(&mut self, max: usize) -> usize {
+        match max {
+            0 => 0,
+            max => (self.next_u64() % max as u64) as usize,
+        }
+    }
+}
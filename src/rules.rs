@@ -0,0 +1,153 @@
+use crate::{
+    board::DECAY_ROUNDS,
+    locale::{tr, trf},
+    mode::Mode,
+    pos::Pos,
+    start_rule::StartRule,
+    win_len::WinLen,
+};
+
+/// Rules of the game currently in effect
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub size: Pos,
+    pub win_len: WinLen,
+    pub blocks: usize,
+    pub handicap: usize,
+    /// Seed the random board was generated with
+    pub seed: Option<u64>,
+    pub swap_rule: bool,
+    pub points: bool,
+    /// Whether the board grows, when a mark is placed near its edge
+    pub infinite: bool,
+    pub mode: Mode,
+    /// Number of boards of the multi-board variant
+    pub boards: usize,
+    /// Number of pieces of each player, which are moved once placed
+    pub pieces: Option<usize>,
+    /// Number of further moves after which a placed mark disappears
+    pub decay: Option<usize>,
+    /// Distance from own marks within which the player sees the board
+    pub fog: Option<usize>,
+    pub best_of: Option<usize>,
+    pub start_rule: StartRule,
+}
+
+impl Rules {
+    /// Gets description of the rules, one rule per line
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines =
+            vec![trf("rules.board", &[&self.size.x, &self.size.y])];
+        match self.points {
+            _ if self.mode == Mode::OrderChaos => lines.extend([
+                tr("rules.free-marks").into(),
+                trf("rules.order-wins", &[&self.win_len.describe()]),
+                tr("rules.chaos-wins").into(),
+            ]),
+            _ if self.mode == Mode::Notakto => lines.extend([
+                trf("rules.boards", &[&self.boards]),
+                trf("rules.dead-board", &[&self.win_len.describe()]),
+                tr("rules.notakto-lose").into(),
+            ]),
+            _ if self.mode == Mode::Wild => lines.extend([
+                tr("rules.free-marks").into(),
+                trf("rules.wild-win", &[&self.win_len.describe()]),
+                tr("rules.wild-draw").into(),
+            ]),
+            true => lines.extend([
+                trf("rules.line", &[&self.win_len.describe()]),
+                tr("rules.points-win").into(),
+                tr("rules.points-draw").into(),
+            ]),
+            _ if self.decay.is_some() => lines.extend([
+                trf("rules.win", &[&self.win_len.describe()]),
+                trf(
+                    "rules.decay-draw",
+                    &[&(self.size.x * self.size.y * DECAY_ROUNDS)],
+                ),
+            ]),
+            _ if self.infinite => lines.extend([
+                trf("rules.win", &[&self.win_len.describe()]),
+                tr("rules.infinite").into(),
+            ]),
+            false => lines.extend([
+                trf("rules.win", &[&self.win_len.describe()]),
+                tr("rules.draw").into(),
+            ]),
+        }
+        if self.blocks > 0 {
+            lines.push(trf("rules.blocks", &[&self.blocks]));
+        }
+        if self.handicap > 0 {
+            lines.push(trf("rules.handicap", &[&self.handicap]));
+        }
+        if let Some(pieces) = self.pieces {
+            lines.push(trf("rules.pieces", &[&pieces]));
+        }
+        if let Some(decay) = self.decay {
+            lines.push(trf("rules.decay", &[&decay]));
+        }
+        if let Some(fog) = self.fog {
+            lines.push(trf("rules.fog", &[&fog]));
+        }
+        if let Some(seed) = self.seed {
+            lines.push(trf("rules.seed", &[&seed]));
+        }
+        if self.swap_rule {
+            lines.push(tr("rules.swap").into());
+        }
+        if let Some(best_of) = self.best_of {
+            lines.push(trf("rules.match", &[&best_of, &best_of.div_ceil(2)]));
+        }
+        lines.push(trf("rules.start", &[&self.start_rule.describe()]));
+        lines
+    }
+
+    /// Gets summary of the rules as `key=value` pairs separated by space,
+    /// which is embedded in the exported games
+    pub fn summary(&self) -> String {
+        let win = match self.win_len.uniform() {
+            Some(len) => format!("win={len}"),
+            None => format!(
+                "win-rows={} win-cols={} win-diag={}",
+                self.win_len.rows, self.win_len.cols, self.win_len.diag
+            ),
+        };
+        let mut res = format!(
+            "size={}x{} {win} blocks={} swap-rule={}",
+            self.size.x, self.size.y, self.blocks, self.swap_rule
+        );
+        if self.handicap > 0 {
+            res.push_str(&format!(" handicap={}", self.handicap));
+        }
+        if let Some(seed) = self.seed {
+            res.push_str(&format!(" seed={seed}"));
+        }
+        if self.mode != Mode::Normal {
+            res.push_str(&format!(" mode={}", self.mode.name()));
+        }
+        if let Some(pieces) = self.pieces {
+            res.push_str(&format!(" pieces={pieces}"));
+        }
+        if let Some(decay) = self.decay {
+            res.push_str(&format!(" decay={decay}"));
+        }
+        if let Some(fog) = self.fog {
+            res.push_str(&format!(" fog={fog}"));
+        }
+        if self.boards > 0 {
+            res.push_str(&format!(" boards={}", self.boards));
+        }
+        if self.points {
+            res.push_str(" points=true");
+        }
+        if self.infinite {
+            res.push_str(" infinite=true");
+        }
+        if let Some(best_of) = self.best_of {
+            res.push_str(&format!(" best-of={best_of}"));
+        }
+        res.push_str(&format!(" start={}", self.start_rule.name()));
+        res
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    io, thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    app::App, args::Args, board::Board, config::Config, engine::Registry,
+    error::Error, input::Action, screen::Screen,
+};
+
+/// Size of the headless screen
+const SIZE: (usize, usize) = (80, 24);
+/// Longest wait for the background work after an action
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Test harness playing scripted actions in the [`App`] without a
+/// terminal. Screen is rendered to a buffer, so it can be checked together
+/// with the board and the score.
+pub struct Scenario {
+    app: App,
+    exited: bool,
+}
+
+impl Scenario {
+    /// Creates new [`Scenario`] started with the given arguments. Config of
+    /// the user isn't used, board is 3x3 unless the arguments say otherwise.
+    pub fn new(args: &[&str]) -> Result<Self, Error> {
+        let args = ["tictactoe"].iter().chain(args).map(|a| a.to_string());
+        let args = Args::parse(args, Registry::default())?;
+        let config = Config {
+            size: Some((3, 3)),
+            ..Config::default()
+        };
+        let mut app = App::new(&args, config, vec![]);
+        app.wizard = None;
+        app.term =
+            Screen::headless(SIZE.0, SIZE.1).small_screen(App::small_screen());
+        Ok(Self { app, exited: false })
+    }
+
+    /// Plays the actions one by one, each after the background work of
+    /// the previous one finished
+    pub fn play(&mut self, actions: &[Action]) -> Result<(), Error> {
+        for action in actions {
+            if self.exited {
+                Err(Error::Unavailable("scenario already exited".into()))?;
+            }
+            let res = self.app.action_handler(*action);
+            self.handle(res)?;
+        }
+        Ok(())
+    }
+
+    /// Presses the keys one by one, so they are translated to actions the
+    /// same way as in the game
+    pub fn keys(&mut self, keys: &[KeyCode]) -> Result<(), Error> {
+        for key in keys {
+            if self.exited {
+                Err(Error::Unavailable("scenario already exited".into()))?;
+            }
+            let res = self.app.key_handler(KeyEvent::from(*key));
+            self.handle(res)?;
+        }
+        Ok(())
+    }
+
+    /// Types the text as the character keys
+    pub fn typing(&mut self, text: &str) -> Result<(), Error> {
+        let keys: Vec<_> = text.chars().map(KeyCode::Char).collect();
+        self.keys(&keys)
+    }
+
+    /// Gets the played board
+    pub fn board(&self) -> &Board {
+        &self.app.game.board
+    }
+
+    /// Gets games won by the first and the second player
+    pub fn score(&self) -> (usize, usize) {
+        self.app.game.score
+    }
+
+    /// Gets the message shown in the status bar
+    pub fn status(&self) -> Option<&str> {
+        self.app.status.current()
+    }
+
+    /// Checks whether the game was exited
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Renders the screen and gets its text line by line
+    pub fn screen(&mut self) -> Result<String, Error> {
+        self.app.render()?;
+        Ok(self.app.term.text())
+    }
+
+    /// Stores the exit and waits until the background work is finished
+    fn handle(&mut self, res: Result<(), Error>) -> Result<(), Error> {
+        match res {
+            Err(Error::Exit) => {
+                self.exited = true;
+                return Ok(());
+            }
+            res => res?,
+        }
+
+        let start = Instant::now();
+        self.app.update()?;
+        while self.app.working() {
+            if start.elapsed() > TIMEOUT {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "scenario timed out waiting for the background work",
+                ))?;
+            }
+            thread::sleep(Duration::from_millis(1));
+            self.app.update()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cell::Cell, palette::Command, pos::Pos, record::Record};
+
+    use super::*;
+
+    #[test]
+    fn game_is_won_and_restarted() {
+        let mut scenario = Scenario::new(&[]).unwrap();
+        // Numpad digits place directly on the 3x3 board
+        let moves = [7, 4, 8, 5, 9].map(Action::Numpad);
+        scenario.play(&moves).unwrap();
+        assert_eq!(scenario.board().state(), Some(Cell::Cross));
+        assert_eq!(scenario.score(), (1, 0));
+        assert!(scenario.screen().unwrap().contains("X wins!"));
+
+        scenario.play(&[Action::Run(Command::NewGame)]).unwrap();
+        assert!(scenario.board().history.is_empty());
+        assert_eq!(scenario.score(), (1, 0));
+    }
+
+    #[test]
+    fn quit_is_confirmed() {
+        let mut scenario = Scenario::new(&["-s", "5", "5"]).unwrap();
+        scenario
+            .keys(&[KeyCode::Char('2'), KeyCode::Right])
+            .unwrap();
+        scenario
+            .keys(&[KeyCode::Enter, KeyCode::Char('q')])
+            .unwrap();
+        assert_eq!(scenario.board().history.len(), 1);
+        assert!(scenario.screen().unwrap().contains("Quit?"));
+        assert_eq!(scenario.status(), None);
+
+        scenario.keys(&[KeyCode::Char('n')]).unwrap();
+        assert!(!scenario.screen().unwrap().contains("Quit?"));
+        scenario
+            .keys(&[KeyCode::Char('q'), KeyCode::Char('y')])
+            .unwrap();
+        assert!(scenario.exited());
+    }
+
+    #[test]
+    fn opponent_is_set_by_command() {
+        let mut scenario = Scenario::new(&[]).unwrap();
+        scenario.typing(":ai easy").unwrap();
+        scenario.keys(&[KeyCode::Enter]).unwrap();
+        assert_eq!(scenario.status(), Some("AI opponent plays circle"));
+        scenario.play(&[Action::Numpad(5)]).unwrap();
+        // Opponent replies before the next action
+        assert_eq!(scenario.board().history.len(), 2);
+        assert_eq!(scenario.board().history[1].0, Cell::Circle);
+    }
+
+    #[test]
+    fn opponent_waits_while_paused() {
+        let mut scenario = Scenario::new(&[]).unwrap();
+        scenario.typing(":ai easy").unwrap();
+        scenario.keys(&[KeyCode::Enter]).unwrap();
+        scenario.play(&[Action::Run(Command::Pause)]).unwrap();
+        scenario.app.game.play(Pos::new(1, 1)).unwrap();
+        scenario.handle(Ok(())).unwrap();
+        assert_eq!(scenario.board().history.len(), 1);
+
+        scenario.keys(&[KeyCode::Char('p')]).unwrap();
+        assert_eq!(scenario.board().history.len(), 2);
+    }
+
+    #[test]
+    fn hidden_mark_is_not_given_away() {
+        let args = ["-s", "5", "5", "--fog", "1"];
+        let mut scenario = Scenario::new(&args).unwrap();
+        scenario.app.game.play(Pos::new(0, 0)).unwrap();
+        scenario.app.game.play(Pos::new(4, 4)).unwrap();
+        scenario.app.game.board.select(Pos::new(4, 4));
+        scenario.play(&[Action::Place]).unwrap();
+        assert_eq!(scenario.status(), None);
+        assert_eq!(scenario.board().history.len(), 2);
+    }
+
+    #[test]
+    fn import_without_rules_warns() {
+        let mut scenario = Scenario::new(&[]).unwrap();
+        let rec = Record::parse("[Size \"3x3\"]\n[Win \"3\"]\n\n1. b2 *");
+        scenario.app.import(&rec.unwrap(), None).unwrap();
+        let warning =
+            "Record has no rule summary, its rules may be incomplete";
+        assert_eq!(scenario.status(), Some(warning));
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    fmt::Write as _,
+    io::{self, stdout, Write},
+};
+
+use termint::{
+    buffer::{Buffer, Cell},
+    enums::Cursor,
+    geometry::{Coords, Rect},
+    term::Term,
+    widgets::Widget,
+};
+
+use crate::error::Error;
+
+/// Starts synchronized update, terminal shows the frame once it's complete
+const SYNC_START: &str = "\x1b[?2026h";
+/// Ends synchronized update
+const SYNC_END: &str = "\x1b[?2026l";
+
+/// Double-buffered renderer of the whole terminal. Keeps the previously
+/// rendered buffer and writes only the cells, which changed since then,
+/// in a single write.
+#[derive(Debug, Default)]
+pub struct Screen {
+    prev: Option<Buffer>,
+    small: Option<Box<dyn Widget>>,
+    /// Size of the headless screen, which only keeps the rendered buffer
+    /// instead of writing it to the terminal
+    headless: Option<Coords>,
+}
+
+impl Screen {
+    /// Creates new [`Screen`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates [`Screen`] of the given size rendering only to the buffer,
+    /// which can be read by [`Screen::text`]
+    #[cfg(test)]
+    pub fn headless(width: usize, height: usize) -> Self {
+        Self {
+            headless: Some(Coords::new(width, height)),
+            ..Self::default()
+        }
+    }
+
+    /// Gets size of the screen, [`None`] when it can't be determined
+    pub fn size(&self) -> Option<(usize, usize)> {
+        match self.headless {
+            Some(size) => Some((size.x, size.y)),
+            None => Term::get_size(),
+        }
+    }
+
+    /// Gets characters of the last rendered buffer line by line
+    #[cfg(test)]
+    pub fn text(&self) -> String {
+        let Some(buffer) = &self.prev else {
+            return String::new();
+        };
+        let mut text = String::new();
+        for (id, cell) in buffer.content().iter().enumerate() {
+            if id > 0 && id % buffer.width() == 0 {
+                text.push('\n');
+            }
+            text.push(cell.val);
+        }
+        text
+    }
+
+    /// Sets widget shown when the rendered widget doesn't fit the terminal
+    pub fn small_screen<T: Widget + 'static>(mut self, small: T) -> Self {
+        self.small = Some(Box::new(small));
+        self
+    }
+
+    /// Renders given widget over the whole terminal
+    pub fn render<T: Widget>(&mut self, widget: T) -> Result<(), Error> {
+        let (w, h) = self.size().ok_or_else(|| {
+            io::Error::other("cannot determine terminal size")
+        })?;
+        let size = Coords::new(w, h);
+        let mut buffer =
+            Buffer::empty(Rect::from_coords(Coords::new(1, 1), size));
+        match &self.small {
+            Some(small)
+                if w < widget.width(&size) || h < widget.height(&size) =>
+            {
+                small.render(&mut buffer)
+            }
+            _ => widget.render(&mut buffer),
+        }
+
+        if self.headless.is_none() {
+            let frame = Screen::frame(&buffer, self.prev.as_ref());
+            let mut out = stdout().lock();
+            out.write_all(frame.as_bytes())?;
+            out.flush()?;
+        }
+        self.prev = Some(buffer);
+        Ok(())
+    }
+
+    /// Forgets the previous buffer, so the next render redraws everything
+    pub fn clear(&mut self) {
+        self.prev = None;
+    }
+
+    /// Gets escape sequences drawing the cells of the buffer, which differ
+    /// from the previous buffer. Draws all the cells without previous
+    /// buffer or when its size differs.
+    pub fn frame(buffer: &Buffer, prev: Option<&Buffer>) -> String {
+        let prev = prev.filter(|p| {
+            p.x() == buffer.x()
+                && p.y() == buffer.y()
+                && p.width() == buffer.width()
+                && p.height() == buffer.height()
+        });
+
+        let mut out = String::from(SYNC_START);
+        let mut style: Option<Cell> = None;
+        // Whether the cursor is right after the last drawn cell
+        let mut follows = false;
+        for (id, cell) in buffer.content().iter().enumerate() {
+            let x = id % buffer.width();
+            if x == 0 {
+                follows = false;
+            }
+            if prev.is_some_and(|p| Screen::same(&p.get_cell(id), cell)) {
+                follows = false;
+                continue;
+            }
+
+            if !follows {
+                let pos = Cursor::Pos(
+                    buffer.x() + x,
+                    buffer.y() + id / buffer.width(),
+                );
+                _ = write!(out, "{pos}");
+            }
+            if !style.is_some_and(|s| Screen::same_style(&s, cell)) {
+                _ = write!(
+                    out,
+                    "\x1b[0m{}{}{}",
+                    cell.modifier,
+                    cell.fg.to_fg(),
+                    cell.bg.to_bg()
+                );
+                style = Some(*cell);
+            }
+            out.push(cell.val);
+            follows = true;
+        }
+        out.push_str("\x1b[0m");
+        out.push_str(SYNC_END);
+        out
+    }
+
+    /// Checks whether the cells look the same
+    fn same(a: &Cell, b: &Cell) -> bool {
+        a.val == b.val && Screen::same_style(a, b)
+    }
+
+    /// Checks whether the cells are drawn with the same style
+    fn same_style(a: &Cell, b: &Cell) -> bool {
+        a.fg == b.fg && a.bg == b.bg && a.modifier == b.modifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use termint::widgets::{StrSpanExtension, Widget};
+
+    use super::*;
+
+    #[test]
+    fn draws_only_changed_cells() {
+        let rect = Rect::new(1, 1, 10, 2);
+        let mut prev = Buffer::empty(rect);
+        "hello".to_span().render(&mut prev);
+        let mut buffer = Buffer::empty(rect);
+        "help".to_span().render(&mut buffer);
+
+        let full = Screen::frame(&buffer, None);
+        assert!(full.contains("help"));
+        let same = Screen::frame(&prev, Some(&prev));
+        assert_eq!(same, format!("{SYNC_START}\x1b[0m{SYNC_END}"));
+
+        let diff = Screen::frame(&buffer, Some(&prev));
+        assert!(diff.contains(&Cursor::Pos(4, 1).to_string()));
+        assert!(!diff.contains("hel"));
+        assert!(diff.len() < full.len());
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+/// Delay before the next round starts
+const ROUND_DELAY: Duration = Duration::from_millis(1500);
+
+/// Best-of-N match, which consists of multiple rounds
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub best_of: usize,
+    /// Rounds won by the first and the second player
+    pub wins: (usize, usize),
+    pub round: usize,
+    ended: Option<Instant>,
+}
+
+impl Series {
+    /// Creates new [`Series`] with given number of rounds
+    pub fn new(best_of: usize) -> Self {
+        Self {
+            best_of,
+            wins: (0, 0),
+            round: 0,
+            ended: None,
+        }
+    }
+
+    /// Gets number of round wins needed to win the match
+    pub fn target(&self) -> usize {
+        self.best_of.div_ceil(2)
+    }
+
+    /// Ends the current round, `winner` is index of the winning player or
+    /// `None` on draw
+    pub fn end_round(&mut self, winner: Option<usize>) {
+        match winner {
+            Some(0) => self.wins.0 += 1,
+            Some(_) => self.wins.1 += 1,
+            None => {}
+        }
+        self.ended = Some(Instant::now());
+    }
+
+    /// Postpones the next round by the given time the game was paused for
+    pub fn resume(&mut self, paused: Duration) {
+        self.ended = self.ended.map(|e| e + paused);
+    }
+
+    /// Starts the next round
+    pub fn next_round(&mut self) {
+        self.round += 1;
+        self.ended = None;
+    }
+
+    /// Gets index of the player, who won the match
+    pub fn winner(&self) -> Option<usize> {
+        if self.wins.0 >= self.target() {
+            Some(0)
+        } else if self.wins.1 >= self.target() {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the next round should be started
+    pub fn ready(&self) -> bool {
+        self.winner().is_none()
+            && self.ended.is_some_and(|e| e.elapsed() >= ROUND_DELAY)
+    }
+
+    /// Gets time remaining until the next round starts
+    pub fn remaining(&self) -> Option<Duration> {
+        self.ended.map(|e| ROUND_DELAY.saturating_sub(e.elapsed()))
+    }
+
+    /// Checks whether the second player starts the current round
+    pub fn swapped(&self) -> bool {
+        self.round % 2 == 1
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    cmp::{max, min},
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use crate::{
+    board::Board,
+    cell::Cell,
+    engine::{Options, Registry},
+    error::Error,
+    game::Game,
+    json::Json,
+    mode::Mode,
+    notation::{parse_pos, pos_name},
+    pos::Pos,
+    rng::Rng,
+    win_len::WinLen,
+};
+
+/// Port the server listens on when not given
+pub const DEFAULT_PORT: u16 = 7878;
+/// Largest supported board side, so requests can't allocate huge boards
+const MAX_SIZE: usize = 100;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const INTERNAL_ERROR: i32 = -32603;
+/// Error code of the moves breaking the rules
+const ILLEGAL_MOVE: i32 = -32000;
+
+/// Runs the engine server on the given local port. Each connection plays
+/// its own game using JSON-RPC 2.0 requests, one per line. Best moves are
+/// searched by the given engines.
+pub fn run(port: u16, engines: &Registry) -> Result<(), Error> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|source| Error::Network { source })?;
+    println!("Listening on 127.0.0.1:{port}");
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let engines = engines.clone();
+        thread::spawn(move || {
+            // Closed connection only ends its own game
+            _ = Session::new(engines).serve(stream);
+        });
+    }
+    Ok(())
+}
+
+/// Game played over a single connection
+#[derive(Debug)]
+struct Session {
+    game: Game,
+    /// Engines the best move is searched by
+    engines: Registry,
+}
+
+impl Session {
+    /// Creates [`Session`] playing on 3x3 board
+    fn new(engines: Registry) -> Self {
+        Self {
+            game: Game::new(Board::new(3, 3, 3), Mode::Normal),
+            engines,
+        }
+    }
+
+    /// Answers requests read from the stream until it's closed
+    fn serve(&mut self, stream: TcpStream) -> Result<(), Error> {
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let res = self.respond(&line);
+            writeln!(writer, "{res}")?;
+        }
+        Ok(())
+    }
+
+    /// Gets response to the given request line
+    fn respond(&mut self, line: &str) -> Json {
+        let req = match Json::parse(line) {
+            Ok(req) => req,
+            Err(e) => return error(Json::Null, PARSE_ERROR, &e.to_string()),
+        };
+        let id = req.get("id").cloned().unwrap_or(Json::Null);
+        let Some(method) = req.get("method").and_then(|m| m.as_str()) else {
+            return error(id, INVALID_REQUEST, "missing method");
+        };
+
+        let params = req.get("params").cloned().unwrap_or(Json::obj([]));
+        let res = match method {
+            "new_game" => self.new_game(&params),
+            "make_move" => self.make_move(&params),
+            "best_move" => self.best_move(&params),
+            "get_state" => Ok(self.state()),
+            _ => {
+                let msg = format!("unknown method '{method}'");
+                return error(id, METHOD_NOT_FOUND, &msg);
+            }
+        };
+        match res {
+            Ok(res) => Json::obj([
+                ("jsonrpc", "2.0".into()),
+                ("result", res),
+                ("id", id),
+            ]),
+            Err((code, msg)) => error(id, code, &msg),
+        }
+    }
+
+    /// Starts new game with the board given by the parameters
+    fn new_game(&mut self, params: &Json) -> Result<Json, (i32, String)> {
+        let width = num_param(params, "width")?.unwrap_or(3);
+        let height = num_param(params, "height")?.unwrap_or(width);
+        let win =
+            num_param(params, "win")?.unwrap_or(min(max(width, height), 5));
+        if !(3..=MAX_SIZE).contains(&width)
+            || !(3..=MAX_SIZE).contains(&height)
+        {
+            let msg = format!("board size has to be between 3 and {MAX_SIZE}");
+            return Err((INVALID_PARAMS, msg));
+        }
+        if let Err(e) = WinLen::new(win).check(width, height) {
+            return Err((INVALID_PARAMS, e.to_string()));
+        }
+
+        let board = Board::new(width, height, win);
+        self.game = Game::new(board, Mode::Normal);
+        Ok(self.state())
+    }
+
+    /// Places mark of the player on turn on the cell given by the name or
+    /// the coordinates
+    fn make_move(&mut self, params: &Json) -> Result<Json, (i32, String)> {
+        let pos = match params.get("cell").and_then(|c| c.as_str()) {
+            Some(name) => parse_pos(name),
+            None => match (num_param(params, "x")?, num_param(params, "y")?) {
+                (Some(x), Some(y)) => Some(Pos::new(x, y)),
+                _ => None,
+            },
+        };
+        let size = self.game.board.size;
+        let pos = pos
+            .filter(|p| p.x < size.x && p.y < size.y)
+            .ok_or((INVALID_PARAMS, "expected cell on the board".into()))?;
+
+        if let Err(e) = self.game.legality(pos) {
+            return Err((ILLEGAL_MOVE, e.reason().to_string()));
+        }
+        self.game
+            .play(pos)
+            .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+        Ok(self.state())
+    }
+
+    /// Gets move the AI engine of the given difficulty would play
+    fn best_move(&mut self, params: &Json) -> Result<Json, (i32, String)> {
+        let name = params.get("difficulty").map_or(Some("hard"), Json::as_str);
+        let options = Options {
+            seed: Rng::time_seed(),
+            ..Options::default()
+        };
+        let mut engine = self
+            .engines
+            .create(name.unwrap_or_default(), &options)
+            .map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+        let best = engine
+            .choose_move(&self.game.board, self.game.current_player())
+            .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+        Ok(best.map_or(Json::Null, |pos| {
+            Json::obj([
+                ("x", (pos.x as f64).into()),
+                ("y", (pos.y as f64).into()),
+                ("cell", pos_name(&pos).as_str().into()),
+            ])
+        }))
+    }
+
+    /// Gets state of the game with rows of the board
+    fn state(&self) -> Json {
+        let board = &self.game.board;
+        let rows = board
+            .rows()
+            .map(|row| {
+                Json::Str(
+                    row.iter()
+                        .map(|c| match c {
+                            Cell::Empty => '.',
+                            c => c.symbol(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        let result = self.game.result();
+        let (turn, state) = match result {
+            None => {
+                let player = self.game.current_player();
+                (Json::Str(player.symbol().to_string()), "play")
+            }
+            Some(Cell::Empty) => (Json::Null, "draw"),
+            Some(_) => (Json::Null, "win"),
+        };
+        let winner = match result {
+            Some(cell @ (Cell::Cross | Cell::Circle)) => {
+                Json::Str(cell.symbol().to_string())
+            }
+            _ => Json::Null,
+        };
+        Json::obj([
+            ("width", (board.size.x as f64).into()),
+            ("height", (board.size.y as f64).into()),
+            ("win", (board.win_len.rows as f64).into()),
+            ("rows", Json::Arr(rows)),
+            ("state", state.into()),
+            ("turn", turn),
+            ("winner", winner),
+        ])
+    }
+}
+
+/// Gets optional non-negative integer parameter
+fn num_param(
+    params: &Json,
+    key: &str,
+) -> Result<Option<usize>, (i32, String)> {
+    match params.get(key) {
+        None => Ok(None),
+        Some(val) => val
+            .as_f64()
+            .filter(|n| *n >= 0. && n.fract() == 0.)
+            .map(|n| Some(n as usize))
+            .ok_or((INVALID_PARAMS, format!("expected integer {key}"))),
+    }
+}
+
+/// Creates JSON-RPC error response
+fn error(id: Json, code: i32, msg: &str) -> Json {
+    Json::obj([
+        ("jsonrpc", "2.0".into()),
+        (
+            "error",
+            Json::obj([
+                ("code", (code as f64).into()),
+                ("message", msg.into()),
+            ]),
+        ),
+        ("id", id),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sends request with given method and params to the session
+    fn call(session: &mut Session, method: &str, params: &str) -> Json {
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":{params}}}"#
+        );
+        session.respond(&req)
+    }
+
+    #[test]
+    fn plays_game_to_win() {
+        let mut session = Session::new(Registry::default());
+        call(&mut session, "new_game", r#"{"width":3}"#);
+        for cell in ["a1", "a2", "b1", "b2"] {
+            let res = call(
+                &mut session,
+                "make_move",
+                &format!(r#"{{"cell":"{cell}"}}"#),
+            );
+            assert!(res.get("result").is_some());
+        }
+        let best = call(&mut session, "best_move", "{}");
+        let best = best.get("result").and_then(|r| r.get("cell"));
+        assert_eq!(best.and_then(|c| c.as_str()), Some("c1"));
+
+        let res = call(&mut session, "make_move", r#"{"x":2,"y":0}"#);
+        let res = res.get("result").unwrap();
+        assert_eq!(res.get("winner").and_then(|w| w.as_str()), Some("X"));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let mut session = Session::new(Registry::default());
+        let res = call(&mut session, "make_move", r#"{"cell":"b2"}"#);
+        assert!(res.get("result").is_some());
+        let res = call(&mut session, "make_move", r#"{"cell":"b2"}"#);
+        let code = res.get("error").and_then(|e| e.get("code"));
+        assert_eq!(code.and_then(|c| c.as_f64()), Some(ILLEGAL_MOVE as f64));
+
+        let res = session.respond("{");
+        let code = res.get("error").and_then(|e| e.get("code"));
+        assert_eq!(code.and_then(|c| c.as_f64()), Some(PARSE_ERROR as f64));
+
+        let res = call(&mut session, "new_game", r#"{"width":5,"win":2}"#);
+        let code = res.get("error").and_then(|e| e.get("code"));
+        assert_eq!(code.and_then(|c| c.as_f64()), Some(INVALID_PARAMS as f64));
+    }
+}
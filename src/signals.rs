@@ -0,0 +1,23 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+#[cfg(unix)]
+use signal_hook::consts::SIGHUP;
+use signal_hook::consts::{SIGINT, SIGTERM};
+
+use crate::error::Error;
+
+/// Signals ending the app gracefully, hangup is sent on ssh disconnects
+#[cfg(unix)]
+const SIGNALS: &[i32] = &[SIGINT, SIGTERM, SIGHUP];
+#[cfg(not(unix))]
+const SIGNALS: &[i32] = &[SIGINT, SIGTERM];
+
+/// Registers handlers of the [`SIGNALS`]. Returns flag, which is set when
+/// any of them is received, instead of the app being killed.
+pub fn register() -> Result<Arc<AtomicBool>, Error> {
+    let flag = Arc::new(AtomicBool::new(false));
+    for signal in SIGNALS {
+        signal_hook::flag::register(*signal, flag.clone())?;
+    }
+    Ok(flag)
+}
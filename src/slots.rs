@@ -0,0 +1,200 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use termint::{
+    buffer::Buffer,
+    enums::Color,
+    geometry::{Constraint, Coords},
+    widgets::{Block, Layout, Spacer, StrSpanExtension, Widget},
+};
+
+use crate::{
+    daily::{self, DAY},
+    error::Error,
+    paths,
+    record::{Record, EXTENSION},
+    theme::Theme,
+};
+
+/// Width of the load screen content (without borders)
+const WIDTH: usize = 48;
+/// Maximum number of the listed slots
+const MAX_LISTED: usize = 15;
+
+/// Game saved under a name, so multiple games can be continued later
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub name: String,
+    /// Board size from the record, like `7x7`
+    pub size: String,
+    /// Number of the played moves
+    pub moves: usize,
+    /// Time the slot was last saved at
+    pub saved: Option<SystemTime>,
+}
+
+impl Slot {
+    /// Gets time of the last save in the `YYYY-MM-DD HH:MM` format (UTC)
+    pub fn time(&self) -> String {
+        let Some(secs) = self
+            .saved
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+        else {
+            return "-".into();
+        };
+        let mins = secs % DAY / 60;
+        format!(
+            "{} {:02}:{:02}",
+            daily::date(secs / DAY),
+            mins / 60,
+            mins % 60
+        )
+    }
+}
+
+/// Gets directory the save slots are stored in
+pub fn dir() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join("saves"))
+}
+
+/// Gets path of the slot with given name. Name can contain only letters,
+/// digits, `-` and `_`, so it can't point outside of the slots directory.
+pub fn path(name: &str) -> Result<PathBuf, Error> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        Err(Error::parse(
+            "slot name can contain only letters, digits, - and _",
+        ))?;
+    }
+    let dir = dir().ok_or_else(|| Error::no_dir("data"))?;
+    Ok(dir.join(format!("{name}.{EXTENSION}")))
+}
+
+/// Lists all the saved slots from the most recently saved, slots which
+/// can't be read are skipped
+pub fn list() -> Vec<Slot> {
+    let Some(entries) = dir().and_then(|d| fs::read_dir(d).ok()) else {
+        return vec![];
+    };
+    let mut slots: Vec<_> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != EXTENSION {
+                return None;
+            }
+            let rec = Record::load(&path).ok()?;
+            Some(Slot {
+                name: path.file_stem()?.to_string_lossy().to_string(),
+                size: rec.tag("Size").unwrap_or("?").to_string(),
+                moves: rec.moves.len(),
+                saved: entry.metadata().and_then(|m| m.modified()).ok(),
+            })
+        })
+        .collect();
+    slots.sort_by_key(|s| Reverse(s.saved));
+    slots
+}
+
+/// Load screen listing the save slots, rendered over the game
+#[derive(Debug, Clone)]
+pub struct LoadScreen {
+    slots: Vec<Slot>,
+    selected: usize,
+    theme: Theme,
+}
+
+impl LoadScreen {
+    /// Creates new [`LoadScreen`] listing the currently saved slots
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            slots: list(),
+            selected: 0,
+            theme,
+        }
+    }
+
+    /// Moves selection up
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down
+    pub fn down(&mut self) {
+        if self.selected + 1 < self.slots.len().min(MAX_LISTED) {
+            self.selected += 1;
+        }
+    }
+
+    /// Gets selected slot
+    pub fn selected(&self) -> Option<&Slot> {
+        self.slots.get(self.selected)
+    }
+}
+
+impl Widget for LoadScreen {
+    fn render(&self, buffer: &mut Buffer) {
+        let mut block = Block::vertical()
+            .title("Saved games".fg(self.theme.accent))
+            .border_color(self.theme.dim);
+        if self.slots.is_empty() {
+            let hint = "No saved games, use --save-as <name>";
+            block.add_child(hint.fg(self.theme.dim), Constraint::Length(1));
+        }
+        for (i, slot) in self.slots.iter().take(MAX_LISTED).enumerate() {
+            let fg = match i == self.selected {
+                true => self.theme.accent,
+                false => Color::Default,
+            };
+            let info = format!(
+                "{} {:>3} moves  {}",
+                slot.size,
+                slot.moves,
+                slot.time()
+            );
+            let mut item = Layout::horizontal();
+            item.add_child(slot.name.as_str().fg(fg), Constraint::Min(0));
+            item.add_child(Spacer::new(), Constraint::Fill);
+            item.add_child(info.fg(self.theme.dim), Constraint::Min(0));
+            block.add_child(item, Constraint::Length(1));
+        }
+        let keys = "[Enter]Load  [Esc]Close";
+        block.add_child(keys.fg(self.theme.dim), Constraint::Length(1));
+        block.render(buffer);
+    }
+
+    fn height(&self, _size: &Coords) -> usize {
+        self.slots.len().clamp(1, MAX_LISTED) + 3
+    }
+
+    fn width(&self, _size: &Coords) -> usize {
+        WIDTH + 2
+    }
+}
+
+impl From<LoadScreen> for Box<dyn Widget> {
+    fn from(value: LoadScreen) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_names_stay_in_directory() {
+        assert!(path("friday-match_2").is_ok());
+        assert!(path("../stats").is_err());
+        assert!(path("a/b").is_err());
+        assert!(path("").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    io::{stdout, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{config::SoundConfig, error::Error};
+
+/// Represents event, which can have sound assigned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    Place,
+    Invalid,
+    Win,
+    Draw,
+    Lose,
+    Tick,
+}
+
+impl SoundEvent {
+    /// All the sound events
+    pub const ALL: &'static [SoundEvent] = &[
+        SoundEvent::Place,
+        SoundEvent::Invalid,
+        SoundEvent::Win,
+        SoundEvent::Draw,
+        SoundEvent::Lose,
+        SoundEvent::Tick,
+    ];
+
+    /// Gets name of the event, which is used in config and as sound file name
+    pub fn name(&self) -> &'static str {
+        match self {
+            SoundEvent::Place => "place",
+            SoundEvent::Invalid => "invalid",
+            SoundEvent::Win => "win",
+            SoundEvent::Draw => "draw",
+            SoundEvent::Lose => "lose",
+            SoundEvent::Tick => "tick",
+        }
+    }
+}
+
+/// Sound mode set by the arguments, which overrides the sound config
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundMode {
+    Off,
+    Bell,
+    On,
+}
+
+impl SoundMode {
+    /// Applies the mode to the given sound config
+    pub fn apply(&self, config: &mut SoundConfig) {
+        match self {
+            SoundMode::Off => config.enabled = false,
+            SoundMode::Bell => {
+                config.enabled = true;
+                config.bell = true;
+                config.pack = None;
+            }
+            SoundMode::On => config.enabled = true,
+        }
+    }
+}
+
+impl FromStr for SoundMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(SoundMode::Off),
+            "bell" => Ok(SoundMode::Bell),
+            "on" => Ok(SoundMode::On),
+            _ => Err(Error::parse(format!(
+                "expected off, bell or on, got '{s}'"
+            ))),
+        }
+    }
+}
+
+/// Sound pack loaded from the directory. Sound files are named by the event
+/// they belong to (e.g. `place.wav`, `win.ogg`)
+#[derive(Debug, Clone, Default)]
+pub struct SoundPack {
+    sounds: Vec<(SoundEvent, PathBuf)>,
+}
+
+impl SoundPack {
+    /// Loads sound pack from the given directory
+    pub fn load<P: AsRef<Path>>(dir: P) -> Self {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Self::default();
+        };
+
+        let mut sounds = Vec::new();
+        for path in entries.flatten().map(|e| e.path()) {
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Some(e) = SoundEvent::ALL.iter().find(|e| e.name() == stem)
+            {
+                sounds.push((*e, path));
+            }
+        }
+        Self { sounds }
+    }
+
+    /// Gets sound file of the given event
+    pub fn get(&self, event: SoundEvent) -> Option<&Path> {
+        self.sounds
+            .iter()
+            .find(|(e, _)| *e == event)
+            .map(|(_, p)| p.as_path())
+    }
+}
+
+/// Plays sounds of the sound pack based on the sound config
+#[derive(Debug, Clone, Default)]
+pub struct Sound {
+    config: SoundConfig,
+    pack: SoundPack,
+}
+
+impl Sound {
+    /// Creates new [`Sound`] and loads the sound pack set in config
+    pub fn new(config: SoundConfig) -> Self {
+        let pack = match (&config.pack, config.enabled) {
+            (Some(dir), true) => SoundPack::load(dir),
+            _ => SoundPack::default(),
+        };
+        Self { config, pack }
+    }
+
+    /// Plays sound of the given event, when enabled. Terminal bell is rung
+    /// instead, when the event has no sound file and bell is enabled.
+    pub fn play(&self, event: SoundEvent) {
+        if !self.config.enabled || !self.config.events.contains(&event) {
+            return;
+        }
+        match self.pack.get(event) {
+            Some(file) => play_file(file, self.config.volume),
+            // Ringing on each cursor movement would be too noisy
+            None if self.config.bell && event != SoundEvent::Tick => {
+                let mut out = stdout();
+                _ = out.write_all(b"\x07");
+                _ = out.flush();
+            }
+            None => {}
+        }
+    }
+}
+
+/// Plays given sound file in the background using the system audio player
+#[cfg(feature = "audio")]
+fn play_file(file: &Path, volume: u8) {
+    use std::process::{Command, Stdio};
+
+    let file = file.to_owned();
+    let volume = format!("--volume={}", volume as u32 * 65536 / 100);
+    std::thread::spawn(move || {
+        let played = Command::new("paplay")
+            .arg(volume)
+            .arg(&file)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        if !played {
+            _ = Command::new("aplay")
+                .arg("-q")
+                .arg(&file)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+    });
+}
+
+/// Audio backend is disabled, sounds are not played
+#[cfg(not(feature = "audio"))]
+fn play_file(_file: &Path, _volume: u8) {}
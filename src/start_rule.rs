@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use crate::{cell::Cell, error::Error, locale::tr};
+
+/// Rule deciding which player starts the next game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StartRule {
+    Alternate,
+    LoserStarts,
+    WinnerStarts,
+    #[default]
+    AlwaysX,
+}
+
+impl StartRule {
+    /// All the start rules
+    pub const ALL: &'static [StartRule] = &[
+        StartRule::Alternate,
+        StartRule::LoserStarts,
+        StartRule::WinnerStarts,
+        StartRule::AlwaysX,
+    ];
+
+    /// Gets name of the rule
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartRule::Alternate => "alternate",
+            StartRule::LoserStarts => "loser-starts",
+            StartRule::WinnerStarts => "winner-starts",
+            StartRule::AlwaysX => "always-x",
+        }
+    }
+
+    /// Gets description of the rule
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StartRule::Alternate => tr("start.alternate"),
+            StartRule::LoserStarts => tr("start.loser"),
+            StartRule::WinnerStarts => tr("start.winner"),
+            StartRule::AlwaysX => tr("start.always-x"),
+        }
+    }
+
+    /// Gets player starting the next game based on the player, who started
+    /// the previous game, and its result. Draw or unfinished game alternates
+    /// the starting player.
+    pub fn next(&self, starter: Cell, result: Option<Cell>) -> Cell {
+        let winner = result.filter(|c| *c != Cell::Empty);
+        match (self, winner) {
+            (StartRule::AlwaysX, _) => Cell::Cross,
+            (StartRule::LoserStarts, Some(winner)) => winner.next(),
+            (StartRule::WinnerStarts, Some(winner)) => winner,
+            _ => starter.next(),
+        }
+    }
+}
+
+impl FromStr for StartRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StartRule::ALL
+            .iter()
+            .find(|r| r.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "expected alternate, loser-starts, winner-starts or \
+                     always-x, got '{s}'"
+                ))
+            })
+    }
+}
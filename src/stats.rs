@@ -0,0 +1,117 @@
+use std::{fs, path::PathBuf};
+
+use crate::{cell::Cell, error::Error, paths};
+
+/// Game results persisted between the runs
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    pub cross: usize,
+    pub circle: usize,
+    pub draws: usize,
+    /// Day of the last solved daily challenge
+    pub daily: u64,
+    /// Number of daily challenges solved in a row
+    pub streak: usize,
+}
+
+impl Stats {
+    /// Loads stats from the stats file, missing or invalid values are zero
+    pub fn load() -> Self {
+        let mut stats = Self::default();
+        let Some(text) = Self::path().and_then(|p| fs::read_to_string(p).ok())
+        else {
+            return stats;
+        };
+
+        for (key, val) in text.lines().filter_map(|l| l.split_once('=')) {
+            let val = val.trim().parse().unwrap_or_default();
+            match key.trim() {
+                "cross" => stats.cross = val,
+                "circle" => stats.circle = val,
+                "draws" => stats.draws = val,
+                "daily" => stats.daily = val as u64,
+                "streak" => stats.streak = val,
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    /// Saves stats to the stats file
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(&path, e))?;
+        }
+        fs::write(
+            &path,
+            format!(
+                "cross = {}\ncircle = {}\ndraws = {}\ndaily = {}\n\
+                streak = {}\n",
+                self.cross, self.circle, self.draws, self.daily, self.streak
+            ),
+        )
+        .map_err(|e| Error::save(&path, e))?;
+        Ok(())
+    }
+
+    /// Adds result of the finished game
+    pub fn add(&mut self, winner: Cell) {
+        match winner {
+            Cell::Cross => self.cross += 1,
+            Cell::Circle => self.circle += 1,
+            _ => self.draws += 1,
+        }
+    }
+
+    /// Records solving the daily challenge of the given day
+    pub fn solve_daily(&mut self, day: u64) {
+        if self.solved(day) {
+            return;
+        }
+        self.streak = match self.daily + 1 == day {
+            true => self.streak + 1,
+            false => 1,
+        };
+        self.daily = day;
+    }
+
+    /// Checks whether the daily challenge of the given day was solved
+    pub fn solved(&self, day: u64) -> bool {
+        self.streak > 0 && self.daily == day
+    }
+
+    /// Gets streak of the solved daily challenges, which is broken when
+    /// the challenge of the day before the given day wasn't solved
+    pub fn streak(&self, day: u64) -> usize {
+        match self.daily + 1 >= day {
+            true => self.streak,
+            false => 0,
+        }
+    }
+
+    /// Gets path to the stats file
+    fn path() -> Option<PathBuf> {
+        Some(paths::data_dir()?.join("stats"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn daily_streak() {
+        let mut stats = Stats::default();
+        stats.solve_daily(10);
+        stats.solve_daily(10);
+        stats.solve_daily(11);
+        assert_eq!(stats.streak(12), 2);
+        assert_eq!(stats.streak(13), 0);
+        stats.solve_daily(13);
+        assert!(stats.solved(13));
+        assert_eq!(stats.streak(13), 1);
+    }
+}
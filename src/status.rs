@@ -0,0 +1,51 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How long each message is shown for
+const DURATION: Duration = Duration::from_millis(2500);
+
+/// Queue of the status bar messages, which are shown one after another
+/// until they expire
+#[derive(Debug, Clone, Default)]
+pub struct Status {
+    messages: VecDeque<String>,
+    /// When the current message was shown
+    shown: Option<Instant>,
+}
+
+impl Status {
+    /// Adds message to the queue, message same as the last one is ignored
+    pub fn push<T: Into<String>>(&mut self, msg: T) {
+        let msg = msg.into();
+        if self.messages.back() == Some(&msg) {
+            return;
+        }
+        if self.messages.is_empty() {
+            self.shown = Some(Instant::now());
+        }
+        self.messages.push_back(msg);
+    }
+
+    /// Gets the currently shown message
+    pub fn current(&self) -> Option<&str> {
+        self.messages.front().map(|m| m.as_str())
+    }
+
+    /// Removes the current message when it expired and shows the next one.
+    /// Returns whether the message changed.
+    pub fn update(&mut self) -> bool {
+        if self.remaining().is_none_or(|r| !r.is_zero()) {
+            return false;
+        }
+        self.messages.pop_front();
+        self.shown = (!self.messages.is_empty()).then(Instant::now);
+        true
+    }
+
+    /// Gets time remaining until the current message expires
+    pub fn remaining(&self) -> Option<Duration> {
+        self.shown.map(|s| DURATION.saturating_sub(s.elapsed()))
+    }
+}
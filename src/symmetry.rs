@@ -0,0 +1,67 @@
+use crate::pos::Pos;
+
+/// Rotation or reflection of the board. Position is transposed first and
+/// then flipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Symmetry {
+    transpose: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl Symmetry {
+    /// Gets all the symmetries of the board with given size, only square
+    /// boards can be transposed
+    pub fn all(size: Pos) -> Vec<Symmetry> {
+        let transposes: &[bool] = match size.x == size.y {
+            true => &[false, true],
+            false => &[false],
+        };
+        let mut res = vec![];
+        for &transpose in transposes {
+            for flip_x in [false, true] {
+                for flip_y in [false, true] {
+                    res.push(Symmetry {
+                        transpose,
+                        flip_x,
+                        flip_y,
+                    });
+                }
+            }
+        }
+        res
+    }
+
+    /// Checks whether the symmetry swaps the rows and the columns
+    pub fn transposes(&self) -> bool {
+        self.transpose
+    }
+
+    /// Maps the position on the board with given size
+    pub fn apply(&self, mut pos: Pos, size: Pos) -> Pos {
+        if self.transpose {
+            pos = Pos::new(pos.y, pos.x);
+        }
+        self.flip(pos, size)
+    }
+
+    /// Maps the position on the transformed board back
+    pub fn revert(&self, pos: Pos, size: Pos) -> Pos {
+        let pos = self.flip(pos, size);
+        match self.transpose {
+            true => Pos::new(pos.y, pos.x),
+            false => pos,
+        }
+    }
+
+    /// Flips the position based on the symmetry
+    fn flip(&self, mut pos: Pos, size: Pos) -> Pos {
+        if self.flip_x {
+            pos.x = size.x - 1 - pos.x;
+        }
+        if self.flip_y {
+            pos.y = size.y - 1 - pos.y;
+        }
+        pos
+    }
+}
@@ -0,0 +1,90 @@
+use std::mem::size_of;
+
+use crate::win_len::WinLen;
+
+/// Default size of the transposition table in MB
+pub const DEFAULT_SIZE: usize = 16;
+
+/// Bound of the score stored in the [`Table`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bound {
+    #[default]
+    Exact,
+    /// Real score is at least the stored score
+    Lower,
+    /// Real score is at most the stored score
+    Upper,
+}
+
+/// Search result of a position stored in the [`Table`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Entry {
+    /// Zobrist hash of the position, 0 means empty entry
+    pub key: u64,
+    pub score: i64,
+    /// Index of the best move in the canonical position
+    pub best: Option<u32>,
+    pub depth: u8,
+    pub bound: Bound,
+}
+
+/// Transposition table storing the searched positions by their hash, so
+/// they don't have to be searched again
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// Size of the table in MB
+    size: usize,
+    entries: Vec<Entry>,
+    /// Board (width, height and win length) the stored positions belong to
+    board: (usize, usize, WinLen),
+}
+
+impl Table {
+    /// Creates new [`Table`] with given size in MB, 0 disables the table.
+    /// Memory is allocated when the table is first used.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            entries: vec![],
+            board: (0, 0, WinLen::default()),
+        }
+    }
+
+    /// Prepares the table for searching positions of the given board,
+    /// entries of the previous board are removed
+    pub fn prepare(&mut self, board: (usize, usize, WinLen)) {
+        let len = self.size * 1024 * 1024 / size_of::<Entry>();
+        if self.entries.len() != len || self.board != board {
+            self.entries = vec![Entry::default(); len];
+            self.board = board;
+        }
+    }
+
+    /// Gets entry of the position with given hash
+    pub fn get(&self, key: u64) -> Option<Entry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let entry = self.entries[(key % self.entries.len() as u64) as usize];
+        (entry.key == key).then_some(entry)
+    }
+
+    /// Stores the entry, entry of other position is always replaced, entry
+    /// of the same position only by deeper search
+    pub fn store(&mut self, entry: Entry) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let id = (entry.key % self.entries.len() as u64) as usize;
+        let old = &mut self.entries[id];
+        if old.key != entry.key || old.depth <= entry.depth {
+            *old = entry;
+        }
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIZE)
+    }
+}
@@ -0,0 +1,244 @@
+use std::{fs, path::Path, str::FromStr};
+
+use termint::{
+    enums::{Color, Modifier},
+    style::Style,
+};
+
+use crate::{cell::Cell, color_mode::ColorMode, error::Error};
+
+/// How the winning sequence is marked on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WinStyle {
+    /// Line drawn using `/ \ - |`, which render the same on all terminals
+    #[default]
+    Line,
+    /// Cells of the sequence filled with solid blocks around the marks
+    Block,
+    /// Background of the cells of the sequence tinted
+    Tint,
+}
+
+impl WinStyle {
+    /// All the win styles
+    pub const ALL: &'static [WinStyle] =
+        &[WinStyle::Line, WinStyle::Block, WinStyle::Tint];
+
+    /// Gets name of the win style
+    pub fn name(&self) -> &'static str {
+        match self {
+            WinStyle::Line => "line",
+            WinStyle::Block => "block",
+            WinStyle::Tint => "tint",
+        }
+    }
+}
+
+impl FromStr for WinStyle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WinStyle::ALL
+            .iter()
+            .find(|w| w.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                Error::parse(format!(
+                    "expected line, block or tint, got '{s}'"
+                ))
+            })
+    }
+}
+
+/// Colors used for rendering the game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub cross: Color,
+    pub circle: Color,
+    pub grid: Color,
+    pub selection: Color,
+    /// Background of the selected cell
+    pub selection_bg: Option<Color>,
+    /// Background of the cells under the win line
+    pub win_bg: Option<Color>,
+    pub win_style: WinStyle,
+    /// Color of the secondary text and borders
+    pub dim: Color,
+    /// Color of the titles and selected items
+    pub accent: Color,
+    pub error: Color,
+    /// Color of the cells, where the player on turn would win
+    pub success: Color,
+    /// Color of the spectator annotations
+    pub highlight: Color,
+    pub mode: ColorMode,
+}
+
+impl Theme {
+    /// Names of the built-in themes
+    pub const PRESETS: &'static [&'static str] = &["default", "ocean", "mono"];
+
+    /// Gets built-in theme with the given name
+    pub fn preset(name: &str) -> Option<Self> {
+        let (cross, circle, grid, selection) = match name {
+            "default" => return Some(Self::default()),
+            "ocean" => (Color::Cyan, Color::Yellow, Color::Blue, Color::White),
+            "mono" => (Color::White, Color::White, Color::Gray, Color::White),
+            _ => return None,
+        };
+        Some(Self {
+            cross,
+            circle,
+            grid,
+            selection,
+            ..Self::default()
+        })
+    }
+
+    /// Loads theme from the given base16 or terminal color scheme file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let text =
+            fs::read_to_string(&path).map_err(|e| Error::load(&path, e))?;
+        Self::from_scheme(&text).map_err(|e| Error::load(&path, e))
+    }
+
+    /// Creates theme from base16 scheme (`base08: "ab4642"`) or terminal
+    /// color scheme (`*.color1: #ab4642`), mapping scheme slots to the
+    /// player, grid and selection colors
+    pub fn from_scheme(text: &str) -> Result<Self, Error> {
+        let mut base = [None; 16];
+        let mut term = [None; 16];
+        for line in text.lines() {
+            let Some((key, val)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().trim_start_matches(['*', '.']).to_lowercase();
+            let Some(color) = parse_hex(val) else {
+                continue;
+            };
+
+            let slot = match key.strip_prefix("base") {
+                Some(id) => usize::from_str_radix(id, 16)
+                    .ok()
+                    .and_then(|id| base.get_mut(id)),
+                None => key
+                    .strip_prefix("color")
+                    .and_then(|id| id.parse::<usize>().ok())
+                    .and_then(|id| term.get_mut(id)),
+            };
+            if let Some(slot) = slot {
+                *slot = Some(color);
+            }
+        }
+
+        let def = Self::default();
+        let theme = match (base[0x08], base[0x0B]) {
+            (Some(red), Some(green)) => Self {
+                cross: green,
+                circle: red,
+                grid: base[0x03].unwrap_or(def.grid),
+                selection: base[0x05].unwrap_or(def.selection),
+                ..def
+            },
+            _ => match (term[1], term[2]) {
+                (Some(red), Some(green)) => Self {
+                    cross: green,
+                    circle: red,
+                    grid: term[8].unwrap_or(def.grid),
+                    selection: term[7].unwrap_or(def.selection),
+                    ..def
+                },
+                _ => {
+                    return Err(Error::parse(
+                        "color scheme doesn't contain red and green",
+                    ))?
+                }
+            },
+        };
+        Ok(theme)
+    }
+
+    /// Gets color of the given cell
+    pub fn cell(&self, cell: Cell) -> Color {
+        match cell {
+            Cell::Cross => self.cross,
+            Cell::Circle => self.circle,
+            Cell::Blocked => self.grid,
+            Cell::Empty => Color::Default,
+        }
+    }
+
+    /// Gets modifier of the given cell's mark, so the marks differ by more
+    /// than just color
+    pub fn weight(&self, cell: Cell) -> u8 {
+        match (self.mode, cell) {
+            (ColorMode::Colorblind, Cell::Cross) => Modifier::BOLD,
+            _ => 0,
+        }
+    }
+
+    /// Gets modifier of the selected cell, selection can't be told apart
+    /// by color in the mono mode
+    pub fn selected(&self) -> u8 {
+        match self.mode {
+            ColorMode::Mono => Modifier::UNDERLINED,
+            _ => 0,
+        }
+    }
+
+    /// Gets style of the cells tinted by the [`WinStyle::Tint`], the win
+    /// background is used when set
+    pub fn win_tint(&self) -> Style {
+        match (self.mode, self.win_bg) {
+            (ColorMode::Mono, _) => Style::new().modifier(Modifier::INVERSED),
+            (_, Some(bg)) => Style::new().bg(bg),
+            (_, None) => Style::new().bg(self.dim),
+        }
+    }
+
+    /// Gets modifier of the most recently placed mark, underline marks the
+    /// selection in the mono mode already
+    pub fn last_move(&self) -> u8 {
+        match self.mode {
+            ColorMode::Mono => Modifier::ITALIC,
+            _ => Modifier::UNDERLINED,
+        }
+    }
+
+    /// Gets style of the highlighted cells
+    pub fn highlighted(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::new().modifier(Modifier::INVERSED),
+            _ => Style::new().bg(self.highlight),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            cross: Color::Green,
+            circle: Color::Red,
+            grid: Color::Gray,
+            selection: Color::Gray,
+            selection_bg: None,
+            win_bg: None,
+            win_style: WinStyle::default(),
+            dim: Color::Gray,
+            accent: Color::Cyan,
+            error: Color::Red,
+            success: Color::Green,
+            highlight: Color::DarkYellow,
+            mode: ColorMode::Full,
+        }
+    }
+}
+
+/// Parses color in hex format (`#rrggbb` or `rrggbb`, optionally quoted)
+pub fn parse_hex(val: &str) -> Option<Color> {
+    let val = val.trim().trim_matches(['"', '\'']).trim_start_matches('#');
+    if val.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(val, 16).ok().map(Color::Hex)
+}
@@ -0,0 +1,61 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    board::Board, cell::Cell, engine::Engine, error::Error, pos::Pos,
+};
+
+/// Frames of the spinner shown while the AI is thinking
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// How long each spinner frame is shown for
+const FRAME: Duration = Duration::from_millis(80);
+
+/// Engine given back with the found move
+type Thought = (Box<dyn Engine>, Result<Option<Pos>, Error>);
+
+/// [`Engine`] searching for the move on a background thread, so the main
+/// loop keeps handling events
+#[derive(Debug)]
+pub struct Thinking {
+    rx: Receiver<Thought>,
+    start: Instant,
+}
+
+impl Thinking {
+    /// Starts searching for the move of the given player on the board
+    pub fn start(
+        mut engine: Box<dyn Engine>,
+        board: Board,
+        player: Cell,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let pos = engine.choose_move(&board, player);
+            _ = tx.send((engine, pos));
+        });
+        Self {
+            rx,
+            start: Instant::now(),
+        }
+    }
+
+    /// Gets the engine back with the found move, when the search finished
+    pub fn result(&self) -> Option<Thought> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Gets the current spinner frame
+    pub fn spinner(&self) -> char {
+        let frame = self.start.elapsed().as_millis() / FRAME.as_millis();
+        SPINNER[frame as usize % SPINNER.len()]
+    }
+
+    /// Gets time remaining until the next spinner frame
+    pub fn remaining(&self) -> Duration {
+        let elapsed = self.start.elapsed().as_millis() % FRAME.as_millis();
+        FRAME.saturating_sub(Duration::from_millis(elapsed as u64))
+    }
+}
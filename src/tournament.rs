@@ -0,0 +1,392 @@
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+use termint::{
+    enums::{Color, Modifier},
+    geometry::Constraint,
+    widgets::{
+        Block, Layout, Paragraph, Spacer, StrSpanExtension, Text, Widget,
+    },
+};
+
+use crate::{
+    error::Error,
+    locale::{tr, trf},
+    paths,
+    theme::Theme,
+};
+
+/// Maximum number of players in the tournament
+const MAX_PLAYERS: usize = 8;
+/// Maximum length of the player name
+const MAX_NAME: usize = 16;
+
+/// Single elimination tournament of local players. Players are seeded in
+/// the given order, top seeds get byes when the bracket isn't full.
+#[derive(Debug, Clone)]
+pub struct Tournament {
+    pub players: Vec<String>,
+    /// Players in the slots of each round, `None` when not decided yet (or
+    /// a bye in the first round)
+    rounds: Vec<Vec<Option<usize>>>,
+    /// Number of drawn games of the current match, sides are swapped after
+    /// each draw
+    draws: usize,
+}
+
+impl Tournament {
+    /// Creates new [`Tournament`] of the given players
+    pub fn new(players: &[String]) -> Result<Self, Error> {
+        if players.len() < 2 || players.len() > MAX_PLAYERS {
+            return Err(Error::parse(format!(
+                "tournament needs 2 to {MAX_PLAYERS} players, got {}",
+                players.len()
+            )));
+        }
+        if let Some(name) = players
+            .iter()
+            .find(|p| p.trim().is_empty() || p.chars().count() > MAX_NAME)
+        {
+            Err(Error::parse(format!(
+                "player name must have 1 to {MAX_NAME} characters, got \
+                 '{name}'"
+            )))?;
+        }
+
+        let size = players.len().next_power_of_two();
+        let first = seeds(size)
+            .into_iter()
+            .map(|s| (s < players.len()).then_some(s))
+            .collect();
+        let mut tournament = Self {
+            players: players.iter().map(|p| p.trim().to_string()).collect(),
+            rounds: vec![first],
+            draws: 0,
+        };
+        while tournament.rounds[tournament.rounds.len() - 1].len() > 1 {
+            let len = tournament.rounds[tournament.rounds.len() - 1].len();
+            tournament.rounds.push(vec![None; len / 2]);
+        }
+
+        // Opponent of the missing player advances
+        for i in 0..size / 2 {
+            if let (Some(p), None) | (None, Some(p)) =
+                (tournament.rounds[0][2 * i], tournament.rounds[0][2 * i + 1])
+            {
+                tournament.rounds[1][i] = Some(p);
+            }
+        }
+        Ok(tournament)
+    }
+
+    /// Starts new tournament of the given players and saves it, saved
+    /// tournament is resumed when no players are given
+    pub fn start(players: &[String]) -> Result<Self, Error> {
+        if players.is_empty() {
+            return Self::load()?.ok_or_else(|| {
+                Error::Unavailable("no tournament to resume".into())
+            });
+        }
+        let tournament = Self::new(players)?;
+        tournament.save()?;
+        Ok(tournament)
+    }
+
+    /// Gets players of the current match, first is the higher seed
+    pub fn pairing(&self) -> Option<(usize, usize)> {
+        let (r, i) = self.current()?;
+        Some((self.rounds[r][2 * i]?, self.rounds[r][2 * i + 1]?))
+    }
+
+    /// Gets players of the current match playing cross and circle
+    pub fn sides(&self) -> Option<(usize, usize)> {
+        let (a, b) = self.pairing()?;
+        match self.draws % 2 {
+            0 => Some((a, b)),
+            _ => Some((b, a)),
+        }
+    }
+
+    /// Gets names of the players of the current match playing cross and
+    /// circle
+    pub fn names(&self) -> Option<(&str, &str)> {
+        let (cross, circle) = self.sides()?;
+        Some((&self.players[cross], &self.players[circle]))
+    }
+
+    /// Records result of the current match game, `None` on draw. Drawn
+    /// match is replayed.
+    pub fn record(&mut self, winner: Option<usize>) {
+        let Some((r, i)) = self.current() else {
+            return;
+        };
+        match winner {
+            Some(winner) => {
+                self.rounds[r + 1][i] = Some(winner);
+                self.draws = 0;
+            }
+            None => self.draws += 1,
+        }
+    }
+
+    /// Gets the tournament winner
+    pub fn champion(&self) -> Option<usize> {
+        self.rounds[self.rounds.len() - 1][0]
+    }
+
+    /// Loads saved tournament, `None` when there is no saved tournament
+    pub fn load() -> Result<Option<Self>, Error> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::load(path, e)),
+        };
+        Self::parse(&text)
+            .map(Some)
+            .ok_or_else(|| Error::load(path, "invalid saved tournament"))
+    }
+
+    /// Saves the tournament, so it can be resumed. Saved tournament is
+    /// removed when it's finished.
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if self.champion().is_some() {
+            return match fs::remove_file(path) {
+                Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(&path, e))?;
+        }
+        fs::write(&path, self.to_text()).map_err(|e| Error::save(&path, e))?;
+        Ok(())
+    }
+
+    /// Renders the bracket with the next match using given theme
+    pub fn render(&self, theme: &Theme) -> Layout {
+        let mut lines: Vec<(Box<dyn Widget>, usize)> = vec![];
+        let current = self.current();
+        for (r, round) in self.rounds.windows(2).enumerate() {
+            let name = self.round_name(r);
+            let len = name.len();
+            lines.push((name.fg(theme.accent).into(), len));
+            for i in 0..round[1].len() {
+                lines.push(self.render_match(theme, r, i, current));
+            }
+            lines.push((Spacer::new().into(), 0));
+        }
+
+        let footer = match (self.champion(), self.names()) {
+            (Some(p), _) => [
+                trf("tournament.won", &[&self.players[p]])
+                    .modifier(Modifier::BOLD),
+                tr("tournament.over-keys").fg(theme.dim),
+            ],
+            (_, Some((cross, circle))) => [
+                trf("tournament.next", &[&cross, &circle]).to_span(),
+                tr("tournament.keys").fg(theme.dim),
+            ],
+            _ => unreachable!("unfinished tournament has a match"),
+        };
+        for line in footer {
+            let len = line.get_text().chars().count();
+            lines.push((line.into(), len));
+        }
+        let width = lines.iter().map(|(_, len)| *len).max();
+        let height = lines.len() + 2;
+
+        let mut block = Block::vertical()
+            .title(tr("tournament.title").fg(theme.accent))
+            .border_color(theme.dim);
+        for (line, _) in lines {
+            block.add_child(line, Constraint::Length(1));
+        }
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(width.unwrap_or(0) + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(height));
+        main
+    }
+
+    /// Renders match of the given round and index
+    fn render_match(
+        &self,
+        theme: &Theme,
+        r: usize,
+        i: usize,
+        current: Option<(usize, usize)>,
+    ) -> (Box<dyn Widget>, usize) {
+        let winner = self.rounds[r + 1][i];
+        let name = |slot: Option<usize>| match slot {
+            Some(p) if winner == Some(p) => {
+                self.players[p].fg(theme.accent).modifier(Modifier::BOLD)
+            }
+            Some(p) if winner.is_some() => self.players[p].fg(theme.dim),
+            Some(p) => self.players[p].fg(Color::Default),
+            None if r == 0 => tr("tournament.bye").fg(theme.dim),
+            None => "?".fg(theme.dim),
+        };
+        let mark = match current == Some((r, i)) {
+            true => ">".fg(theme.accent),
+            false => "-".fg(theme.dim),
+        };
+        let spans = [
+            mark,
+            name(self.rounds[r][2 * i]),
+            tr("vs").fg(theme.dim),
+            name(self.rounds[r][2 * i + 1]),
+        ];
+        // Separator is rendered after the last item as well
+        let len = spans.iter().map(|s| s.get_text().chars().count() + 1);
+        let len = len.sum();
+        let spans = spans.into_iter().map(|s| s.into()).collect();
+        (Box::new(Paragraph::new(spans).separator(" ")), len)
+    }
+
+    /// Gets name of the given round
+    fn round_name(&self, r: usize) -> String {
+        match self.rounds[r + 1].len() {
+            1 => tr("tournament.final").into(),
+            2 => tr("tournament.semifinals").into(),
+            4 => tr("tournament.quarterfinals").into(),
+            _ => trf("tournament.round", &[&(r + 1)]),
+        }
+    }
+
+    /// Gets round and index of the next match, which has both players
+    fn current(&self) -> Option<(usize, usize)> {
+        for (r, round) in self.rounds.windows(2).enumerate() {
+            for (i, winner) in round[1].iter().enumerate() {
+                let (a, b) = (round[0][2 * i], round[0][2 * i + 1]);
+                if winner.is_none() && a.is_some() && b.is_some() {
+                    return Some((r, i));
+                }
+            }
+        }
+        None
+    }
+
+    /// Parses tournament from the saved text, `None` when it's invalid
+    fn parse(text: &str) -> Option<Self> {
+        let mut players = vec![];
+        let mut rounds = vec![];
+        let mut draws = 0;
+        for (key, val) in text.lines().filter_map(|l| l.split_once('=')) {
+            let val = val.trim();
+            match key.trim() {
+                "player" => players.push(val.to_string()),
+                "round" => rounds.push(
+                    val.split_whitespace()
+                        .map(|s| match s {
+                            "-" => Some(None),
+                            s => s.parse().ok().map(Some),
+                        })
+                        .collect::<Option<Vec<_>>>()?,
+                ),
+                "draws" => draws = val.parse().ok()?,
+                _ => {}
+            }
+        }
+
+        // Replays the saved winners on a new bracket, so only the winners
+        // of the played matches are accepted
+        let mut tournament = Self::new(&players).ok()?;
+        if rounds.len() != tournament.rounds.len()
+            || rounds[0] != tournament.rounds[0]
+        {
+            return None;
+        }
+        for (r, round) in rounds.iter().enumerate().skip(1) {
+            if round.len() != tournament.rounds[r].len() {
+                return None;
+            }
+            for (i, winner) in round.iter().enumerate() {
+                let prev = &tournament.rounds[r - 1];
+                match (winner, tournament.rounds[r][i]) {
+                    (None, None) => {}
+                    (Some(p), None)
+                        if prev[2 * i] == Some(*p)
+                            || prev[2 * i + 1] == Some(*p) =>
+                    {
+                        tournament.rounds[r][i] = Some(*p)
+                    }
+                    (winner, bye) if *winner == bye => {}
+                    _ => return None,
+                }
+            }
+        }
+        tournament.draws = draws;
+        Some(tournament)
+    }
+
+    /// Gets text the tournament is saved as
+    fn to_text(&self) -> String {
+        let mut res = String::new();
+        for player in &self.players {
+            res.push_str(&format!("player = {player}\n"));
+        }
+        for round in &self.rounds {
+            let slots: Vec<_> = round
+                .iter()
+                .map(|s| s.map(|p| p.to_string()).unwrap_or("-".into()))
+                .collect();
+            res.push_str(&format!("round = {}\n", slots.join(" ")));
+        }
+        res.push_str(&format!("draws = {}\n", self.draws));
+        res
+    }
+
+    /// Gets path to the saved tournament file
+    fn path() -> Option<PathBuf> {
+        Some(paths::data_dir()?.join("tournament"))
+    }
+}
+
+/// Gets seeds in the order of the bracket slots, so the top seeds meet as
+/// late as possible
+fn seeds(size: usize) -> Vec<usize> {
+    let mut seeds = vec![0];
+    while seeds.len() < size {
+        let len = seeds.len() * 2;
+        seeds = seeds.iter().flat_map(|s| [*s, len - 1 - s]).collect();
+    }
+    seeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("P{i}")).collect()
+    }
+
+    #[test]
+    fn top_seeds_get_byes() {
+        let tournament = Tournament::new(&players(6)).unwrap();
+        assert_eq!(tournament.rounds[1], [Some(0), None, Some(1), None]);
+        assert_eq!(tournament.pairing(), Some((3, 4)));
+    }
+
+    #[test]
+    fn saved_tournament_is_resumed() {
+        let mut tournament = Tournament::new(&players(3)).unwrap();
+        tournament.record(None);
+        assert_eq!(tournament.sides(), Some((2, 1)));
+        tournament.record(Some(2));
+        assert_eq!(tournament.pairing(), Some((0, 2)));
+
+        let text = tournament.to_text();
+        let resumed = Tournament::parse(&text).unwrap();
+        assert_eq!(resumed.rounds, tournament.rounds);
+        // Winner must come from the match
+        let invalid = text.replace("round = -\n", "round = 1\n");
+        assert!(Tournament::parse(&invalid).is_none());
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+use termint::{enums::Color, widgets::StrSpanExtension};
+
+use crate::{
+    ai::{Ai, Difficulty},
+    arena::ArenaSize,
+    board::Board,
+    cell::Cell,
+    error::Error,
+    game::Game,
+    mode::Mode,
+    rng::Rng,
+    weights::{self, Weights},
+};
+
+/// Randomness of the opening moves, so the games differ
+const TEMPERATURE: f64 = 0.5;
+
+/// Number of rounds played when not given
+pub const DEFAULT_ROUNDS: usize = 20;
+/// Number of games on each board size in a round when not given
+pub const DEFAULT_GAMES: usize = 10;
+/// Sizes played when none are given, the standard board is left out as
+/// the medium AI already plays it well
+pub const DEFAULT_SIZES: &[ArenaSize] = &[(7, 7, 4), (10, 10, 5), (15, 15, 5)];
+
+/// Tunes the weights of the medium AI by hill climbing. Each round the
+/// randomly changed weights play against the best weights so far and
+/// replace them, when they score more than half of the points. The best
+/// weights are saved to the given file, the AI loads them from the default
+/// one.
+pub fn run(
+    rounds: usize,
+    games: usize,
+    sizes: &[ArenaSize],
+    save: Option<&str>,
+) -> Result<(), Error> {
+    let path = match save {
+        Some(path) => PathBuf::from(path),
+        None => Weights::path().ok_or_else(|| Error::no_dir("data"))?,
+    };
+    let mut best = weights::current().clone();
+    let mut rng = Rng::from_time();
+    let mut improved = false;
+
+    println!(
+        "{} {:>8} {:>10}",
+        format!("{:<8}", "round").fg(Color::Green),
+        "score",
+        "result"
+    );
+    for round in 1..=rounds {
+        let candidate = best.mutate(&mut rng);
+        let score = play_match(&candidate, &best, games, sizes, &mut rng)?;
+        let accepted = score > 0.5;
+        println!(
+            "{:<8} {:>7.1}% {:>10}",
+            round,
+            score * 100.,
+            if accepted { "accepted" } else { "kept" }
+        );
+        if accepted {
+            best = candidate;
+            best.save(&path)?;
+            improved = true;
+        }
+    }
+    match improved {
+        true => println!("\n{best}Weights saved to '{}'", path.display()),
+        false => println!("\nNo better weights found"),
+    }
+    Ok(())
+}
+
+/// Plays given number of games on each board size between the AIs with the
+/// given weights, they alternate who starts. Returns the share of points
+/// of the first weights, draw counts as half of the point.
+fn play_match(
+    first: &Weights,
+    second: &Weights,
+    games: usize,
+    sizes: &[ArenaSize],
+    rng: &mut Rng,
+) -> Result<f64, Error> {
+    let mut points = 0;
+    for (w, h, win) in sizes {
+        for game in 0..games {
+            let mut players = [first, second].map(|weights| {
+                Ai::new(Difficulty::Medium)
+                    .temperature(TEMPERATURE)
+                    .seed(rng.next_u64())
+                    .weights(weights.clone())
+            });
+            let board = Board::new(*w, *h, *win);
+            points += match play(&mut players, board, game % 2)? {
+                Some(0) => 2,
+                Some(_) => 0,
+                None => 1,
+            };
+        }
+    }
+    let total = games * sizes.len() * 2;
+    Ok(points as f64 / total.max(1) as f64)
+}
+
+/// Plays single game, where the AI with the given index starts. Returns
+/// index of the winner, [`None`] on draw.
+fn play(
+    players: &mut [Ai; 2],
+    board: Board,
+    first: usize,
+) -> Result<Option<usize>, Error> {
+    let mut game = Game::new(board, Mode::Normal);
+    let mut turn = first;
+    loop {
+        let player = game.current_player();
+        let Some(pos) = players[turn].best_move(&game.board, player) else {
+            return Ok(None);
+        };
+        game.play(pos)?;
+        match game.result() {
+            Some(Cell::Empty) => return Ok(None),
+            Some(_) => return Ok(Some(turn)),
+            None => turn = 1 - turn,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_without_defence_lose() {
+        let mut rng = Rng::new(3);
+        let passive = Weights {
+            defend: 0,
+            ..Weights::default()
+        };
+        let best = Weights::default();
+        let score = play_match(&passive, &best, 2, &[(7, 7, 4)], &mut rng);
+        assert!(score.unwrap() < 0.5);
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::{error::Error, paths, rng::Rng};
+
+/// Maximum value of the weights, so the scores can't overflow
+const MAX: i64 = 1_000_000_000;
+
+static WEIGHTS: OnceLock<Weights> = OnceLock::new();
+
+/// Weights of the heuristic scoring the cells by the lines going through
+/// them, used to choose the moves of the medium AI and to order the moves
+/// of the hard AI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Weights {
+    /// Score of the line by the number of its marks starting with one,
+    /// longer lines use the last score
+    pub line: Vec<i64>,
+    /// Multiplier of the line score by the number of its open ends
+    pub open: [i64; 3],
+    /// Percentage of the opponent's score added to the cell score
+    pub defend: i64,
+}
+
+impl Weights {
+    /// Gets score of the line with given number of marks
+    pub fn line(&self, len: usize) -> i64 {
+        let id = len.clamp(1, self.line.len()) - 1;
+        self.line.get(id).copied().unwrap_or_default()
+    }
+
+    /// Creates copy of the weights with one of them randomly changed
+    pub fn mutate(&self, rng: &mut Rng) -> Self {
+        let mut weights = self.clone();
+        // Changes by up to 40 % in either direction
+        let scale = |val: i64, rng: &mut Rng| {
+            let factor = 0.6 + rng.unit() * 0.8;
+            ((val as f64 * factor).round() as i64).clamp(1, MAX)
+        };
+        let len = self.line.len();
+        match rng.range(len + 3) {
+            i if i < len => weights.line[i] = scale(self.line[i], rng),
+            i if i < len + 2 => {
+                let id = i - len + 1;
+                weights.open[id] = scale(self.open[id], rng);
+            }
+            _ => weights.defend = scale(self.defend, rng).min(200),
+        }
+        weights
+    }
+
+    /// Loads weights from the given file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text =
+            fs::read_to_string(path).map_err(|e| Error::load(path, e))?;
+        text.parse().map_err(|e| Error::load(path, e))
+    }
+
+    /// Loads the tuned weights, default weights are used when they weren't
+    /// tuned yet
+    pub fn load_tuned() -> Result<Self, Error> {
+        match Weights::path() {
+            Some(path) if path.exists() => Weights::load(path),
+            _ => Ok(Weights::default()),
+        }
+    }
+
+    /// Saves weights to the given file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(path, e))?;
+        }
+        fs::write(path, self.to_string()).map_err(|e| Error::save(path, e))?;
+        Ok(())
+    }
+
+    /// Gets path of the tuned weights
+    pub fn path() -> Option<PathBuf> {
+        Some(paths::data_dir()?.join("weights.txt"))
+    }
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            line: (1..=9).map(|len| 10_i64.pow(len)).collect(),
+            open: [1, 2, 3],
+            defend: 90,
+        }
+    }
+}
+
+impl FromStr for Weights {
+    type Err = Error;
+
+    /// Parses weights from lines with the key and the values, like
+    /// `open = 1 2 3`. Missing weights have their default value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = Weights::default();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, vals) = line.split_once('=').ok_or_else(|| {
+                Error::parse(format!("expected key = value, got '{line}'"))
+            })?;
+            let vals = vals
+                .split_whitespace()
+                .map(|v| v.parse().ok().filter(|v| (0..=MAX).contains(v)))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    Error::parse(format!("invalid weights in '{line}'"))
+                })?;
+            match (key.trim(), vals.as_slice()) {
+                ("line", [_, ..]) => weights.line = vals,
+                ("open", [none, one, two]) => {
+                    weights.open = [*none, *one, *two]
+                }
+                ("defend", [defend]) => weights.defend = *defend,
+                _ => {
+                    Err(Error::parse(format!("invalid weights in '{line}'")))?
+                }
+            }
+        }
+        Ok(weights)
+    }
+}
+
+impl Display for Weights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line: Vec<_> = self.line.iter().map(|w| w.to_string()).collect();
+        writeln!(f, "line = {}", line.join(" "))?;
+        let [none, one, two] = self.open;
+        writeln!(f, "open = {none} {one} {two}")?;
+        writeln!(f, "defend = {}", self.defend)
+    }
+}
+
+/// Sets the weights used by the AI, they can be set only once before
+/// they're first used
+pub fn set(weights: Weights) {
+    _ = WEIGHTS.set(weights);
+}
+
+/// Gets the weights used by the AI, default weights when none were set
+pub fn current() -> &'static Weights {
+    WEIGHTS.get_or_init(Weights::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_weights() {
+        let weights: Weights =
+            "line = 5 40 300\n# tuned\ndefend = 80\n".parse().unwrap();
+        assert_eq!(weights.line(0), 5);
+        assert_eq!(weights.line(3), 300);
+        assert_eq!(weights.line(7), 300);
+        assert_eq!(weights.open, Weights::default().open);
+        assert_eq!(weights.to_string().parse::<Weights>().unwrap(), weights);
+        assert!("open = 1 2".parse::<Weights>().is_err());
+        assert!("defend = -1".parse::<Weights>().is_err());
+
+        let mut rng = Rng::new(1);
+        let changed = (0..20)
+            .map(|_| weights.mutate(&mut rng))
+            .filter(|w| *w != weights)
+            .count();
+        assert!(changed > 10);
+    }
+}
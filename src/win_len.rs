@@ -0,0 +1,103 @@
+use std::cmp::min;
+
+use crate::{error::Error, locale::trf};
+
+/// Length of the line needed to win in each direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WinLen {
+    pub rows: usize,
+    pub cols: usize,
+    /// Length in both the diagonal directions
+    pub diag: usize,
+}
+
+impl WinLen {
+    /// Creates new [`WinLen`] with the same length in every direction
+    pub fn new(len: usize) -> Self {
+        Self {
+            rows: len,
+            cols: len,
+            diag: len,
+        }
+    }
+
+    /// Overrides lengths of the directions, which are given
+    pub fn with(
+        mut self,
+        rows: Option<usize>,
+        cols: Option<usize>,
+        diag: Option<usize>,
+    ) -> Self {
+        self.rows = rows.unwrap_or(self.rows);
+        self.cols = cols.unwrap_or(self.cols);
+        self.diag = diag.unwrap_or(self.diag);
+        self
+    }
+
+    /// Gets length needed in the given direction
+    pub fn get(&self, (dx, dy): (isize, isize)) -> usize {
+        match (dx, dy) {
+            (_, 0) => self.rows,
+            (0, _) => self.cols,
+            _ => self.diag,
+        }
+    }
+
+    /// Gets the length, when it's the same in every direction
+    pub fn uniform(&self) -> Option<usize> {
+        (self.rows == self.cols && self.cols == self.diag).then_some(self.rows)
+    }
+
+    /// Checks whether a line can be completed in any direction on the
+    /// board of given size
+    pub fn fits(&self, width: usize, height: usize) -> bool {
+        self.rows <= width
+            || self.cols <= height
+            || self.diag <= min(width, height)
+    }
+
+    /// Checks whether the game on the board of given size can be won and
+    /// the lengths are supported
+    pub fn check(&self, width: usize, height: usize) -> Result<(), Error> {
+        if min(self.rows, min(self.cols, self.diag)) < 3 {
+            Err(Error::parse("minimum supported win length is 3"))?;
+        }
+        if !self.fits(width, height) {
+            Err(Error::parse(format!(
+                "{} can't be reached on {width}x{height} board",
+                self.describe()
+            )))?;
+        }
+        Ok(())
+    }
+
+    /// Gets description of the lengths (e.g. `5 in a row in any direction`)
+    pub fn describe(&self) -> String {
+        match self.uniform() {
+            Some(len) => trf("win-len.uniform", &[&len]),
+            None => trf(
+                "win-len.directions",
+                &[&self.rows, &self.cols, &self.diag],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_when_any_direction_fits() {
+        assert!(WinLen::new(3).fits(3, 3));
+        assert!(!WinLen::new(10).fits(3, 3));
+        // Column is long enough, rows aren't
+        assert!(WinLen::new(5).fits(3, 10));
+        assert!(!WinLen::new(5).with(None, Some(11), None).fits(3, 10));
+        assert!(WinLen::new(9).with(None, None, Some(3)).fits(3, 3));
+        assert!(WinLen::new(3)
+            .with(None, Some(2), None)
+            .check(3, 3)
+            .is_err());
+    }
+}
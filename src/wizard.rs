@@ -0,0 +1,145 @@
+use std::fs;
+
+use termint::{
+    enums::Color,
+    geometry::Constraint,
+    widgets::{Block, Layout, Spacer, StrSpanExtension},
+};
+
+use crate::{
+    config::Config, controls::Controls, error::Error, locale::tr, theme::Theme,
+};
+
+/// Width of the wizard content (without borders)
+const WIDTH: usize = 44;
+/// Board sizes offered by the wizard, `None` fills the screen
+const SIZES: &[Option<(usize, usize)>] = &[
+    None,
+    Some((3, 3)),
+    Some((5, 5)),
+    Some((7, 7)),
+    Some((15, 15)),
+];
+
+/// First-run setup, which writes the initial config file
+#[derive(Debug, Clone, Default)]
+pub struct Wizard {
+    selected: usize,
+    controls: usize,
+    theme: usize,
+    size: usize,
+    stats: bool,
+}
+
+impl Wizard {
+    /// Number of the options
+    const OPTIONS: usize = 4;
+
+    /// Creates new [`Wizard`] with default values selected
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves selection to the previous option
+    pub fn up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection to the next option
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1).min(Self::OPTIONS - 1);
+    }
+
+    /// Changes value of the selected option by given offset
+    pub fn change(&mut self, offset: isize) {
+        let shift = |val: usize, len: usize| {
+            (val as isize + offset).rem_euclid(len as isize) as usize
+        };
+        match self.selected {
+            0 => self.controls = shift(self.controls, Controls::ALL.len()),
+            1 => self.theme = shift(self.theme, Theme::PRESETS.len()),
+            2 => self.size = shift(self.size, SIZES.len()),
+            _ => self.stats = !self.stats,
+        }
+    }
+
+    /// Writes the chosen values to the config file
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Config::path().ok_or_else(|| Error::no_dir("config"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::save(&path, e))?;
+        }
+        fs::write(&path, self.config_text())
+            .map_err(|e| Error::save(&path, e))?;
+        Ok(())
+    }
+
+    /// Renders the wizard using the given theme
+    pub fn render(&self, theme: &Theme) -> Layout {
+        let mut block = Block::vertical()
+            .title(tr("wizard.title").fg(theme.accent))
+            .border_color(theme.dim);
+        block
+            .add_child(tr("wizard.text").fg(theme.dim), Constraint::Length(1));
+        block.add_child(Spacer::new(), Constraint::Length(1));
+
+        let size = match SIZES[self.size] {
+            Some(_) => self.size_name(),
+            None => tr("wizard.auto").to_string(),
+        };
+        let stats = match self.stats {
+            true => tr("wizard.on"),
+            false => tr("wizard.off"),
+        };
+        let options = [
+            (tr("wizard.controls"), Controls::ALL[self.controls].name()),
+            (tr("wizard.theme"), Theme::PRESETS[self.theme]),
+            (tr("wizard.size"), size.as_str()),
+            (tr("wizard.stats"), stats),
+        ];
+        for (i, (name, val)) in options.iter().enumerate() {
+            let fg = match i == self.selected {
+                true => theme.accent,
+                false => Color::Default,
+            };
+            let mut item = Layout::horizontal();
+            item.add_child(name.fg(fg), Constraint::Min(0));
+            item.add_child(Spacer::new(), Constraint::Fill);
+            item.add_child(format!("< {val} >").fg(fg), Constraint::Min(0));
+            block.add_child(item, Constraint::Length(1));
+        }
+
+        block.add_child(Spacer::new(), Constraint::Length(1));
+        block
+            .add_child(tr("wizard.keys").fg(theme.dim), Constraint::Length(1));
+
+        let mut layout = Layout::horizontal().center();
+        layout.add_child(block, Constraint::Length(WIDTH + 2));
+        let mut main = Layout::vertical().center();
+        main.add_child(layout, Constraint::Length(Self::OPTIONS + 6));
+        main
+    }
+
+    /// Gets config file content with the chosen values
+    fn config_text(&self) -> String {
+        format!(
+            "# Generated by the first-run setup\n\n\
+             [controls]\npreset = {}\n\n\
+             [theme]\npreset = {}\n\n\
+             [board]\nsize = {}\n\n\
+             [stats]\nenabled = {}\n",
+            Controls::ALL[self.controls].name(),
+            Theme::PRESETS[self.theme],
+            self.size_name(),
+            self.stats,
+        )
+    }
+
+    /// Gets name of the chosen board size
+    fn size_name(&self) -> String {
+        match SIZES[self.size] {
+            Some((w, h)) => format!("{w}x{h}"),
+            None => "auto".to_string(),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+/// Density the board is rendered with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Zoom {
+    /// Single character per cell without the grid
+    Compact,
+    #[default]
+    Normal,
+    /// Cells of 5x3 characters
+    Large,
+}
+
+impl Zoom {
+    /// Gets name of the zoom level
+    pub fn name(&self) -> &'static str {
+        match self {
+            Zoom::Compact => "compact",
+            Zoom::Normal => "normal",
+            Zoom::Large => "large",
+        }
+    }
+
+    /// Gets the next zoom level, compact follows the large one
+    pub fn next(&self) -> Zoom {
+        match self {
+            Zoom::Compact => Zoom::Normal,
+            Zoom::Normal => Zoom::Large,
+            Zoom::Large => Zoom::Compact,
+        }
+    }
+
+    /// Gets distance between the cells in columns and rows, it includes
+    /// single border line except in the compact zoom
+    pub fn cell(&self) -> (usize, usize) {
+        match self {
+            Zoom::Compact => (1, 1),
+            Zoom::Normal => (4, 2),
+            Zoom::Large => (6, 4),
+        }
+    }
+
+    /// Gets width of the grid lines
+    pub fn border(&self) -> usize {
+        match self {
+            Zoom::Compact => 0,
+            _ => 1,
+        }
+    }
+}
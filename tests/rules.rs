@@ -0,0 +1,120 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Plays the game in the accessible mode with given arguments, the moves
+/// are typed one per line. Returns the output and the exit code.
+fn play(args: &[&str], moves: &[&str]) -> (String, i32) {
+    // Config and stats of the user aren't used
+    let home = env::temp_dir().join("tictactoe-tests");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tictactoe"))
+        .arg("--accessible")
+        .args(args)
+        .env("TICTACTOE_CONFIG", &home)
+        .env("TICTACTOE_DATA", &home)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("cannot run tictactoe");
+
+    let mut stdin = child.stdin.take().unwrap();
+    for pos in moves {
+        writeln!(stdin, "{pos}").unwrap();
+    }
+    drop(stdin);
+
+    let out = child.wait_with_output().unwrap();
+    let text = String::from_utf8_lossy(&out.stdout).to_string();
+    (text, out.status.code().unwrap_or(-1))
+}
+
+#[test]
+fn row_column_and_diagonal_win() {
+    let (out, code) = play(&[], &["a1", "a2", "b1", "b2", "c1"]);
+    assert!(out.contains("X wins!"), "{out}");
+    assert_eq!(code, 0);
+
+    let (out, _) = play(&[], &["a1", "b1", "b2", "c1", "a3", "a2", "c3"]);
+    assert!(out.contains("X wins!"), "{out}");
+    let (out, _) = play(&[], &["a1", "b1", "b2", "c1", "c3"]);
+    assert!(out.contains("X wins!"), "{out}");
+}
+
+#[test]
+fn occupied_cell_is_rejected() {
+    let (out, _) = play(&[], &["b2", "b2", "a1"]);
+    assert!(out.contains("Can't play b2, cell is occupied."), "{out}");
+    assert!(out.contains("O plays a1."), "{out}");
+}
+
+#[test]
+fn draw_is_announced_before_board_is_full() {
+    let (out, _) = play(&[], &["c2", "a2", "a3", "c1", "b1", "b3"]);
+    assert!(out.contains("Draw."), "{out}");
+    assert!(!out.contains("wins!"), "{out}");
+}
+
+#[test]
+fn moves_after_the_end_are_rejected() {
+    let (out, _) = play(&[], &["a1", "a2", "b1", "b2", "c1", "c3"]);
+    assert!(out.contains("Can't play c3, game ended."), "{out}");
+
+    let (out, _) = play(&[], &["a1", "a2", "b1", "b2", "c1", "restart", "c3"]);
+    assert_eq!(out.matches("New game").count(), 2, "{out}");
+    assert!(out.contains("X plays c3."), "{out}");
+}
+
+#[test]
+fn narrow_board_wins_only_along_long_side() {
+    let args = ["-s", "3", "10", "-w", "5"];
+    // Rows are shorter than the win length
+    let (out, _) = play(&args, &["a1", "a2", "b1", "b2", "c1", "c2"]);
+    assert!(!out.contains("wins!"), "{out}");
+
+    let moves = ["a1", "b1", "a2", "b2", "a3", "b3", "a4", "b4", "a5"];
+    let (out, _) = play(&args, &moves);
+    assert!(out.contains("X wins!"), "{out}");
+}
+
+#[test]
+fn invalid_arguments_exit_with_code() {
+    let (out, code) = play(&["-s", "2", "3"], &[]);
+    assert!(out.contains("minimum supported size is 3"), "{out}");
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn missing_file_exits_with_code() {
+    let (out, code) = play(&["--lang", "xx"], &[]);
+    assert!(out.contains("cannot load"), "{out}");
+    assert_eq!(code, 6);
+}
+
+#[test]
+fn unreachable_win_length_is_rejected() {
+    let (out, code) = play(&["-s", "3", "3", "-w", "10"], &[]);
+    assert!(out.contains("can't be reached on 3x3 board"), "{out}");
+    assert_eq!(code, 2);
+}
+
+#[test]
+fn cells_follow_numbering() {
+    let args = ["--numbering", "bottom-left", "--rtl"];
+    let (out, _) = play(&args, &["a1", "c3"]);
+    assert!(out.contains("X plays a1."), "{out}");
+    // a1 is the bottom right corner, row numbers are on the right
+    assert!(out.contains("O . . 3\n"), "{out}");
+    assert!(out.contains(". . X 1\n"), "{out}");
+}
+
+#[test]
+fn preset_sets_board() {
+    let (out, _) = play(&["--preset", "gomoku"], &[]);
+    assert!(out.contains("New game on 15x15 board, 5 in a row"), "{out}");
+
+    let (out, code) = play(&["--preset", "tiny"], &[]);
+    assert!(out.contains("unknown preset 'tiny'"), "{out}");
+    assert_eq!(code, 2);
+}